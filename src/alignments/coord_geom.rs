@@ -7,7 +7,11 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::Point2D;
+use crate::error::LandXMLError;
+use crate::models::{Extensions, Point2D};
+use crate::units::Angle;
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
 
 /// Rotation direction for curves and spirals
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -18,10 +22,32 @@ pub enum RotationDirection {
     Ccw,
 }
 
+impl RotationDirection {
+    /// LandXMLのrot属性値から解析
+    pub fn from_landxml(value: &str) -> Result<Self, LandXMLError> {
+        match value.trim() {
+            "cw" => Ok(Self::Cw),
+            "ccw" => Ok(Self::Ccw),
+            _ => Err(LandXMLError::InvalidFormat {
+                message: format!("Invalid rotation direction: {}", value),
+            }),
+        }
+    }
+
+    /// LandXMLのrot属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::Cw => "cw",
+            Self::Ccw => "ccw",
+        }
+    }
+}
+
 /// Spiral (transition curve) type
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SpiralType {
     /// Clothoid curve (Cornu spiral) - most common in road design
+    #[default]
     Clothoid,
     /// Bloss curve
     Bloss,
@@ -41,9 +67,35 @@ pub enum SpiralType {
     Other(String),
 }
 
-impl Default for SpiralType {
-    fn default() -> Self {
-        SpiralType::Clothoid
+impl SpiralType {
+    /// LandXMLのspiType属性値から解析
+    pub fn from_landxml(value: &str) -> Self {
+        match value.trim() {
+            "clothoid" => Self::Clothoid,
+            "bloss" => Self::Bloss,
+            "cubic" => Self::Cubic,
+            "cubicParabola" => Self::CubicParabola,
+            "sinusoid" => Self::Sinusoid,
+            "cosine" => Self::Cosinoid,
+            "biquadraticParabola" => Self::BiquadraticParabola,
+            "radioid" => Self::Radioid,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// LandXMLのspiType属性値を取得
+    pub fn as_landxml_str(&self) -> &str {
+        match self {
+            Self::Clothoid => "clothoid",
+            Self::Bloss => "bloss",
+            Self::Cubic => "cubic",
+            Self::CubicParabola => "cubicParabola",
+            Self::Sinusoid => "sinusoid",
+            Self::Cosinoid => "cosine",
+            Self::BiquadraticParabola => "biquadraticParabola",
+            Self::Radioid => "radioid",
+            Self::Other(value) => value,
+        }
     }
 }
 
@@ -56,6 +108,18 @@ pub struct Line {
     pub start: Point2D,
     /// End point coordinates
     pub end: Point2D,
+    /// Start point elevation (third value of Start)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_elevation: Option<f64>,
+    /// End point elevation (third value of End)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_elevation: Option<f64>,
+    /// Start point name (e.g. "BP", "KA1-1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_name: Option<String>,
+    /// End point name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_name: Option<String>,
 
     /// Computed or measured length (meters)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -76,6 +140,10 @@ pub struct Line {
     /// State (existing, proposed, asBuilt, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
+
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
 }
 
 /// Circular arc (LandXML Curve element)
@@ -87,6 +155,18 @@ pub struct Curve {
     pub start: Point2D,
     /// End point coordinates
     pub end: Point2D,
+    /// Start point elevation (third value of Start)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_elevation: Option<f64>,
+    /// End point elevation (third value of End)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_elevation: Option<f64>,
+    /// Start point name (e.g. "BP", "KA1-1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_name: Option<String>,
+    /// End point name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_name: Option<String>,
     /// Center point coordinates
     #[serde(skip_serializing_if = "Option::is_none")]
     pub center: Option<Point2D>,
@@ -138,6 +218,10 @@ pub struct Curve {
     /// State (existing, proposed, asBuilt, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
+
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
 }
 
 /// Spiral / Transition curve (LandXML Spiral element)
@@ -149,6 +233,18 @@ pub struct Spiral {
     pub start: Point2D,
     /// End point coordinates
     pub end: Point2D,
+    /// Start point elevation (third value of Start)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_elevation: Option<f64>,
+    /// End point elevation (third value of End)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_elevation: Option<f64>,
+    /// Start point name (e.g. "BP", "KA1-1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_name: Option<String>,
+    /// End point name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_name: Option<String>,
     /// Point of intersection (PI)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pi: Option<Point2D>,
@@ -170,6 +266,10 @@ pub struct Spiral {
     /// Clothoid parameter A (A² = R × L)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub constant: Option<f64>,
+    /// Whether `constant` came from the standard `constant` attribute
+    /// (otherwise it is written as a J-LandXML Feature "A")
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub constant_attribute: bool,
 
     /// Tangent angle (theta) in radians
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -209,6 +309,10 @@ pub struct Spiral {
     /// State (existing, proposed, asBuilt, etc.)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
+
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
 }
 
 /// Geometry element (horizontal alignment component)
@@ -219,6 +323,91 @@ pub enum GeomElement {
     Spiral(Spiral),
 }
 
+impl GeomElement {
+    /// 始点座標を取得
+    pub fn start(&self) -> Point2D {
+        match self {
+            Self::Line(e) => e.start,
+            Self::Curve(e) => e.start,
+            Self::Spiral(e) => e.start,
+        }
+    }
+
+    /// 終点座標を取得
+    pub fn end(&self) -> Point2D {
+        match self {
+            Self::Line(e) => e.end,
+            Self::Curve(e) => e.end,
+            Self::Spiral(e) => e.end,
+        }
+    }
+
+    /// 要素長（メートル）を取得
+    ///
+    /// 属性値がない場合は座標から計算します（円弧は中心点が必要）。
+    pub fn length(&self) -> Option<f64> {
+        match self {
+            Self::Line(e) => Some(
+                e.length
+                    .unwrap_or_else(|| (e.end.x - e.start.x).hypot(e.end.y - e.start.y)),
+            ),
+            Self::Curve(e) => e
+                .length
+                .or_else(|| e.radius.zip(e.delta).map(|(r, d)| r * d.abs()))
+                .or_else(|| {
+                    let center = e.center?;
+                    // 方向角は時計回りが正のため、右回りの中心角は終点と始点の方向角の差
+                    let (start, end) = (
+                        Angle::azimuth(center, e.start),
+                        Angle::azimuth(center, e.end),
+                    );
+                    let angle = match e.rot {
+                        RotationDirection::Cw => end - start,
+                        RotationDirection::Ccw => start - end,
+                    };
+                    let radius = (e.start.x - center.x).hypot(e.start.y - center.y);
+                    Some(radius * angle.normalized().radians())
+                }),
+            Self::Spiral(e) => Some(e.length),
+        }
+    }
+
+    /// 始点における接線の方向角を取得
+    ///
+    /// 属性値がない場合は座標から計算します（円弧は中心点が必要）。
+    pub fn start_direction(&self) -> Option<Angle> {
+        match self {
+            Self::Line(e) => Some(
+                e.dir
+                    .map(Angle::from_radians)
+                    .unwrap_or_else(|| Angle::azimuth(e.start, e.end)),
+            ),
+            Self::Curve(e) => e.dir_start.map(Angle::from_radians).or_else(|| {
+                // 接線は中心から始点への方向に直交する
+                let radial = Angle::azimuth(e.center?, e.start);
+                let quarter = Angle::from_degrees(90.0);
+                Some(
+                    match e.rot {
+                        RotationDirection::Cw => radial + quarter,
+                        RotationDirection::Ccw => radial - quarter,
+                    }
+                    .normalized(),
+                )
+            }),
+            Self::Spiral(e) => e.dir_start.map(Angle::from_radians),
+        }
+    }
+
+    /// 始点・終点の標高を変更可能な参照で取得
+    pub fn elevations_mut(&mut self) -> [&mut Option<f64>; 2] {
+        match self {
+            Self::Line(e) => [&mut e.start_elevation, &mut e.end_elevation],
+            Self::Curve(e) => [&mut e.start_elevation, &mut e.end_elevation],
+            Self::Spiral(e) => [&mut e.start_elevation, &mut e.end_elevation],
+        }
+    }
+}
+
 /// Coordinate geometry (horizontal alignment)
 ///
 /// Contains a sequence of Line, Curve, and Spiral elements
@@ -237,6 +426,383 @@ pub struct CoordGeom {
     /// State
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<String>,
+
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+// ============================================================================
+// XML解析
+// ============================================================================
+
+/// Start/End要素を解析（座標、標高、点名）
+fn parse_end_point(
+    element: &XmlElement,
+    name: &str,
+) -> Result<(Point2D, Option<f64>, Option<String>), LandXMLError> {
+    let child = element.required_child(name)?;
+    let (point, elevation) = child.point()?;
+    Ok((point, elevation, child.attr_string("name")))
+}
+
+/// 任意の点要素（Center, PI）を解析
+fn parse_optional_point(element: &XmlElement, name: &str) -> Result<Option<Point2D>, LandXMLError> {
+    element
+        .child(name)
+        .map(|child| child.point().map(|(point, _)| point))
+        .transpose()
+}
+
+fn parse_rot(element: &XmlElement) -> Result<RotationDirection, LandXMLError> {
+    let rot = element
+        .attr("rot")
+        .ok_or_else(|| LandXMLError::MissingElement {
+            element: format!("{}@rot", element.name),
+        })?;
+    RotationDirection::from_landxml(rot)
+}
+
+impl Line {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let (start, start_elevation, start_name) = parse_end_point(element, "Start")?;
+        let (end, end_elevation, end_name) = parse_end_point(element, "End")?;
+        Ok(Self {
+            start,
+            end,
+            start_elevation,
+            end_elevation,
+            start_name,
+            end_name,
+            length: element.attr_f64("length"),
+            dir: element.attr_f64("dir"),
+            sta_start: element.attr_f64("staStart"),
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            state: element.attr_string("state"),
+            extensions: element.extensions_by(
+                &["length", "dir", "staStart", "name", "desc", "state"],
+                |position, child| {
+                    matches!(child.name.as_str(), "Start" | "End")
+                        && element.is_first_named(position)
+                },
+            ),
+        })
+    }
+}
+
+impl Curve {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let (start, start_elevation, start_name) = parse_end_point(element, "Start")?;
+        let (end, end_elevation, end_name) = parse_end_point(element, "End")?;
+        Ok(Self {
+            start,
+            end,
+            start_elevation,
+            end_elevation,
+            start_name,
+            end_name,
+            center: parse_optional_point(element, "Center")?,
+            pi: parse_optional_point(element, "PI")?,
+            rot: parse_rot(element)?,
+            radius: element.attr_f64("radius"),
+            length: element.attr_f64("length"),
+            chord: element.attr_f64("chord"),
+            delta: element.attr_f64("delta"),
+            tangent: element.attr_f64("tangent"),
+            external: element.attr_f64("external"),
+            mid_ord: element.attr_f64("midOrd"),
+            dir_start: element.attr_f64("dirStart"),
+            dir_end: element.attr_f64("dirEnd"),
+            sta_start: element.attr_f64("staStart"),
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            state: element.attr_string("state"),
+            extensions: element.extensions_by(
+                &[
+                    "rot", "radius", "length", "chord", "delta", "tangent", "external", "midOrd",
+                    "dirStart", "dirEnd", "staStart", "name", "desc", "state",
+                ],
+                |position, child| {
+                    matches!(child.name.as_str(), "Start" | "Center" | "End" | "PI")
+                        && element.is_first_named(position)
+                },
+            ),
+        })
+    }
+}
+
+impl Spiral {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let (start, start_elevation, start_name) = parse_end_point(element, "Start")?;
+        let (end, end_elevation, end_name) = parse_end_point(element, "End")?;
+        let length = element
+            .attr_f64("length")
+            .ok_or_else(|| LandXMLError::MissingElement {
+                element: "Spiral@length".to_string(),
+            })?;
+        // J-LandXMLではクロソイドパラメータAをFeatureで保持する
+        let constant_attribute = element.attr("constant").is_some();
+        let constant = element.attr_f64("constant").or_else(|| {
+            element
+                .feature_property("A")
+                .and_then(|v| v.trim().parse::<f64>().ok())
+        });
+
+        Ok(Self {
+            start,
+            end,
+            start_elevation,
+            end_elevation,
+            start_name,
+            end_name,
+            pi: parse_optional_point(element, "PI")?,
+            length,
+            radius_start: element.attr_f64("radiusStart"),
+            radius_end: element.attr_f64("radiusEnd"),
+            rot: parse_rot(element)?,
+            spi_type: element
+                .attr("spiType")
+                .map(SpiralType::from_landxml)
+                .unwrap_or_default(),
+            constant,
+            constant_attribute,
+            theta: element.attr_f64("theta"),
+            total_x: element.attr_f64("totalX"),
+            total_y: element.attr_f64("totalY"),
+            tan_long: element.attr_f64("tanLong"),
+            tan_short: element.attr_f64("tanShort"),
+            chord: element.attr_f64("chord"),
+            dir_start: element.attr_f64("dirStart"),
+            dir_end: element.attr_f64("dirEnd"),
+            sta_start: element.attr_f64("staStart"),
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            state: element.attr_string("state"),
+            // constant属性がない場合、Aのみを持つFeatureはconstantとして保持する
+            extensions: element.extensions_by(
+                &[
+                    "length",
+                    "radiusStart",
+                    "radiusEnd",
+                    "rot",
+                    "spiType",
+                    "constant",
+                    "theta",
+                    "totalX",
+                    "totalY",
+                    "tanLong",
+                    "tanShort",
+                    "chord",
+                    "dirStart",
+                    "dirEnd",
+                    "staStart",
+                    "name",
+                    "desc",
+                    "state",
+                ],
+                |position, child| {
+                    (matches!(child.name.as_str(), "Start" | "PI" | "End")
+                        && element.is_first_named(position))
+                        || (!constant_attribute && child.is_feature_with_only(&["A"]))
+                },
+            ),
+        })
+    }
+}
+
+impl CoordGeom {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let mut elements = Vec::new();
+        for child in &element.children {
+            match child.name.as_str() {
+                "Line" => elements.push(GeomElement::Line(Line::from_xml(child)?)),
+                "Curve" => elements.push(GeomElement::Curve(Curve::from_xml(child)?)),
+                "Spiral" => elements.push(GeomElement::Spiral(Spiral::from_xml(child)?)),
+                _ => {}
+            }
+        }
+        Ok(Self {
+            elements,
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            state: element.attr_string("state"),
+            extensions: element
+                .extensions(&["name", "desc", "state"], &["Line", "Curve", "Spiral"]),
+        })
+    }
+}
+
+// ============================================================================
+// XML出力
+// ============================================================================
+
+/// Start/End要素を生成（座標、標高、点名）
+fn end_point_xml(
+    writer: &LandXmlWriter,
+    name: &str,
+    point: Point2D,
+    elevation: Option<f64>,
+    point_name: Option<&str>,
+) -> XmlElement {
+    let text = match elevation {
+        Some(z) => writer.numbers(&[point.x, point.y, z]),
+        None => writer.numbers(&[point.x, point.y]),
+    };
+    XmlElement::new(name)
+        .with_opt_attr("name", point_name)
+        .with_text(text)
+}
+
+/// 任意の点要素（Center, PI）を生成
+fn optional_point_xml(
+    writer: &LandXmlWriter,
+    name: &str,
+    point: Option<Point2D>,
+) -> Option<XmlElement> {
+    point.map(|p| XmlElement::new(name).with_text(writer.numbers(&[p.x, p.y])))
+}
+
+impl Line {
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("Line")
+            .with_opt_attr("staStart", writer.opt_number(self.sta_start))
+            .with_opt_attr("length", writer.opt_number(self.length))
+            .with_opt_attr("dir", writer.opt_number(self.dir))
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_opt_attr("state", self.state.as_deref())
+            .with_child(end_point_xml(
+                writer,
+                "Start",
+                self.start,
+                self.start_elevation,
+                self.start_name.as_deref(),
+            ))
+            .with_child(end_point_xml(
+                writer,
+                "End",
+                self.end,
+                self.end_elevation,
+                self.end_name.as_deref(),
+            ))
+            .with_extensions(&self.extensions)
+    }
+}
+
+impl Curve {
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("Curve")
+            .with_attr("rot", self.rot.as_landxml_str())
+            .with_opt_attr("radius", writer.opt_number(self.radius))
+            .with_opt_attr("length", writer.opt_number(self.length))
+            .with_opt_attr("chord", writer.opt_number(self.chord))
+            .with_opt_attr("delta", writer.opt_number(self.delta))
+            .with_opt_attr("tangent", writer.opt_number(self.tangent))
+            .with_opt_attr("external", writer.opt_number(self.external))
+            .with_opt_attr("midOrd", writer.opt_number(self.mid_ord))
+            .with_opt_attr("dirStart", writer.opt_number(self.dir_start))
+            .with_opt_attr("dirEnd", writer.opt_number(self.dir_end))
+            .with_opt_attr("staStart", writer.opt_number(self.sta_start))
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_opt_attr("state", self.state.as_deref())
+            .with_child(end_point_xml(
+                writer,
+                "Start",
+                self.start,
+                self.start_elevation,
+                self.start_name.as_deref(),
+            ))
+            .with_children(optional_point_xml(writer, "Center", self.center))
+            .with_child(end_point_xml(
+                writer,
+                "End",
+                self.end,
+                self.end_elevation,
+                self.end_name.as_deref(),
+            ))
+            .with_children(optional_point_xml(writer, "PI", self.pi))
+            .with_extensions(&self.extensions)
+    }
+}
+
+impl Spiral {
+    /// Spiral要素を生成
+    ///
+    /// 曲率半径が無限大（None）の場合は"INF"を出力し、
+    /// クロソイドパラメータAはJ-LandXMLに従いFeatureで出力します
+    /// （未知要素のFeatureにAが含まれる場合はそちらを優先）。
+    /// 読み込み時にconstant属性だった場合はconstant属性で出力します。
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        let radius = |value: Option<f64>| match value {
+            Some(r) => writer.number(r),
+            None => "INF".to_string(),
+        };
+        let constant = self.constant.filter(|_| self.constant_attribute);
+        let feature = self
+            .constant
+            .filter(|_| !self.constant_attribute && !self.extensions.has_feature_property("A"))
+            .map(|a| {
+                XmlElement::new("Feature").with_child(
+                    XmlElement::new("Property")
+                        .with_attr("label", "A")
+                        .with_attr("value", writer.number(a)),
+                )
+            });
+
+        XmlElement::new("Spiral")
+            .with_attr("length", writer.number(self.length))
+            .with_attr("radiusStart", radius(self.radius_start))
+            .with_attr("radiusEnd", radius(self.radius_end))
+            .with_attr("rot", self.rot.as_landxml_str())
+            .with_attr("spiType", self.spi_type.as_landxml_str())
+            .with_opt_attr("constant", writer.opt_number(constant))
+            .with_opt_attr("theta", writer.opt_number(self.theta))
+            .with_opt_attr("totalX", writer.opt_number(self.total_x))
+            .with_opt_attr("totalY", writer.opt_number(self.total_y))
+            .with_opt_attr("tanLong", writer.opt_number(self.tan_long))
+            .with_opt_attr("tanShort", writer.opt_number(self.tan_short))
+            .with_opt_attr("chord", writer.opt_number(self.chord))
+            .with_opt_attr("dirStart", writer.opt_number(self.dir_start))
+            .with_opt_attr("dirEnd", writer.opt_number(self.dir_end))
+            .with_opt_attr("staStart", writer.opt_number(self.sta_start))
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_opt_attr("state", self.state.as_deref())
+            .with_child(end_point_xml(
+                writer,
+                "Start",
+                self.start,
+                self.start_elevation,
+                self.start_name.as_deref(),
+            ))
+            .with_children(optional_point_xml(writer, "PI", self.pi))
+            .with_child(end_point_xml(
+                writer,
+                "End",
+                self.end,
+                self.end_elevation,
+                self.end_name.as_deref(),
+            ))
+            .with_children(feature)
+            .with_extensions(&self.extensions)
+    }
+}
+
+impl CoordGeom {
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("CoordGeom")
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_opt_attr("state", self.state.as_deref())
+            .with_children(self.elements.iter().map(|element| match element {
+                GeomElement::Line(line) => line.to_xml(writer),
+                GeomElement::Curve(curve) => curve.to_xml(writer),
+                GeomElement::Spiral(spiral) => spiral.to_xml(writer),
+            }))
+            .with_extensions(&self.extensions)
+    }
 }
 
 #[cfg(test)]
@@ -248,12 +814,17 @@ mod tests {
         let line = Line {
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 100.0, y: 0.0 },
+            start_elevation: None,
+            end_elevation: None,
+            start_name: None,
+            end_name: None,
             length: Some(100.0),
             dir: Some(std::f64::consts::FRAC_PI_2), // East
             sta_start: Some(0.0),
             name: Some("L1".to_string()),
             desc: None,
             state: None,
+            extensions: Extensions::default(),
         };
         assert_eq!(line.length, Some(100.0));
     }
@@ -263,6 +834,10 @@ mod tests {
         let curve = Curve {
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 100.0, y: 100.0 },
+            start_elevation: Some(50.0),
+            end_elevation: Some(49.5),
+            start_name: Some("KE1-1".to_string()),
+            end_name: Some("KE1-2".to_string()),
             center: Some(Point2D { x: 100.0, y: 0.0 }),
             pi: None,
             rot: RotationDirection::Ccw,
@@ -279,8 +854,17 @@ mod tests {
             name: Some("C1".to_string()),
             desc: None,
             state: None,
+            extensions: Extensions::default(),
         };
         assert_eq!(curve.rot, RotationDirection::Ccw);
+
+        // 長さ未設定の場合は中心点から計算
+        let element = GeomElement::Curve(curve);
+        let length = element.length().unwrap();
+        assert!((length - 50.0 * std::f64::consts::PI).abs() < 1e-9);
+        // 中心が北にある左回りの円弧は東向きに始まる
+        let direction = element.start_direction().unwrap();
+        assert!((direction.degrees() - 90.0).abs() < 1e-9);
     }
 
     #[test]
@@ -288,6 +872,10 @@ mod tests {
         let spiral = Spiral {
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 50.0, y: 10.0 },
+            start_elevation: None,
+            end_elevation: None,
+            start_name: None,
+            end_name: None,
             pi: None,
             length: 50.0,
             radius_start: None, // Starts from straight
@@ -295,6 +883,7 @@ mod tests {
             rot: RotationDirection::Cw,
             spi_type: SpiralType::Clothoid,
             constant: Some(100.0), // A = 100
+            constant_attribute: false,
             theta: None,
             total_x: None,
             total_y: None,
@@ -307,21 +896,100 @@ mod tests {
             name: Some("S1".to_string()),
             desc: None,
             state: None,
+            extensions: Extensions::default(),
         };
         assert_eq!(spiral.spi_type, SpiralType::Clothoid);
     }
 
+    #[test]
+    fn test_coord_geom_from_xml() {
+        let xml = r#"<CoordGeom>
+            <Line length="100.40703773">
+              <Start name="BP">-5851.24470669 -16562.24159873 90.90600001</Start>
+              <End name="KA1-1">-5764.59435677 -16511.51399200 88.79745221</End>
+            </Line>
+            <Spiral length="62.5" radiusStart="INF" radiusEnd="250.0" rot="ccw" spiType="clothoid">
+              <Start>-5764.59435677 -16511.51399200</Start>
+              <PI>-5728.60693277 -16490.44591907</PI>
+              <End>-5709.42743874 -16482.23193203</End>
+              <Feature><Property label="A" value="125"/></Feature>
+            </Spiral>
+        </CoordGeom>"#;
+        let element = XmlElement::parse_str(xml).unwrap();
+        let coord_geom = CoordGeom::from_xml(&element).unwrap();
+        assert_eq!(coord_geom.elements.len(), 2);
+
+        match &coord_geom.elements[0] {
+            GeomElement::Line(line) => {
+                assert_eq!(line.start_name.as_deref(), Some("BP"));
+                assert_eq!(line.end_elevation, Some(88.79745221));
+            }
+            other => panic!("unexpected element: {:?}", other),
+        }
+        match &coord_geom.elements[1] {
+            GeomElement::Spiral(spiral) => {
+                assert_eq!(spiral.radius_start, None);
+                assert_eq!(spiral.radius_end, Some(250.0));
+                assert_eq!(spiral.constant, Some(125.0));
+                assert_eq!(spiral.rot, RotationDirection::Ccw);
+                assert!(spiral.pi.is_some());
+            }
+            other => panic!("unexpected element: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_spiral_constant_to_xml() {
+        let writer = LandXmlWriter::new().with_precision(3);
+        let spiral = |constant: &str| {
+            let xml = format!(
+                r#"<Spiral length="62.5" radiusStart="INF" radiusEnd="250.0" rot="ccw" {}>
+                  <Start>0 0</Start><End>62.5 0</End>{}
+                </Spiral>"#,
+                if constant == "attribute" {
+                    r#"constant="125.123456""#
+                } else {
+                    ""
+                },
+                if constant == "feature" {
+                    r#"<Feature><Property label="A" value="125.123456"/></Feature>"#
+                } else {
+                    ""
+                },
+            );
+            Spiral::from_xml(&XmlElement::parse_str(&xml).unwrap())
+                .unwrap()
+                .to_xml(&writer)
+        };
+
+        // 標準のconstant属性はconstant属性のまま出力
+        let element = spiral("attribute");
+        assert_eq!(element.attr("constant"), Some("125.123"));
+        assert!(element.child("Feature").is_none());
+
+        // J-LandXMLのFeatureは出力精度に従ってFeatureで出力
+        let element = spiral("feature");
+        assert_eq!(element.attr("constant"), None);
+        let property = element.child("Feature").unwrap().child("Property").unwrap();
+        assert_eq!(property.attr("value"), Some("125.123"));
+    }
+
     #[test]
     fn test_serialize_deserialize() {
         let line = Line {
             start: Point2D { x: 0.0, y: 0.0 },
             end: Point2D { x: 100.0, y: 0.0 },
+            start_elevation: None,
+            end_elevation: None,
+            start_name: None,
+            end_name: None,
             length: Some(100.0),
             dir: None,
             sta_start: None,
             name: None,
             desc: None,
             state: None,
+            extensions: Extensions::default(),
         };
         let json = serde_json::to_string(&line).unwrap();
         let parsed: Line = serde_json::from_str(&json).unwrap();
@@ -0,0 +1,252 @@
+//! Cross-section elements (CrossSects)
+//!
+//! LandXML 1.2 specification:
+//! - CrossSect: Cross section at a station
+//! - CrossSectSurf: Surface line across the section (PntList2D)
+//! - DesignCrossSectSurf: Design template points (CrossSectPnt)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::LandXMLError;
+use crate::models::{Extensions, Point2D};
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
+
+/// Cross-section surface line (LandXML CrossSectSurf element)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrossSectSurf {
+    /// Surface name
+    pub name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Section point lists (x = offset from centerline, y = elevation), one per PntList2D
+    ///
+    /// Gaps in the surface are represented by multiple lists.
+    pub point_lists: Vec<Vec<Point2D>>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+/// Design cross-section point (LandXML CrossSectPnt element)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrossSectPnt {
+    /// Point code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Offset from centerline
+    pub offset: f64,
+    /// Elevation
+    pub elevation: f64,
+}
+
+/// Design cross-section surface (LandXML DesignCrossSectSurf element)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DesignCrossSectSurf {
+    /// Element name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Side of road (left, right, both)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    /// Design points
+    pub points: Vec<CrossSectPnt>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+/// Cross section at a station (LandXML CrossSect element)
+///
+/// Reference: http://www.landxml.org/schema/LandXML-1.2/documentation/LandXML-1.2Doc_CrossSect.html
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrossSect {
+    /// Element name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Station (chainage)
+    pub sta: f64,
+    /// Surface lines (existing ground, soil layers, etc.)
+    pub surfaces: Vec<CrossSectSurf>,
+    /// Design template surfaces
+    pub design_surfaces: Vec<DesignCrossSectSurf>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+/// Cross-section container (LandXML CrossSects element)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrossSects {
+    /// Cross sections ordered by station
+    pub sections: Vec<CrossSect>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl CrossSects {
+    /// 測点で横断面を検索
+    pub fn find_by_sta(&self, sta: f64, tolerance: f64) -> Option<&CrossSect> {
+        self.sections
+            .iter()
+            .find(|section| (section.sta - sta).abs() <= tolerance)
+    }
+}
+
+// ============================================================================
+// XML解析
+// ============================================================================
+
+impl CrossSect {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let sta = element
+            .attr_f64("sta")
+            .ok_or_else(|| LandXMLError::MissingElement {
+                element: "CrossSect@sta".to_string(),
+            })?;
+
+        let mut section = Self {
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            sta,
+            extensions: element.extensions(
+                &["name", "desc", "sta"],
+                &["CrossSectSurf", "DesignCrossSectSurf"],
+            ),
+            ..Default::default()
+        };
+
+        for child in &element.children {
+            match child.name.as_str() {
+                "CrossSectSurf" => section.surfaces.push(CrossSectSurf {
+                    name: child.attr_string("name").unwrap_or_default(),
+                    desc: child.attr_string("desc"),
+                    point_lists: child.pnt_lists_2d()?,
+                    extensions: child.extensions(&["name", "desc"], &["PntList2D"]),
+                }),
+                "DesignCrossSectSurf" => {
+                    let points = child
+                        .children_named("CrossSectPnt")
+                        .map(|pnt| {
+                            pnt.point().map(|(point, _)| CrossSectPnt {
+                                code: pnt.attr_string("code"),
+                                offset: point.x,
+                                elevation: point.y,
+                            })
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    section.design_surfaces.push(DesignCrossSectSurf {
+                        name: child.attr_string("name"),
+                        desc: child.attr_string("desc"),
+                        side: child.attr_string("side"),
+                        points,
+                        extensions: child.extensions(&["name", "desc", "side"], &["CrossSectPnt"]),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(section)
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        let surfaces = self.surfaces.iter().map(|surface| {
+            XmlElement::new("CrossSectSurf")
+                .with_attr("name", surface.name.as_str())
+                .with_opt_attr("desc", surface.desc.as_deref())
+                .with_children(writer.pnt_lists_2d(&surface.point_lists))
+                .with_extensions(&surface.extensions)
+        });
+        let design_surfaces = self.design_surfaces.iter().map(|surface| {
+            XmlElement::new("DesignCrossSectSurf")
+                .with_opt_attr("name", surface.name.as_deref())
+                .with_opt_attr("desc", surface.desc.as_deref())
+                .with_opt_attr("side", surface.side.as_deref())
+                .with_children(surface.points.iter().map(|pnt| {
+                    XmlElement::new("CrossSectPnt")
+                        .with_opt_attr("code", pnt.code.as_deref())
+                        .with_text(writer.numbers(&[pnt.offset, pnt.elevation]))
+                }))
+                .with_extensions(&surface.extensions)
+        });
+
+        XmlElement::new("CrossSect")
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_attr("sta", writer.number(self.sta))
+            .with_children(surfaces)
+            .with_children(design_surfaces)
+            .with_extensions(&self.extensions)
+    }
+}
+
+impl CrossSects {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        Ok(Self {
+            sections: element
+                .children_named("CrossSect")
+                .map(CrossSect::from_xml)
+                .collect::<Result<Vec<_>, _>>()?,
+            extensions: element.extensions(&[], &["CrossSect"]),
+        })
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("CrossSects")
+            .with_children(self.sections.iter().map(|section| section.to_xml(writer)))
+            .with_extensions(&self.extensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_by_sta() {
+        let cross_sects = CrossSects {
+            sections: vec![CrossSect {
+                name: Some("NO.17".to_string()),
+                sta: 339.625281,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(cross_sects.find_by_sta(339.6253, 0.001).is_some());
+        assert!(cross_sects.find_by_sta(340.0, 0.001).is_none());
+    }
+
+    #[test]
+    fn test_cross_sects_from_xml() {
+        let xml = r#"<CrossSects>
+            <CrossSect name="NO.17" sta="339.625281">
+              <CrossSectSurf name="ExistingGround">
+                <PntList2D>-30.0 77.86 -29.683 78.0 0.0 86.914</PntList2D>
+              </CrossSectSurf>
+              <DesignCrossSectSurf name="Carriageway" desc="道路面" side="left">
+                <CrossSectPnt code="PL-1-Lp1">0.00000000 82.12162605</CrossSectPnt>
+                <CrossSectPnt code="PL-1-Lp2">-3.00000000 82.06162605</CrossSectPnt>
+              </DesignCrossSectSurf>
+            </CrossSect>
+        </CrossSects>"#;
+        let element = XmlElement::parse_str(xml).unwrap();
+        let cross_sects = CrossSects::from_xml(&element).unwrap();
+
+        let section = &cross_sects.sections[0];
+        assert_eq!(section.sta, 339.625281);
+        assert_eq!(section.surfaces[0].point_lists[0].len(), 3);
+        let design = &section.design_surfaces[0];
+        assert_eq!(design.side.as_deref(), Some("left"));
+        assert_eq!(design.points[1].code.as_deref(), Some("PL-1-Lp2"));
+        assert_eq!(design.points[1].offset, -3.0);
+    }
+}
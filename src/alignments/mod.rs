@@ -6,5 +6,9 @@
 //! - CrossSection: Cross-sectional data
 
 pub mod coord_geom;
+pub mod cross_sects;
+pub mod profile;
 
 pub use coord_geom::*;
+pub use cross_sects::*;
+pub use profile::*;
@@ -0,0 +1,312 @@
+//! Vertical alignment elements (Profile)
+//!
+//! LandXML 1.2 specification:
+//! - ProfAlign: Design vertical alignment (PVI, ParaCurve, UnsymParaCurve, CircCurve)
+//! - ProfSurf: Ground profile along the alignment (PntList2D)
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::LandXMLError;
+use crate::models::{Extensions, Point2D};
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
+
+/// Vertical geometry element of a ProfAlign
+///
+/// `sta` and `elevation` are the station and elevation of the intersection point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProfAlignElement {
+    /// Point of vertical intersection
+    Pvi { sta: f64, elevation: f64 },
+    /// Symmetric parabolic vertical curve
+    ParaCurve {
+        sta: f64,
+        elevation: f64,
+        length: f64,
+    },
+    /// Asymmetric parabolic vertical curve
+    UnsymParaCurve {
+        sta: f64,
+        elevation: f64,
+        length_in: f64,
+        length_out: f64,
+    },
+    /// Circular vertical curve
+    CircCurve {
+        sta: f64,
+        elevation: f64,
+        length: f64,
+        radius: f64,
+    },
+}
+
+impl ProfAlignElement {
+    /// 変化点の測点を取得
+    pub fn sta(&self) -> f64 {
+        match self {
+            Self::Pvi { sta, .. }
+            | Self::ParaCurve { sta, .. }
+            | Self::UnsymParaCurve { sta, .. }
+            | Self::CircCurve { sta, .. } => *sta,
+        }
+    }
+
+    /// 変化点の標高を取得
+    pub fn elevation(&self) -> f64 {
+        match self {
+            Self::Pvi { elevation, .. }
+            | Self::ParaCurve { elevation, .. }
+            | Self::UnsymParaCurve { elevation, .. }
+            | Self::CircCurve { elevation, .. } => *elevation,
+        }
+    }
+
+    /// 変化点の標高を変更可能な参照で取得
+    pub fn elevation_mut(&mut self) -> &mut f64 {
+        match self {
+            Self::Pvi { elevation, .. }
+            | Self::ParaCurve { elevation, .. }
+            | Self::UnsymParaCurve { elevation, .. }
+            | Self::CircCurve { elevation, .. } => elevation,
+        }
+    }
+}
+
+/// Design vertical alignment (LandXML ProfAlign element)
+///
+/// Reference: http://www.landxml.org/schema/LandXML-1.2/documentation/LandXML-1.2Doc_ProfAlign.html
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfAlign {
+    /// Element name
+    pub name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Sequence of vertical geometry elements
+    pub elements: Vec<ProfAlignElement>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+/// Ground profile (LandXML ProfSurf element)
+///
+/// Reference: http://www.landxml.org/schema/LandXML-1.2/documentation/LandXML-1.2Doc_ProfSurf.html
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfSurf {
+    /// Element name
+    pub name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Profile point lists (x = station, y = elevation), one per PntList2D
+    ///
+    /// Gaps in the profile are represented by multiple lists.
+    pub point_lists: Vec<Vec<Point2D>>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+/// Vertical alignment container (LandXML Profile element)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    /// Element name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Design vertical alignments
+    pub prof_aligns: Vec<ProfAlign>,
+    /// Ground profiles
+    pub prof_surfs: Vec<ProfSurf>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+// ============================================================================
+// XML解析
+// ============================================================================
+
+impl ProfAlignElement {
+    /// PVI系要素を解析（未知の要素はNone）
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Option<Self>, LandXMLError> {
+        let required = |key: &str| {
+            element
+                .attr_f64(key)
+                .ok_or_else(|| LandXMLError::MissingElement {
+                    element: format!("{}@{}", element.name, key),
+                })
+        };
+
+        let parsed = match element.name.as_str() {
+            "PVI" => {
+                let (point, _) = element.point()?;
+                Self::Pvi {
+                    sta: point.x,
+                    elevation: point.y,
+                }
+            }
+            "ParaCurve" => {
+                let (point, _) = element.point()?;
+                Self::ParaCurve {
+                    sta: point.x,
+                    elevation: point.y,
+                    length: required("length")?,
+                }
+            }
+            "UnsymParaCurve" => {
+                let (point, _) = element.point()?;
+                Self::UnsymParaCurve {
+                    sta: point.x,
+                    elevation: point.y,
+                    length_in: required("lengthIn")?,
+                    length_out: required("lengthOut")?,
+                }
+            }
+            "CircCurve" => {
+                let (point, _) = element.point()?;
+                Self::CircCurve {
+                    sta: point.x,
+                    elevation: point.y,
+                    length: required("length")?,
+                    radius: required("radius")?,
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(parsed))
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        let point = writer.numbers(&[self.sta(), self.elevation()]);
+        match self {
+            Self::Pvi { .. } => XmlElement::new("PVI"),
+            Self::ParaCurve { length, .. } => {
+                XmlElement::new("ParaCurve").with_attr("length", writer.number(*length))
+            }
+            Self::UnsymParaCurve {
+                length_in,
+                length_out,
+                ..
+            } => XmlElement::new("UnsymParaCurve")
+                .with_attr("lengthIn", writer.number(*length_in))
+                .with_attr("lengthOut", writer.number(*length_out)),
+            Self::CircCurve { length, radius, .. } => XmlElement::new("CircCurve")
+                .with_attr("length", writer.number(*length))
+                .with_attr("radius", writer.number(*radius)),
+        }
+        .with_text(point)
+    }
+}
+
+impl Profile {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let mut profile = Self {
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            extensions: element.extensions(&["name", "desc"], &["ProfAlign", "ProfSurf"]),
+            ..Default::default()
+        };
+
+        for child in &element.children {
+            match child.name.as_str() {
+                "ProfAlign" => {
+                    let mut elements = Vec::new();
+                    for pvi in &child.children {
+                        if let Some(parsed) = ProfAlignElement::from_xml(pvi)? {
+                            elements.push(parsed);
+                        }
+                    }
+                    profile.prof_aligns.push(ProfAlign {
+                        name: child.attr_string("name").unwrap_or_default(),
+                        desc: child.attr_string("desc"),
+                        elements,
+                        extensions: child.extensions(
+                            &["name", "desc"],
+                            &["PVI", "ParaCurve", "UnsymParaCurve", "CircCurve"],
+                        ),
+                    });
+                }
+                "ProfSurf" => profile.prof_surfs.push(ProfSurf {
+                    name: child.attr_string("name").unwrap_or_default(),
+                    desc: child.attr_string("desc"),
+                    point_lists: child.pnt_lists_2d()?,
+                    extensions: child.extensions(&["name", "desc"], &["PntList2D"]),
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(profile)
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        let prof_aligns = self.prof_aligns.iter().map(|prof_align| {
+            XmlElement::new("ProfAlign")
+                .with_attr("name", prof_align.name.as_str())
+                .with_opt_attr("desc", prof_align.desc.as_deref())
+                .with_children(prof_align.elements.iter().map(|e| e.to_xml(writer)))
+                .with_extensions(&prof_align.extensions)
+        });
+        let prof_surfs = self.prof_surfs.iter().map(|prof_surf| {
+            XmlElement::new("ProfSurf")
+                .with_attr("name", prof_surf.name.as_str())
+                .with_opt_attr("desc", prof_surf.desc.as_deref())
+                .with_children(writer.pnt_lists_2d(&prof_surf.point_lists))
+                .with_extensions(&prof_surf.extensions)
+        });
+
+        XmlElement::new("Profile")
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_children(prof_aligns)
+            .with_children(prof_surfs)
+            .with_extensions(&self.extensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prof_align_element_accessors() {
+        let mut element = ProfAlignElement::ParaCurve {
+            sta: 300.0,
+            elevation: 82.716,
+            length: 80.0,
+        };
+        assert_eq!(element.sta(), 300.0);
+        *element.elevation_mut() += 1.0;
+        assert!((element.elevation() - 83.716).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_profile_from_xml() {
+        let xml = r#"<Profile>
+            <ProfAlign name="縦断線形 1">
+              <PVI>-90.00000000 90.90600001</PVI>
+              <ParaCurve length="80.000000">300.00000029 82.71600000</ParaCurve>
+              <PVI>995.94618316 75.38160000</PVI>
+            </ProfAlign>
+            <ProfSurf name="地盤線">
+              <PntList2D>-90.0 90.966 -80.0 91.081 -
+70.19 91.193</PntList2D>
+            </ProfSurf>
+        </Profile>"#;
+        let element = XmlElement::parse_str(xml).unwrap();
+        let profile = Profile::from_xml(&element).unwrap();
+
+        assert_eq!(profile.prof_aligns[0].name, "縦断線形 1");
+        assert_eq!(profile.prof_aligns[0].elements.len(), 3);
+        assert!(matches!(
+            profile.prof_aligns[0].elements[1],
+            ProfAlignElement::ParaCurve { length, .. } if length == 80.0
+        ));
+        assert_eq!(profile.prof_surfs[0].point_lists[0].len(), 3);
+        assert_eq!(profile.prof_surfs[0].point_lists[0][2].x, -70.19);
+    }
+}
@@ -6,6 +6,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::Extensions;
+use crate::xml::XmlElement;
+
 /// Application that created the LandXML document
 ///
 /// Reference: LandXML 1.2 Application element
@@ -21,6 +24,42 @@ pub struct Application {
     pub manufacturer_url: Option<String>,
     /// Creation timestamp
     pub time_stamp: Option<String>,
+    /// 未知の属性・子要素（Author等）
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl Application {
+    pub(crate) fn from_xml(element: &XmlElement) -> Self {
+        Self {
+            name: element.attr_string("name"),
+            manufacturer: element.attr_string("manufacturer"),
+            version: element.attr_string("version"),
+            manufacturer_url: element.attr_string("manufacturerURL"),
+            time_stamp: element.attr_string("timeStamp"),
+            extensions: element.extensions(
+                &[
+                    "name",
+                    "manufacturer",
+                    "version",
+                    "manufacturerURL",
+                    "timeStamp",
+                ],
+                &[],
+            ),
+        }
+    }
+
+    /// Application要素を生成（nameは必須属性のため未設定時は空文字）
+    pub(crate) fn to_xml(&self) -> XmlElement {
+        XmlElement::new("Application")
+            .with_attr("name", self.name.clone().unwrap_or_default())
+            .with_opt_attr("manufacturer", self.manufacturer.as_deref())
+            .with_opt_attr("version", self.version.as_deref())
+            .with_opt_attr("manufacturerURL", self.manufacturer_url.as_deref())
+            .with_opt_attr("timeStamp", self.time_stamp.as_deref())
+            .with_extensions(&self.extensions)
+    }
 }
 
 #[cfg(test)]
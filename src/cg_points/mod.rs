@@ -6,7 +6,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::Point3D;
+use crate::error::LandXMLError;
+use crate::models::{Extensions, Point3D};
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
 
 /// A single control/ground point
 ///
@@ -21,8 +24,13 @@ pub struct CgPoint {
     pub code: Option<String>,
     /// 3D position
     pub position: Point3D,
+    /// Whether the source gave an elevation (false for "northing easting" only)
+    pub has_elevation: bool,
     /// Point type
     pub point_type: Option<String>,
+    /// 未知の属性（featureRef等）
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
 }
 
 /// Collection of CgPoints
@@ -30,8 +38,64 @@ pub struct CgPoint {
 /// Reference: LandXML 1.2 CgPoints element
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct CgPoints {
+    /// Collection name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// List of control/ground points
     pub points: Vec<CgPoint>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl CgPoints {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let points = element
+            .children_named("CgPoint")
+            .map(|point| {
+                let (position, elevation) = point.point()?;
+                Ok(CgPoint {
+                    name: point.attr_string("name"),
+                    desc: point.attr_string("desc"),
+                    code: point.attr_string("code"),
+                    position: Point3D {
+                        x: position.x,
+                        y: position.y,
+                        z: elevation.unwrap_or(0.0),
+                    },
+                    has_elevation: elevation.is_some(),
+                    point_type: None,
+                    extensions: point.extensions(&["name", "desc", "code"], &[]),
+                })
+            })
+            .collect::<Result<Vec<_>, LandXMLError>>()?;
+
+        Ok(Self {
+            name: element.attr_string("name"),
+            points,
+            extensions: element.extensions(&["name"], &["CgPoint"]),
+        })
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("CgPoints")
+            .with_opt_attr("name", self.name.as_deref())
+            .with_children(self.points.iter().map(|point| {
+                let p = &point.position;
+                let text = if point.has_elevation {
+                    writer.numbers(&[p.x, p.y, p.z])
+                } else {
+                    writer.numbers(&[p.x, p.y])
+                };
+                XmlElement::new("CgPoint")
+                    .with_opt_attr("name", point.name.as_deref())
+                    .with_opt_attr("desc", point.desc.as_deref())
+                    .with_opt_attr("code", point.code.as_deref())
+                    .with_text(text)
+                    .with_extensions(&point.extensions)
+            }))
+            .with_extensions(&self.extensions)
+    }
 }
 
 #[cfg(test)]
@@ -49,8 +113,29 @@ mod tests {
                 y: 2000.0,
                 z: 100.0,
             },
+            has_elevation: true,
             point_type: None,
+            extensions: Extensions::default(),
         };
         assert_eq!(point.name, Some("CP1".to_string()));
     }
+
+    #[test]
+    fn test_cg_points_from_xml() {
+        let xml = r#"<CgPoints name="IntermediatePnts">
+            <CgPoint name="BP (NO.-4-10)" featureRef="0">-5851.24470669 -16562.24159873</CgPoint>
+            <CgPoint name="BM1" code="BM">-5842.6 -16557.1 91.25</CgPoint>
+        </CgPoints>"#;
+        let element = XmlElement::parse_str(xml).unwrap();
+        let cg_points = CgPoints::from_xml(&element).unwrap();
+
+        assert_eq!(cg_points.name.as_deref(), Some("IntermediatePnts"));
+        assert!(!cg_points.points[0].has_elevation);
+        assert!(cg_points.points[1].has_elevation);
+        assert_eq!(cg_points.points[1].position.z, 91.25);
+        assert_eq!(
+            cg_points.points[0].extensions.attribute("featureRef"),
+            Some("0")
+        );
+    }
 }
@@ -0,0 +1,1032 @@
+//! CRS定義文字列の出力・解析
+//!
+//! J-LandXMLの座標系を他のGISソフトウェアで扱える形式に変換します：
+//! - WKT2（ISO 19162:2019）
+//! - PROJJSON
+//! - PROJ文字列（CRS定義・変換パイプライン）
+//!
+//! 鉛直原子を含む複合座標系として出力します。T.P以外の河川基準面は
+//! differTPを鉛直オフセットとする変換付き（BoundCRS）で表現します。
+//!
+//! また、EPSGコード・WKT（WKT1/WKT2）・PROJ文字列から平面直角座標系と
+//! 水平測地原子を判定します（標準LandXMLのepsgCode・ogcWktCode属性用）。
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use super::jlandxml::{
+    HorizontalDatum, JLandXmlCoordinateSystem, JapanPlaneCoordinateSystem, VerticalDatum,
+};
+use super::projection::PLANE_ORIGIN_SCALE_FACTOR;
+use crate::error::LandXMLError;
+
+/// PROJJSONのスキーマURL
+const PROJJSON_SCHEMA: &str = "https://proj.org/schemas/v0.7/projjson.schema.json";
+
+/// 角度単位（度）のラジアン換算値
+const DEGREE_IN_RADIANS: &str = "0.0174532925199433";
+
+// ============================================================================
+// 測地原子ごとの定義値
+// ============================================================================
+
+/// 回転楕円体の定義
+struct EllipsoidDefinition {
+    name: &'static str,
+    semi_major_axis: f64,
+    inverse_flattening: f64,
+    proj_name: &'static str,
+}
+
+const GRS80: EllipsoidDefinition = EllipsoidDefinition {
+    name: "GRS 1980",
+    semi_major_axis: 6_378_137.0,
+    inverse_flattening: 298.257_222_101,
+    proj_name: "GRS80",
+};
+
+const BESSEL_1841: EllipsoidDefinition = EllipsoidDefinition {
+    name: "Bessel 1841",
+    semi_major_axis: 6_377_397.155,
+    inverse_flattening: 299.152_812_8,
+    proj_name: "bessel",
+};
+
+impl HorizontalDatum {
+    /// 平面直角座標系のEPSGコードを取得（測地原子別）
+    ///
+    /// JGD2000: 2443～2461、JGD2011: 6669～6687、旧日本測地系: 30161～30179
+    pub fn projected_epsg_code(&self, zone: JapanPlaneCoordinateSystem) -> u32 {
+        let offset = u32::from(zone.zone_number()) - 1;
+        match self {
+            Self::JGD2000 => 2443 + offset,
+            Self::JGD2011 => 6669 + offset,
+            Self::TD => 30161 + offset,
+        }
+    }
+
+    /// 地理座標系のEPSGコードを取得
+    pub fn geographic_epsg_code(&self) -> u32 {
+        match self {
+            Self::JGD2000 => 4612,
+            Self::JGD2011 => 6668,
+            Self::TD => 4301,
+        }
+    }
+
+    /// 地理座標系のEPSGコード（JGD2000: 4612、JGD2011: 6668、旧日本測地系: 4301）から判定
+    pub fn from_geographic_epsg(code: u32) -> Result<Self, LandXMLError> {
+        match code {
+            4612 => Ok(Self::JGD2000),
+            6668 => Ok(Self::JGD2011),
+            4301 => Ok(Self::TD),
+            _ => Err(LandXMLError::InvalidCoordinateSystem(format!(
+                "EPSG:{} is not a Japanese geographic CRS",
+                code
+            ))),
+        }
+    }
+
+    /// 地理座標系の名称（EPSG表記）
+    fn crs_name(&self) -> &'static str {
+        match self {
+            Self::JGD2000 => "JGD2000",
+            Self::JGD2011 => "JGD2011",
+            Self::TD => "Tokyo",
+        }
+    }
+
+    /// 測地原子の名称（EPSG表記）
+    fn datum_name(&self) -> &'static str {
+        match self {
+            Self::JGD2000 => "Japanese Geodetic Datum 2000",
+            Self::JGD2011 => "Japanese Geodetic Datum 2011",
+            Self::TD => "Tokyo",
+        }
+    }
+
+    fn ellipsoid(&self) -> &'static EllipsoidDefinition {
+        match self {
+            Self::JGD2000 | Self::JGD2011 => &GRS80,
+            Self::TD => &BESSEL_1841,
+        }
+    }
+
+    /// 世界測地系への変換パラメータ（PROJの+towgs84）
+    fn towgs84(&self) -> Option<&'static str> {
+        match self {
+            Self::JGD2000 | Self::JGD2011 => None,
+            Self::TD => Some("-146.414,507.337,680.507,0,0,0,0"),
+        }
+    }
+
+    /// 平面直角座標系の名称（EPSG表記）
+    pub fn projected_crs_name(&self, zone: JapanPlaneCoordinateSystem) -> String {
+        format!(
+            "{} / Japan Plane Rectangular CS {}",
+            self.crs_name(),
+            zone.roman_numeral()
+        )
+    }
+
+    /// T.Pに対応する鉛直座標系（名称, 鉛直原子名, EPSGコード）
+    fn vertical_crs(&self) -> (&'static str, &'static str, u32) {
+        match self {
+            Self::JGD2000 => (
+                "JGD2000 (vertical) height",
+                "Japanese Geodetic Datum 2000 (vertical)",
+                6694,
+            ),
+            Self::JGD2011 => (
+                "JGD2011 (vertical) height",
+                "Japanese Geodetic Datum 2011 (vertical)",
+                6695,
+            ),
+            Self::TD => (
+                "JSLD69 height",
+                "Japanese Standard Levelling Datum 1969",
+                5723,
+            ),
+        }
+    }
+
+    /// 平面直角座標系のPROJ文字列（CRS定義）
+    pub fn proj4_definition(&self, zone: JapanPlaneCoordinateSystem) -> String {
+        let mut definition = format!(
+            "{} +ellps={}",
+            tmerc_parameters(zone),
+            self.ellipsoid().proj_name
+        );
+        if let Some(towgs84) = self.towgs84() {
+            definition.push_str(" +towgs84=");
+            definition.push_str(towgs84);
+        }
+        definition.push_str(" +units=m +no_defs");
+        definition
+    }
+}
+
+/// 横メルカトル投影のPROJパラメータ
+fn tmerc_parameters(zone: JapanPlaneCoordinateSystem) -> String {
+    let (lat0, lon0) = zone.origin();
+    format!(
+        "+proj=tmerc +lat_0={} +lon_0={} +k={} +x_0=0 +y_0=0",
+        format_number(lat0),
+        format_number(lon0),
+        format_number(PLANE_ORIGIN_SCALE_FACTOR)
+    )
+}
+
+/// 数値を12桁の小数で丸めて末尾の0を除去
+fn format_number(value: f64) -> String {
+    let formatted = format!("{:.12}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+// ============================================================================
+// WKT2
+// ============================================================================
+
+/// WKTの引数
+enum WktArg {
+    /// 引用符付き文字列
+    Quoted(String),
+    /// 数値・列挙値
+    Raw(String),
+    /// 入れ子の要素
+    Node(WktNode),
+}
+
+/// WKTの要素（KEYWORD[args...]）
+struct WktNode {
+    keyword: &'static str,
+    args: Vec<WktArg>,
+}
+
+impl WktNode {
+    fn new(keyword: &'static str) -> Self {
+        Self {
+            keyword,
+            args: Vec::new(),
+        }
+    }
+
+    fn quoted(mut self, value: impl Into<String>) -> Self {
+        self.args.push(WktArg::Quoted(value.into()));
+        self
+    }
+
+    fn raw(mut self, value: impl Into<String>) -> Self {
+        self.args.push(WktArg::Raw(value.into()));
+        self
+    }
+
+    fn node(mut self, node: WktNode) -> Self {
+        self.args.push(WktArg::Node(node));
+        self
+    }
+
+    fn id(self, code: u32) -> Self {
+        self.node(WktNode::new("ID").quoted("EPSG").raw(code.to_string()))
+    }
+
+    /// 整形して出力（入れ子の要素を改行・字下げ）
+    fn render(&self, indent: usize, out: &mut String) {
+        out.push_str(self.keyword);
+        out.push('[');
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            match arg {
+                WktArg::Quoted(value) => {
+                    out.push('"');
+                    out.push_str(&value.replace('"', "\"\""));
+                    out.push('"');
+                }
+                WktArg::Raw(value) => out.push_str(value),
+                WktArg::Node(node) => {
+                    if i > 0 {
+                        out.push('\n');
+                        out.push_str(&" ".repeat((indent + 1) * 4));
+                    }
+                    node.render(indent + 1, out);
+                }
+            }
+        }
+        out.push(']');
+    }
+}
+
+fn metre() -> WktNode {
+    WktNode::new("LENGTHUNIT").quoted("metre").raw("1")
+}
+
+fn degree() -> WktNode {
+    WktNode::new("ANGLEUNIT")
+        .quoted("degree")
+        .raw(DEGREE_IN_RADIANS)
+}
+
+fn wkt_parameter(name: &str, value: f64, unit: WktNode, code: u32) -> WktNode {
+    WktNode::new("PARAMETER")
+        .quoted(name)
+        .raw(format_number(value))
+        .node(unit)
+        .id(code)
+}
+
+fn wkt_projected_crs(datum: HorizontalDatum, zone: JapanPlaneCoordinateSystem) -> WktNode {
+    let ellipsoid = datum.ellipsoid();
+    let (lat0, lon0) = zone.origin();
+
+    let base = WktNode::new("BASEGEOGCRS")
+        .quoted(datum.crs_name())
+        .node(
+            WktNode::new("DATUM").quoted(datum.datum_name()).node(
+                WktNode::new("ELLIPSOID")
+                    .quoted(ellipsoid.name)
+                    .raw(format_number(ellipsoid.semi_major_axis))
+                    .raw(format_number(ellipsoid.inverse_flattening))
+                    .node(metre()),
+            ),
+        )
+        .node(
+            WktNode::new("PRIMEM")
+                .quoted("Greenwich")
+                .raw("0")
+                .node(degree()),
+        )
+        .id(datum.geographic_epsg_code());
+
+    let conversion = WktNode::new("CONVERSION")
+        .quoted(format!(
+            "Japan Plane Rectangular CS zone {}",
+            zone.roman_numeral()
+        ))
+        .node(
+            WktNode::new("METHOD")
+                .quoted("Transverse Mercator")
+                .id(9807),
+        )
+        .node(wkt_parameter(
+            "Latitude of natural origin",
+            lat0,
+            degree(),
+            8801,
+        ))
+        .node(wkt_parameter(
+            "Longitude of natural origin",
+            lon0,
+            degree(),
+            8802,
+        ))
+        .node(wkt_parameter(
+            "Scale factor at natural origin",
+            PLANE_ORIGIN_SCALE_FACTOR,
+            WktNode::new("SCALEUNIT").quoted("unity").raw("1"),
+            8805,
+        ))
+        .node(wkt_parameter("False easting", 0.0, metre(), 8806))
+        .node(wkt_parameter("False northing", 0.0, metre(), 8807));
+
+    WktNode::new("PROJCRS")
+        .quoted(datum.projected_crs_name(zone))
+        .node(base)
+        .node(conversion)
+        .node(WktNode::new("CS").raw("Cartesian").raw("2"))
+        .node(
+            WktNode::new("AXIS")
+                .quoted("northing (X)")
+                .raw("north")
+                .node(WktNode::new("ORDER").raw("1"))
+                .node(metre()),
+        )
+        .node(
+            WktNode::new("AXIS")
+                .quoted("easting (Y)")
+                .raw("east")
+                .node(WktNode::new("ORDER").raw("2"))
+                .node(metre()),
+        )
+        .id(datum.projected_epsg_code(zone))
+}
+
+fn wkt_vertical_crs(name: &str, datum_name: &str, code: Option<u32>) -> WktNode {
+    let node = WktNode::new("VERTCRS")
+        .quoted(name)
+        .node(WktNode::new("VDATUM").quoted(datum_name))
+        .node(WktNode::new("CS").raw("vertical").raw("1"))
+        .node(
+            WktNode::new("AXIS")
+                .quoted("gravity-related height (H)")
+                .raw("up")
+                .node(metre()),
+        );
+    match code {
+        Some(code) => node.id(code),
+        None => node,
+    }
+}
+
+// ============================================================================
+// 座標系からの出力
+// ============================================================================
+
+/// 複合座標系の鉛直成分
+enum VerticalComponent {
+    /// T.P（測地原子に対応する鉛直座標系）
+    Standard,
+    /// 河川基準面（T.P標高 = 標高 + differTP）
+    RiverDatum {
+        datum: VerticalDatum,
+        differ_tp: f64,
+    },
+}
+
+impl JLandXmlCoordinateSystem {
+    fn export_zone(&self) -> Result<JapanPlaneCoordinateSystem, LandXMLError> {
+        self.plane_coordinate_zone
+            .ok_or_else(|| LandXMLError::InvalidCoordinateSystemNameFormat {
+                name: self.horizontal_coordinate_system_name.clone(),
+            })
+    }
+
+    fn vertical_component(&self) -> VerticalComponent {
+        match self.vertical_datum {
+            VerticalDatum::TP => VerticalComponent::Standard,
+            datum => VerticalComponent::RiverDatum {
+                datum,
+                differ_tp: self.differ_tp.unwrap_or_else(|| datum.tp_offset()),
+            },
+        }
+    }
+
+    /// 複合座標系の名称（"JGD2011 / Japan Plane Rectangular CS IX + JGD2011 (vertical) height"等）
+    pub fn compound_crs_name(&self) -> Result<String, LandXMLError> {
+        let zone = self.export_zone()?;
+        let vertical = match self.vertical_component() {
+            VerticalComponent::Standard => self.horizontal_datum.vertical_crs().0.to_string(),
+            VerticalComponent::RiverDatum { datum, .. } => format!("{} height", datum.as_str()),
+        };
+        Ok(format!(
+            "{} + {}",
+            self.horizontal_datum.projected_crs_name(zone),
+            vertical
+        ))
+    }
+
+    /// WKT2（ISO 19162:2019）形式の複合座標系定義を出力
+    pub fn to_wkt2(&self) -> Result<String, LandXMLError> {
+        let zone = self.export_zone()?;
+        let (tp_name, tp_datum, tp_code) = self.horizontal_datum.vertical_crs();
+
+        let vertical = match self.vertical_component() {
+            VerticalComponent::Standard => wkt_vertical_crs(tp_name, tp_datum, Some(tp_code)),
+            VerticalComponent::RiverDatum { datum, differ_tp } => {
+                let source_name = format!("{} height", datum.as_str());
+                WktNode::new("BOUNDCRS")
+                    .node(WktNode::new("SOURCECRS").node(wkt_vertical_crs(
+                        &source_name,
+                        datum.as_str(),
+                        None,
+                    )))
+                    .node(WktNode::new("TARGETCRS").node(wkt_vertical_crs(
+                        tp_name,
+                        tp_datum,
+                        Some(tp_code),
+                    )))
+                    .node(
+                        WktNode::new("ABRIDGEDTRANSFORMATION")
+                            .quoted(format!("{} to {}", source_name, tp_name))
+                            .node(WktNode::new("METHOD").quoted("Vertical Offset").id(9616))
+                            .node(wkt_parameter("Vertical Offset", differ_tp, metre(), 8603)),
+                    )
+            }
+        };
+
+        let compound = WktNode::new("COMPOUNDCRS")
+            .quoted(self.compound_crs_name()?)
+            .node(wkt_projected_crs(self.horizontal_datum, zone))
+            .node(vertical);
+
+        let mut out = String::new();
+        compound.render(0, &mut out);
+        Ok(out)
+    }
+
+    /// PROJJSON形式の複合座標系定義を出力
+    pub fn to_projjson(&self) -> Result<Value, LandXMLError> {
+        let zone = self.export_zone()?;
+        let datum = self.horizontal_datum;
+        let ellipsoid = datum.ellipsoid();
+        let (lat0, lon0) = zone.origin();
+        let (tp_name, tp_datum, tp_code) = datum.vertical_crs();
+
+        let parameter = |name: &str, value: f64, unit: &str, code: u32| {
+            json!({
+                "name": name,
+                "value": value,
+                "unit": unit,
+                "id": { "authority": "EPSG", "code": code }
+            })
+        };
+
+        let projected = json!({
+            "type": "ProjectedCRS",
+            "name": datum.projected_crs_name(zone),
+            "base_crs": {
+                "name": datum.crs_name(),
+                "datum": {
+                    "type": "GeodeticReferenceFrame",
+                    "name": datum.datum_name(),
+                    "ellipsoid": {
+                        "name": ellipsoid.name,
+                        "semi_major_axis": ellipsoid.semi_major_axis,
+                        "inverse_flattening": ellipsoid.inverse_flattening
+                    }
+                },
+                "coordinate_system": {
+                    "subtype": "ellipsoidal",
+                    "axis": [
+                        { "name": "Geodetic latitude", "abbreviation": "Lat", "direction": "north", "unit": "degree" },
+                        { "name": "Geodetic longitude", "abbreviation": "Lon", "direction": "east", "unit": "degree" }
+                    ]
+                },
+                "id": { "authority": "EPSG", "code": datum.geographic_epsg_code() }
+            },
+            "conversion": {
+                "name": format!("Japan Plane Rectangular CS zone {}", zone.roman_numeral()),
+                "method": {
+                    "name": "Transverse Mercator",
+                    "id": { "authority": "EPSG", "code": 9807 }
+                },
+                "parameters": [
+                    parameter("Latitude of natural origin", lat0, "degree", 8801),
+                    parameter("Longitude of natural origin", lon0, "degree", 8802),
+                    parameter("Scale factor at natural origin", PLANE_ORIGIN_SCALE_FACTOR, "unity", 8805),
+                    parameter("False easting", 0.0, "metre", 8806),
+                    parameter("False northing", 0.0, "metre", 8807)
+                ]
+            },
+            "coordinate_system": {
+                "subtype": "Cartesian",
+                "axis": [
+                    { "name": "Northing", "abbreviation": "X", "direction": "north", "unit": "metre" },
+                    { "name": "Easting", "abbreviation": "Y", "direction": "east", "unit": "metre" }
+                ]
+            },
+            "id": { "authority": "EPSG", "code": datum.projected_epsg_code(zone) }
+        });
+
+        let vertical_crs = |name: &str, datum_name: &str, code: Option<u32>| {
+            let mut crs = json!({
+                "type": "VerticalCRS",
+                "name": name,
+                "datum": { "type": "VerticalReferenceFrame", "name": datum_name },
+                "coordinate_system": {
+                    "subtype": "vertical",
+                    "axis": [
+                        { "name": "Gravity-related height", "abbreviation": "H", "direction": "up", "unit": "metre" }
+                    ]
+                }
+            });
+            if let Some(code) = code {
+                crs["id"] = json!({ "authority": "EPSG", "code": code });
+            }
+            crs
+        };
+
+        let vertical = match self.vertical_component() {
+            VerticalComponent::Standard => vertical_crs(tp_name, tp_datum, Some(tp_code)),
+            VerticalComponent::RiverDatum {
+                datum: river,
+                differ_tp,
+            } => {
+                let source_name = format!("{} height", river.as_str());
+                json!({
+                    "type": "BoundCRS",
+                    "source_crs": vertical_crs(&source_name, river.as_str(), None),
+                    "target_crs": vertical_crs(tp_name, tp_datum, Some(tp_code)),
+                    "transformation": {
+                        "name": format!("{} to {}", source_name, tp_name),
+                        "method": {
+                            "name": "Vertical Offset",
+                            "id": { "authority": "EPSG", "code": 9616 }
+                        },
+                        "parameters": [parameter("Vertical Offset", differ_tp, "metre", 8603)]
+                    }
+                })
+            }
+        };
+
+        Ok(json!({
+            "$schema": PROJJSON_SCHEMA,
+            "type": "CompoundCRS",
+            "name": self.compound_crs_name()?,
+            "components": [projected, vertical]
+        }))
+    }
+
+    /// 地理座標（緯度, 経度, T.P標高）から平面直角座標（X, Y, 標高）へのPROJパイプライン
+    ///
+    /// 河川基準面の場合は最後にdifferTP分の鉛直オフセットを適用します。
+    pub fn to_proj_pipeline(&self) -> Result<String, LandXMLError> {
+        let zone = self.export_zone()?;
+        let mut pipeline = format!(
+            "+proj=pipeline +step +proj=axisswap +order=2,1 \
+             +step +proj=unitconvert +xy_in=deg +xy_out=rad \
+             +step {} +ellps={} \
+             +step +proj=axisswap +order=2,1",
+            tmerc_parameters(zone),
+            self.horizontal_datum.ellipsoid().proj_name
+        );
+        if let VerticalComponent::RiverDatum { differ_tp, .. } = self.vertical_component() {
+            // 河川基準面の標高 = T.P標高 - differTP
+            pipeline.push_str(&format!(
+                " +step +proj=affine +zoff={}",
+                format_number(-differ_tp)
+            ));
+        }
+        Ok(pipeline)
+    }
+
+    /// 水平座標系のPROJ文字列（CRS定義）
+    pub fn to_proj4_string(&self) -> Result<String, LandXMLError> {
+        let zone = self.export_zone()?;
+        Ok(self.horizontal_datum.proj4_definition(zone))
+    }
+
+    /// proj4_stringが未設定の場合に測地原子・系番号から設定
+    pub fn fill_proj4_string(&mut self) {
+        if self.proj4_string.is_none() {
+            self.proj4_string = self.to_proj4_string().ok();
+        }
+    }
+}
+
+// ============================================================================
+// CRS定義の解析
+// ============================================================================
+
+/// 平面直角座標系と水平測地原子の組
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlaneCrs {
+    /// 平面直角座標系
+    pub zone: JapanPlaneCoordinateSystem,
+    /// 水平測地原子
+    pub horizontal_datum: HorizontalDatum,
+}
+
+impl PlaneCrs {
+    /// 平面直角座標系のEPSGコードを取得
+    pub fn epsg_code(&self) -> u32 {
+        self.horizontal_datum.projected_epsg_code(self.zone)
+    }
+
+    /// EPSGコードから判定
+    ///
+    /// JGD2000: 2443～2461、JGD2011: 6669～6687、旧日本測地系: 30161～30179
+    pub fn from_epsg(code: u32) -> Result<Self, LandXMLError> {
+        let (horizontal_datum, first) = match code {
+            2443..=2461 => (HorizontalDatum::JGD2000, 2443),
+            6669..=6687 => (HorizontalDatum::JGD2011, 6669),
+            30161..=30179 => (HorizontalDatum::TD, 30161),
+            _ => {
+                return Err(LandXMLError::InvalidCoordinateSystem(format!(
+                    "EPSG:{} is not a Japan Plane Rectangular CS",
+                    code
+                )))
+            }
+        };
+        let zone = JapanPlaneCoordinateSystem::from_zone_number((code - first + 1) as u8)?;
+        Ok(Self {
+            zone,
+            horizontal_datum,
+        })
+    }
+
+    /// EPSGコード表記（"6677"、"EPSG:6677"、URN、URL）から判定
+    ///
+    /// 複合座標系（"EPSG:6677+6695"、複合URN・URL）は最初の成分（水平座標系）を使用します。
+    pub fn from_epsg_str(value: &str) -> Result<Self, LandXMLError> {
+        let horizontal = value
+            .trim()
+            .split(['+', ',', '&'])
+            .find(|part| part.contains(|c: char| c.is_ascii_digit()))
+            .unwrap_or_default();
+        let code = horizontal
+            .rsplit(|c: char| !c.is_ascii_digit())
+            .find(|part| !part.is_empty())
+            .and_then(|digits| digits.parse::<u32>().ok())
+            .ok_or_else(|| {
+                LandXMLError::InvalidCoordinateSystem(format!("Invalid EPSG code: {}", value))
+            })?;
+        Self::from_epsg(code)
+    }
+
+    /// WKT（WKT1/WKT2）から判定
+    ///
+    /// 平面直角座標系のEPSG識別子があればそれを使用し、なければ
+    /// 測地原子名と投影原点のパラメータから判定します。
+    pub fn from_wkt(wkt: &str) -> Result<Self, LandXMLError> {
+        let id_re = regex(r#"(?i)(?:\bID|AUTHORITY)\[\s*"EPSG"\s*,\s*"?(\d+)"?"#)?;
+        if let Some(crs) = id_re
+            .captures_iter(wkt)
+            .filter_map(|c| c[1].parse::<u32>().ok())
+            .find_map(|code| Self::from_epsg(code).ok())
+        {
+            return Ok(crs);
+        }
+
+        let horizontal_datum = datum_from_text(wkt).ok_or_else(|| {
+            LandXMLError::InvalidCoordinateSystem("Unknown geodetic datum in WKT".to_string())
+        })?;
+
+        let parameter = |names: &str| -> Result<Option<f64>, LandXMLError> {
+            let re = regex(&format!(
+                r#"(?i)PARAMETER\[\s*"(?:{})"\s*,\s*([-+0-9.eE]+)"#,
+                names
+            ))?;
+            Ok(re.captures(wkt).and_then(|c| c[1].parse::<f64>().ok()))
+        };
+        let lat0 = parameter("Latitude of natural origin|latitude_of_origin")?;
+        let lon0 = parameter("Longitude of natural origin|central_meridian")?;
+        let scale = parameter("Scale factor at natural origin|scale_factor")?;
+
+        Self::from_parameters(horizontal_datum, lat0, lon0, scale)
+    }
+
+    /// PROJ文字列（CRS定義・パイプライン）から判定
+    ///
+    /// GRS80楕円体の場合はJGD2011とみなします（JGD2000とは区別できません）。
+    pub fn from_proj(proj: &str) -> Result<Self, LandXMLError> {
+        let tokens: Vec<&str> = proj.split_whitespace().collect();
+
+        if let Some(init) = tokens.iter().find_map(|t| t.strip_prefix("+init=")) {
+            return Self::from_epsg_str(init);
+        }
+
+        // パイプラインの場合はtmercのステップのパラメータのみを使用
+        let start = tokens
+            .iter()
+            .position(|t| *t == "+proj=tmerc")
+            .ok_or_else(|| {
+                LandXMLError::InvalidCoordinateSystem(format!(
+                    "Not a transverse mercator definition: {}",
+                    proj
+                ))
+            })?;
+        let step = tokens[start..]
+            .iter()
+            .take_while(|t| **t != "+step")
+            .filter_map(|t| t.trim_start_matches('+').split_once('='))
+            .collect::<Vec<_>>();
+        let value = |key: &str| step.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        let number = |key: &str| value(key).and_then(|v| v.parse::<f64>().ok());
+
+        let horizontal_datum = match (value("ellps"), value("datum")) {
+            (_, Some(datum)) if datum.eq_ignore_ascii_case("tokyo") => HorizontalDatum::TD,
+            (Some(ellps), _) if ellps.eq_ignore_ascii_case("bessel") => HorizontalDatum::TD,
+            (Some(ellps), _) if ellps.eq_ignore_ascii_case("GRS80") => HorizontalDatum::JGD2011,
+            _ => datum_from_text(proj).ok_or_else(|| {
+                LandXMLError::InvalidCoordinateSystem(format!(
+                    "Unknown ellipsoid in PROJ string: {}",
+                    proj
+                ))
+            })?,
+        };
+
+        Self::from_parameters(
+            horizontal_datum,
+            number("lat_0"),
+            number("lon_0"),
+            number("k").or_else(|| number("k_0")),
+        )
+    }
+
+    /// EPSGコード・WKT・PROJ文字列を自動判別して判定
+    pub fn parse(definition: &str) -> Result<Self, LandXMLError> {
+        let definition = definition.trim();
+        if definition.contains("+proj=") || definition.contains("+init=") {
+            Self::from_proj(definition)
+        } else if definition.contains('[') {
+            Self::from_wkt(definition)
+        } else {
+            Self::from_epsg_str(definition)
+        }
+    }
+
+    /// 投影原点と縮尺係数から判定
+    fn from_parameters(
+        horizontal_datum: HorizontalDatum,
+        lat0: Option<f64>,
+        lon0: Option<f64>,
+        scale: Option<f64>,
+    ) -> Result<Self, LandXMLError> {
+        let (Some(lat0), Some(lon0)) = (lat0, lon0) else {
+            return Err(LandXMLError::InvalidCoordinateSystem(
+                "Missing projection origin".to_string(),
+            ));
+        };
+        if let Some(scale) = scale {
+            if (scale - PLANE_ORIGIN_SCALE_FACTOR).abs() > 1e-9 {
+                return Err(LandXMLError::InvalidCoordinateSystem(format!(
+                    "Scale factor {} does not match Japan Plane Rectangular CS",
+                    scale
+                )));
+            }
+        }
+        let zone = JapanPlaneCoordinateSystem::from_origin(lat0, lon0).ok_or_else(|| {
+            LandXMLError::InvalidCoordinateSystem(format!(
+                "No Japan Plane Rectangular CS with origin {}, {}",
+                lat0, lon0
+            ))
+        })?;
+        Ok(Self {
+            zone,
+            horizontal_datum,
+        })
+    }
+}
+
+impl JapanPlaneCoordinateSystem {
+    /// 原点の緯度・経度（度）から平面直角座標系を検索
+    pub fn from_origin(lat: f64, lon: f64) -> Option<Self> {
+        const TOLERANCE: f64 = 1e-6;
+        Self::all_zones().into_iter().find(|zone| {
+            let (lat0, lon0) = zone.origin();
+            (lat0 - lat).abs() < TOLERANCE && (lon0 - lon).abs() < TOLERANCE
+        })
+    }
+}
+
+/// 定義文字列に含まれる測地原子名から水平測地原子を判定
+fn datum_from_text(text: &str) -> Option<HorizontalDatum> {
+    let upper = text.to_uppercase();
+    if upper.contains("JGD2011")
+        || upper.contains("JGD_2011")
+        || upper.contains("JAPANESE GEODETIC DATUM 2011")
+    {
+        Some(HorizontalDatum::JGD2011)
+    } else if upper.contains("JGD2000")
+        || upper.contains("JGD_2000")
+        || upper.contains("JAPANESE GEODETIC DATUM 2000")
+    {
+        Some(HorizontalDatum::JGD2000)
+    } else if upper.contains("TOKYO") || upper.contains("BESSEL") {
+        Some(HorizontalDatum::TD)
+    } else {
+        None
+    }
+}
+
+fn regex(pattern: &str) -> Result<Regex, LandXMLError> {
+    Regex::new(pattern)
+        .map_err(|e| LandXMLError::ParseError(format!("Regex compilation failed: {}", e)))
+}
+
+impl JLandXmlCoordinateSystem {
+    /// epsgCode・ogcWktCode・PROJ文字列から平面直角座標系を判定
+    pub fn detect_plane_crs(&self) -> Option<PlaneCrs> {
+        let base = self.base.as_ref();
+        let epsg = self
+            .epsg_code
+            .as_deref()
+            .or_else(|| base.and_then(|b| b.epsg_code.as_deref()));
+        let wkt = base.and_then(|b| b.ogc_wkt_code.as_deref());
+
+        epsg.and_then(|code| PlaneCrs::from_epsg_str(code).ok())
+            .or_else(|| wkt.and_then(|wkt| PlaneCrs::from_wkt(wkt).ok()))
+            .or_else(|| {
+                self.proj4_string
+                    .as_deref()
+                    .and_then(|proj| PlaneCrs::from_proj(proj).ok())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coordinate_system(vertical: VerticalDatum) -> JLandXmlCoordinateSystem {
+        JLandXmlCoordinateSystem::new(
+            "CRS1".to_string(),
+            HorizontalDatum::JGD2011,
+            vertical,
+            "9(X,Y)".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_epsg_codes_by_datum() {
+        let zone = JapanPlaneCoordinateSystem::Zone9;
+        assert_eq!(HorizontalDatum::JGD2000.projected_epsg_code(zone), 2451);
+        assert_eq!(HorizontalDatum::JGD2011.projected_epsg_code(zone), 6677);
+        assert_eq!(HorizontalDatum::TD.projected_epsg_code(zone), 30169);
+        for datum in [
+            HorizontalDatum::JGD2000,
+            HorizontalDatum::JGD2011,
+            HorizontalDatum::TD,
+        ] {
+            assert_eq!(
+                HorizontalDatum::from_geographic_epsg(datum.geographic_epsg_code()).unwrap(),
+                datum
+            );
+        }
+        assert!(HorizontalDatum::from_geographic_epsg(4326).is_err());
+    }
+
+    #[test]
+    fn test_proj4_string() {
+        let cs = coordinate_system(VerticalDatum::TP);
+        assert_eq!(
+            cs.proj4_string.as_deref(),
+            Some(
+                "+proj=tmerc +lat_0=36 +lon_0=139.833333333333 +k=0.9999 +x_0=0 +y_0=0 \
+                 +ellps=GRS80 +units=m +no_defs"
+            )
+        );
+        let tokyo = HorizontalDatum::TD.proj4_definition(JapanPlaneCoordinateSystem::Zone9);
+        assert!(tokyo.contains("+ellps=bessel +towgs84=-146.414,507.337,680.507"));
+    }
+
+    #[test]
+    fn test_wkt2_compound_crs() {
+        let wkt = coordinate_system(VerticalDatum::TP).to_wkt2().unwrap();
+        assert!(wkt.starts_with(
+            "COMPOUNDCRS[\"JGD2011 / Japan Plane Rectangular CS IX + JGD2011 (vertical) height\""
+        ));
+        assert!(wkt.contains("ID[\"EPSG\",6677]"));
+        assert!(wkt.contains("ID[\"EPSG\",6695]"));
+        assert!(wkt.contains("PARAMETER[\"Longitude of natural origin\",139.833333333333"));
+        assert_eq!(wkt.matches('[').count(), wkt.matches(']').count());
+
+        let river = coordinate_system(VerticalDatum::YP).to_wkt2().unwrap();
+        assert!(river.contains("BOUNDCRS["));
+        assert!(river.contains("VERTCRS[\"Y.P height\""));
+        assert!(river.contains("PARAMETER[\"Vertical Offset\",-0.8402"));
+    }
+
+    #[test]
+    fn test_projjson_and_pipeline() {
+        let cs = coordinate_system(VerticalDatum::YP).with_differ_tp(-0.84);
+        let projjson = cs.to_projjson().unwrap();
+        assert_eq!(projjson["type"], "CompoundCRS");
+        assert_eq!(projjson["components"][0]["id"]["code"], 6677);
+        assert_eq!(projjson["components"][1]["type"], "BoundCRS");
+        assert_eq!(
+            projjson["components"][1]["transformation"]["parameters"][0]["value"],
+            -0.84
+        );
+
+        let pipeline = cs.to_proj_pipeline().unwrap();
+        assert!(pipeline.starts_with("+proj=pipeline +step +proj=axisswap +order=2,1"));
+        assert!(pipeline.contains("+proj=tmerc +lat_0=36 +lon_0=139.833333333333"));
+        assert!(pipeline.ends_with("+step +proj=affine +zoff=0.84"));
+    }
+
+    #[test]
+    fn test_plane_crs_from_epsg() {
+        let crs = PlaneCrs::from_epsg(2451).unwrap();
+        assert_eq!(crs.zone, JapanPlaneCoordinateSystem::Zone9);
+        assert_eq!(crs.horizontal_datum, HorizontalDatum::JGD2000);
+        assert_eq!(
+            PlaneCrs::from_epsg(30179).unwrap().zone,
+            JapanPlaneCoordinateSystem::Zone19
+        );
+        assert_eq!(PlaneCrs::parse("EPSG:6676").unwrap().epsg_code(), 6676);
+        assert_eq!(
+            PlaneCrs::parse("urn:ogc:def:crs:EPSG::6677")
+                .unwrap()
+                .epsg_code(),
+            6677
+        );
+        // 複合座標系（水平 + 鉛直）
+        for compound in [
+            "EPSG:6677+6695",
+            "urn:ogc:def:crs,crs:EPSG::6677,crs:EPSG::6695",
+            "http://www.opengis.net/def/crs-compound?\
+             1=http://www.opengis.net/def/crs/EPSG/0/6677&\
+             2=http://www.opengis.net/def/crs/EPSG/0/6695",
+        ] {
+            assert_eq!(PlaneCrs::parse(compound).unwrap().epsg_code(), 6677);
+        }
+        assert!(PlaneCrs::parse("EPSG:6695+6677").is_err());
+        assert!(PlaneCrs::from_epsg(4326).is_err());
+    }
+
+    #[test]
+    fn test_plane_crs_round_trip() {
+        for datum in [
+            HorizontalDatum::JGD2000,
+            HorizontalDatum::JGD2011,
+            HorizontalDatum::TD,
+        ] {
+            for zone in JapanPlaneCoordinateSystem::all_zones() {
+                let cs = coordinate_system(VerticalDatum::TP)
+                    .with_horizontal_datum(datum)
+                    .with_horizontal_coordinate_system_name(format!("{}(X,Y)", zone.zone_number()));
+                let expected = PlaneCrs {
+                    zone,
+                    horizontal_datum: datum,
+                };
+                assert_eq!(PlaneCrs::parse(&cs.to_wkt2().unwrap()).unwrap(), expected);
+
+                let proj = PlaneCrs::parse(cs.proj4_string.as_deref().unwrap()).unwrap();
+                assert_eq!(proj.zone, zone);
+                let pipeline = PlaneCrs::parse(&cs.to_proj_pipeline().unwrap()).unwrap();
+                assert_eq!(pipeline.zone, zone);
+            }
+        }
+    }
+
+    #[test]
+    fn test_plane_crs_from_wkt1_parameters() {
+        let wkt = r#"PROJCS["JGD_2011_Japan_Zone_8",GEOGCS["GCS_JGD_2011",
+            DATUM["D_JGD_2011",SPHEROID["GRS_1980",6378137.0,298.257222101]],
+            PRIMEM["Greenwich",0.0],UNIT["Degree",0.0174532925199433]],
+            PROJECTION["Transverse_Mercator"],PARAMETER["False_Easting",0.0],
+            PARAMETER["False_Northing",0.0],PARAMETER["Central_Meridian",138.5],
+            PARAMETER["Scale_Factor",0.9999],PARAMETER["Latitude_Of_Origin",36.0],
+            UNIT["Meter",1.0]]"#;
+        let crs = PlaneCrs::from_wkt(wkt).unwrap();
+        assert_eq!(crs.zone, JapanPlaneCoordinateSystem::Zone8);
+        assert_eq!(crs.horizontal_datum, HorizontalDatum::JGD2011);
+    }
+
+    #[test]
+    fn test_from_base_detects_zone() {
+        use crate::coordinate_system::CoordinateSystem;
+
+        let cs = JLandXmlCoordinateSystem::from_base(CoordinateSystem {
+            name: "Tokyo zone 2".to_string(),
+            epsg_code: Some("30162".to_string()),
+            proj4_string: None,
+            ogc_wkt_code: None,
+            extensions: Default::default(),
+        });
+        assert_eq!(
+            cs.plane_coordinate_zone,
+            Some(JapanPlaneCoordinateSystem::Zone2)
+        );
+        assert_eq!(cs.horizontal_datum, HorizontalDatum::TD);
+        assert_eq!(cs.get_plane_coordinate_epsg(), Some(30162));
+
+        let unknown = JLandXmlCoordinateSystem::from_base(CoordinateSystem {
+            name: "local".to_string(),
+            epsg_code: None,
+            proj4_string: None,
+            ogc_wkt_code: None,
+            extensions: Default::default(),
+        });
+        assert_eq!(unknown.plane_coordinate_zone, None);
+    }
+}
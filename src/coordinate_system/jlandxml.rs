@@ -1,4 +1,4 @@
-//! J-LandXML Ver.1.6 完全対応 座標系定義・変換モジュール
+//! J-LandXML Ver.1.5〜1.7 対応 座標系定義・変換モジュール
 //!
 //! 日本の測量法に基づく座標系の完全実装：
 //! - 水平測地原子（JGD2000/JGD2011/TD）
@@ -11,7 +11,12 @@ use crate::models::LandXML;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use super::migration::{MigrationReport, VersionMigrator};
 use super::standard::CoordinateSystem;
+use super::version::JLandXmlVersion;
+use super::vertical_registry::{TpOffsetQuery, VerticalDatumRegistry};
+use crate::units::Units;
+use crate::xml::XmlElement;
 
 // ============================================================================
 // 平面直角座標系
@@ -111,6 +116,31 @@ impl JapanPlaneCoordinateSystem {
         }
     }
 
+    /// 系番号のローマ数字表記を取得（"IX"等）
+    pub fn roman_numeral(&self) -> &'static str {
+        match self {
+            Self::Zone1 => "I",
+            Self::Zone2 => "II",
+            Self::Zone3 => "III",
+            Self::Zone4 => "IV",
+            Self::Zone5 => "V",
+            Self::Zone6 => "VI",
+            Self::Zone7 => "VII",
+            Self::Zone8 => "VIII",
+            Self::Zone9 => "IX",
+            Self::Zone10 => "X",
+            Self::Zone11 => "XI",
+            Self::Zone12 => "XII",
+            Self::Zone13 => "XIII",
+            Self::Zone14 => "XIV",
+            Self::Zone15 => "XV",
+            Self::Zone16 => "XVI",
+            Self::Zone17 => "XVII",
+            Self::Zone18 => "XVIII",
+            Self::Zone19 => "XIX",
+        }
+    }
+
     /// 適用地域の説明を取得
     pub fn description(&self) -> &'static str {
         match self {
@@ -228,6 +258,7 @@ impl HorizontalDatum {
     }
 
     /// 文字列から測地原子を解析
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self, LandXMLError> {
         match s.trim() {
             "JGD2000" => Ok(Self::JGD2000),
@@ -297,6 +328,7 @@ impl VerticalDatum {
     }
 
     /// 文字列から鉛直原子を解析
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self, LandXMLError> {
         match s.trim() {
             "T.P" => Ok(Self::TP),
@@ -397,6 +429,100 @@ impl CoordinateSystemMapper {
         Ok(None)
     }
 
+    /// horizontalCoordinateSystemName を表記ゆれを許容してパース
+    ///
+    /// 全角数字・括弧（"９（Ｘ，Ｙ）"）、ローマ数字（"IX系"）、"9系"、空白を含む表記を
+    /// 正規形（"9(X,Y)"）に変換します。解釈できない場合はNoneを返します。
+    pub fn normalize_horizontal_coordinate_system_name(
+        name: &str,
+    ) -> Option<NormalizedCoordinateSystemName> {
+        use regex::Regex;
+
+        let mut notes = Vec::new();
+
+        // 全角英数字・記号を半角に変換
+        let mut converted = String::with_capacity(name.len());
+        let mut full_width = false;
+        let mut roman_symbol = false;
+        for c in name.chars() {
+            match c {
+                '\u{FF01}'..='\u{FF5E}' => {
+                    full_width = true;
+                    converted.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c));
+                }
+                '\u{3000}' => {
+                    full_width = true;
+                    converted.push(' ');
+                }
+                '、' => {
+                    full_width = true;
+                    converted.push(',');
+                }
+                '\u{2160}'..='\u{216B}' | '\u{2170}'..='\u{217B}' => {
+                    // ローマ数字記号（Ⅰ～Ⅻ、ⅰ～ⅻ）
+                    roman_symbol = true;
+                    let index = (c as u32 - 0x2160) % 16;
+                    let zone = JapanPlaneCoordinateSystem::from_zone_number(index as u8 + 1).ok();
+                    match zone {
+                        Some(zone) => converted.push_str(zone.roman_numeral()),
+                        None => converted.push(c),
+                    }
+                }
+                _ => converted.push(c),
+            }
+        }
+        if full_width {
+            notes.push("full-width characters converted to half-width".to_string());
+        }
+        if roman_symbol {
+            notes.push("roman numeral symbols converted to letters".to_string());
+        }
+
+        let compact: String = converted.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.len() != converted.len() {
+            notes.push("whitespace removed".to_string());
+        }
+
+        let re = Regex::new(r"^(?i)第?(\d{1,2}|[IVX]+)(系)?(\(X,Y\))?$").ok()?;
+        let captures = re.captures(&compact)?;
+        let zone_text = captures.get(1)?.as_str();
+        let has_suffix = captures.get(2).is_some();
+        let has_axes = captures.get(3).is_some();
+
+        let zone = match zone_text.parse::<u8>() {
+            Ok(number) => JapanPlaneCoordinateSystem::from_zone_number(number).ok()?,
+            Err(_) => {
+                let upper = zone_text.to_ascii_uppercase();
+                let zone = JapanPlaneCoordinateSystem::all_zones()
+                    .into_iter()
+                    .find(|zone| zone.roman_numeral() == upper)?;
+                notes.push(format!(
+                    "roman numeral {} converted to {}",
+                    upper,
+                    zone.zone_number()
+                ));
+                zone
+            }
+        };
+
+        if has_suffix {
+            notes.push("'系' suffix replaced with (X,Y)".to_string());
+        } else if !has_axes {
+            notes.push("missing (X,Y) suffix added".to_string());
+        }
+
+        let normalized = format!("{}(X,Y)", zone.zone_number());
+        if normalized != compact && notes.is_empty() {
+            notes.push("axis notation normalized".to_string());
+        }
+
+        Some(NormalizedCoordinateSystemName {
+            zone,
+            normalized,
+            notes,
+        })
+    }
+
     /// 測地原子と平面直角座標系の整合性をチェック
     pub fn validate_datum_compatibility(
         horizontal_datum: HorizontalDatum,
@@ -414,28 +540,80 @@ impl CoordinateSystemMapper {
     }
 }
 
+/// horizontalCoordinateSystemName の正規化結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedCoordinateSystemName {
+    /// 平面直角座標系
+    pub zone: JapanPlaneCoordinateSystem,
+    /// 正規形（"9(X,Y)"）
+    pub normalized: String,
+    /// 実施した正規化の内容（正規形と一致していた場合は空）
+    pub notes: Vec<String>,
+}
+
+impl NormalizedCoordinateSystemName {
+    /// 正規化が行われたかどうか
+    pub fn is_modified(&self) -> bool {
+        !self.notes.is_empty()
+    }
+}
+
 /// 座標系の統合バリデーター
 pub struct CoordinateSystemValidator;
 
 impl CoordinateSystemValidator {
-    /// 完全な座標系設定の妥当性をチェック
+    /// 完全な座標系設定の妥当性をチェック（組み込みの鉛直原子表を使用）
     pub fn validate_complete_system(
         horizontal_datum: HorizontalDatum,
         vertical_datum: VerticalDatum,
         zone: JapanPlaneCoordinateSystem,
         differ_tp: Option<f64>,
+    ) -> Result<Vec<ValidationWarning>, LandXMLError> {
+        Self::validate_complete_system_with_registry(
+            horizontal_datum,
+            vertical_datum,
+            zone,
+            differ_tp,
+            &VerticalDatumRegistry::builtin(),
+            &TpOffsetQuery::new(),
+        )
+    }
+
+    /// 鉛直原子レジストリを指定して完全な座標系設定の妥当性をチェック
+    ///
+    /// `query`は差分を照会する河川・地域と適用日です。
+    /// 地域未指定の場合、いずれかの地域の差分と一致すれば妥当とみなします。
+    pub fn validate_complete_system_with_registry(
+        horizontal_datum: HorizontalDatum,
+        vertical_datum: VerticalDatum,
+        zone: JapanPlaneCoordinateSystem,
+        differ_tp: Option<f64>,
+        registry: &VerticalDatumRegistry,
+        query: &TpOffsetQuery,
     ) -> Result<Vec<ValidationWarning>, LandXMLError> {
         let mut warnings = Vec::new();
+        let (region, date) = (query.region.as_deref(), query.date);
 
         CoordinateSystemMapper::validate_datum_compatibility(horizontal_datum, zone)?;
 
         if CoordinateSystemMapper::needs_tp_correction(vertical_datum) {
+            let expected_diff = registry
+                .tp_offset(vertical_datum, region, date)
+                .unwrap_or_else(|| vertical_datum.tp_offset());
+
             match differ_tp {
                 Some(provided_diff) => {
-                    let expected_diff = vertical_datum.tp_offset();
                     let tolerance = 0.001;
+                    let matches = |expected: f64| (provided_diff - expected).abs() <= tolerance;
 
-                    if (provided_diff - expected_diff).abs() > tolerance {
+                    let accepted = matches(expected_diff)
+                        || (region.is_none()
+                            && registry
+                                .candidate_offsets(vertical_datum, date)
+                                .into_iter()
+                                .any(matches));
+
+                    if !accepted {
                         warnings.push(ValidationWarning::DifferTpMismatch {
                             vertical_datum,
                             provided: provided_diff,
@@ -446,7 +624,7 @@ impl CoordinateSystemValidator {
                 None => {
                     warnings.push(ValidationWarning::MissingDifferTp {
                         vertical_datum,
-                        expected: vertical_datum.tp_offset(),
+                        expected: expected_diff,
                     });
                 }
             }
@@ -482,6 +660,18 @@ pub enum ValidationWarning {
     UnnecessaryDifferTp,
     /// 旧測地系の使用
     LegacyDatumUsage { datum: HorizontalDatum },
+    /// horizontalCoordinateSystemName を正規化して解釈した
+    NormalizedCoordinateSystemName {
+        original: String,
+        normalized: String,
+        notes: Vec<String>,
+    },
+    /// applicationCriterionのバージョンに対応するプロファイルがない
+    UnsupportedVersion { version: String },
+    /// 宣言バージョンで使用できない構成要素
+    VersionIncompatibleConstruct { version: String, construct: String },
+    /// 未定義のPのidを参照する等、三角形として解釈できない面
+    UnresolvedSurfaceFaces { surface: String, count: usize },
 }
 
 impl fmt::Display for ValidationWarning {
@@ -521,6 +711,36 @@ impl fmt::Display for ValidationWarning {
                     datum.as_str()
                 )
             }
+            ValidationWarning::NormalizedCoordinateSystemName {
+                original,
+                normalized,
+                notes,
+            } => {
+                write!(
+                    f,
+                    "horizontalCoordinateSystemName '{}' interpreted as '{}' ({})",
+                    original,
+                    normalized,
+                    notes.join(", ")
+                )
+            }
+            ValidationWarning::UnsupportedVersion { version } => {
+                write!(f, "Unsupported J-LandXML version '{}'", version)
+            }
+            ValidationWarning::VersionIncompatibleConstruct { version, construct } => {
+                write!(
+                    f,
+                    "{} is not defined in J-LandXML Ver.{}",
+                    construct, version
+                )
+            }
+            ValidationWarning::UnresolvedSurfaceFaces { surface, count } => {
+                write!(
+                    f,
+                    "Surface '{}' has {} faces that do not reference three defined points",
+                    surface, count
+                )
+            }
         }
     }
 }
@@ -529,7 +749,7 @@ impl fmt::Display for ValidationWarning {
 // J-LandXML拡張モデル
 // ============================================================================
 
-/// J-LandXML Ver.1.6 完全対応 座標系情報
+/// J-LandXML 座標系情報
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JLandXmlCoordinateSystem {
     /// 基本属性
@@ -584,39 +804,68 @@ impl JLandXmlCoordinateSystem {
             differ_tp,
             plane_coordinate_zone,
             base: None,
-            epsg_code: plane_coordinate_zone.map(|zone| zone.epsg_code().to_string()),
-            proj4_string: None,
+            epsg_code: plane_coordinate_zone
+                .map(|zone| horizontal_datum.projected_epsg_code(zone).to_string()),
+            proj4_string: plane_coordinate_zone.map(|zone| horizontal_datum.proj4_definition(zone)),
             geoid_name: None,
         })
     }
 
     /// 標準LandXMLの座標系から作成（後方互換性）
+    ///
+    /// epsgCode・ogcWktCode・PROJ文字列から平面直角座標系と水平測地原子を判定します。
+    /// 判定できない場合、平面直角座標系は未設定となります。
     pub fn from_base(base: CoordinateSystem) -> Self {
-        Self {
+        let mut cs = Self {
             name: base.name.clone(),
             desc: None,
             horizontal_datum: HorizontalDatum::JGD2011,
             vertical_datum: VerticalDatum::TP,
-            horizontal_coordinate_system_name: "9(X,Y)".to_string(),
+            horizontal_coordinate_system_name: String::new(),
             differ_tp: None,
-            plane_coordinate_zone: Some(JapanPlaneCoordinateSystem::Zone9),
+            plane_coordinate_zone: None,
             base: Some(base.clone()),
             epsg_code: base.epsg_code,
             proj4_string: base.proj4_string,
             geoid_name: None,
+        };
+
+        if let Some(crs) = cs.detect_plane_crs() {
+            cs.horizontal_datum = crs.horizontal_datum;
+            cs.plane_coordinate_zone = Some(crs.zone);
+            cs.epsg_code = Some(crs.epsg_code().to_string());
+            cs.fill_proj4_string();
         }
+        cs
     }
 
     /// J-LandXML拡張属性を設定
     pub fn with_horizontal_coordinate_system_name(mut self, name: String) -> Self {
         if let Ok(Some(zone)) = CoordinateSystemMapper::parse_horizontal_coordinate_system_name(&name) {
-            self.plane_coordinate_zone = Some(zone);
-            self.epsg_code = Some(zone.epsg_code().to_string());
+            self = self.with_plane_coordinate_zone(zone);
         }
         self.horizontal_coordinate_system_name = name;
         self
     }
 
+    /// 平面直角座標系を設定（EPSGコード・PROJ文字列も更新）
+    pub fn with_plane_coordinate_zone(mut self, zone: JapanPlaneCoordinateSystem) -> Self {
+        self.plane_coordinate_zone = Some(zone);
+        self.epsg_code = Some(self.horizontal_datum.projected_epsg_code(zone).to_string());
+        self.proj4_string = Some(self.horizontal_datum.proj4_definition(zone));
+        self
+    }
+
+    /// 水平測地原子を設定（PROJ文字列も更新）
+    pub fn with_horizontal_datum(mut self, horizontal_datum: HorizontalDatum) -> Self {
+        self.horizontal_datum = horizontal_datum;
+        if let Some(zone) = self.plane_coordinate_zone {
+            self.epsg_code = Some(horizontal_datum.projected_epsg_code(zone).to_string());
+            self.proj4_string = Some(horizontal_datum.proj4_definition(zone));
+        }
+        self
+    }
+
     /// 鉛直原子を設定（differTPも自動更新）
     pub fn with_vertical_datum(mut self, vertical_datum: VerticalDatum) -> Self {
         self.vertical_datum = vertical_datum;
@@ -646,9 +895,10 @@ impl JLandXmlCoordinateSystem {
         self
     }
 
-    /// 平面直角座標系のEPSGコードを取得
+    /// 平面直角座標系のEPSGコードを取得（水平測地原子別）
     pub fn get_plane_coordinate_epsg(&self) -> Option<u32> {
-        self.plane_coordinate_zone.map(|zone| zone.epsg_code())
+        self.plane_coordinate_zone
+            .map(|zone| self.horizontal_datum.projected_epsg_code(zone))
     }
 
     /// T.P基準への標高変換
@@ -675,6 +925,28 @@ impl JLandXmlCoordinateSystem {
         }
     }
 
+    /// 鉛直原子レジストリを指定して座標系の妥当性をバリデーション
+    pub fn validate_with_registry(
+        &self,
+        registry: &VerticalDatumRegistry,
+        query: &TpOffsetQuery,
+    ) -> Result<Vec<ValidationWarning>, LandXMLError> {
+        if let Some(zone) = self.plane_coordinate_zone {
+            CoordinateSystemValidator::validate_complete_system_with_registry(
+                self.horizontal_datum,
+                self.vertical_datum,
+                zone,
+                self.differ_tp,
+                registry,
+                query,
+            )
+        } else {
+            Err(LandXMLError::InvalidCoordinateSystemNameFormat {
+                name: self.horizontal_coordinate_system_name.clone(),
+            })
+        }
+    }
+
     /// 座標系の詳細情報を取得
     pub fn get_coordinate_system_info(&self) -> CoordinateSystemInfo {
         CoordinateSystemInfo {
@@ -689,6 +961,44 @@ impl JLandXmlCoordinateSystem {
             geoid_name: self.geoid_name.clone(),
         }
     }
+    /// CoordinateSystem要素（J-LandXML拡張属性とdifferTPのFeature）を生成
+    ///
+    /// 平面直角座標系が判明している場合、horizontalCoordinateSystemNameは
+    /// 正規形（"9(X,Y)"）で出力します。
+    pub(crate) fn to_xml(&self) -> XmlElement {
+        let horizontal_name = match self.plane_coordinate_zone {
+            Some(zone) => format!("{}(X,Y)", zone.zone_number()),
+            None => self.horizontal_coordinate_system_name.clone(),
+        };
+        let extensions = self
+            .base
+            .as_ref()
+            .map(|base| base.extensions.clone())
+            .unwrap_or_default();
+        let differ_tp = self
+            .differ_tp
+            .filter(|_| !extensions.has_feature_property("differTP"))
+            .map(|differ_tp| {
+                XmlElement::new("Feature").with_child(
+                    XmlElement::new("Property")
+                        .with_attr("label", "differTP")
+                        .with_attr("value", differ_tp.to_string()),
+                )
+            });
+
+        XmlElement::new("CoordinateSystem")
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_opt_attr("name", Some(self.name.as_str()).filter(|n| !n.is_empty()))
+            .with_attr("horizontalDatum", self.horizontal_datum.as_str())
+            .with_attr("verticalDatum", self.vertical_datum.as_str())
+            .with_opt_attr(
+                "horizontalCoordinateSystemName",
+                Some(horizontal_name).filter(|n| !n.is_empty()),
+            )
+            .with_opt_attr("geoidName", self.geoid_name.as_deref())
+            .with_children(differ_tp)
+            .with_extensions(&extensions)
+    }
 }
 
 /// 座標系情報の統合ビュー（完全仕様対応）
@@ -723,11 +1033,19 @@ pub struct JLandXmlDocument {
     /// J-LandXML拡張座標系
     pub coordinate_system: Option<JLandXmlCoordinateSystem>,
 
-    /// J-LandXML Ver.1.6識別情報
+    /// J-LandXMLバージョン（applicationCriterionから判定、例: "1.7"）
     pub j_landxml_version: Option<String>,
 
     /// アプリケーション基準（applicationCriterion）
     pub application_criterion: Option<String>,
+
+    /// 入力ファイルの文字コード（解析時に判定、例: "Shift_JIS"）
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    /// 解析時の警告
+    #[serde(skip)]
+    pub warnings: Vec<ValidationWarning>,
 }
 
 impl JLandXmlDocument {
@@ -737,16 +1055,42 @@ impl JLandXmlDocument {
             .coordinate_system
             .as_ref()
             .map(|cs| JLandXmlCoordinateSystem::from_base(cs.clone()));
+        let application_criterion = base
+            .project
+            .as_ref()
+            .and_then(|p| p.application_criterion.clone());
+        let j_landxml_version = base.project.as_ref().and_then(|p| p.j_landxml_version());
 
         Self {
             base,
             coordinate_system,
-            j_landxml_version: None,
-            application_criterion: None,
+            j_landxml_version,
+            application_criterion,
+            encoding: None,
+            warnings: Vec::new(),
         }
     }
 
-    /// J-LandXML Ver.1.6として識別
+    /// 対応するバージョンとして判定
+    pub fn version(&self) -> Option<JLandXmlVersion> {
+        self.j_landxml_version
+            .as_deref()
+            .and_then(|v| v.parse().ok())
+    }
+
+    /// 指定バージョンへ変換した文書と変換結果の報告を返す
+    pub fn migrate_to(&self, target: JLandXmlVersion) -> (JLandXmlDocument, MigrationReport) {
+        VersionMigrator::new(target).migrate(self)
+    }
+
+    /// 文書の単位系を変換（海外のフィート単位の文書をメートル単位で扱う場合等）
+    ///
+    /// 詳細は[`Units::convert_document`]を参照してください。
+    pub fn convert_units(&mut self, target: &Units) {
+        target.convert_document(&mut self.base);
+    }
+
+    /// J-LandXMLのバージョンを設定
     pub fn with_j_landxml_version(mut self, version: String) -> Self {
         self.j_landxml_version = Some(version);
         self
@@ -911,6 +1255,8 @@ mod tests {
             name: "JGD2011 / Zone 9".to_string(),
             epsg_code: Some("6677".to_string()),
             proj4_string: None,
+            ogc_wkt_code: None,
+            extensions: Default::default(),
         };
 
         let jlandxml_cs = JLandXmlCoordinateSystem::from_base(base_cs)
@@ -934,6 +1280,9 @@ mod tests {
             coordinate_system: None,
             alignments: Vec::new(),
             features: Vec::new(),
+            cg_points: Vec::new(),
+            surfaces: Vec::new(),
+            ..Default::default()
         };
 
         let jlandxml_doc = JLandXmlDocument::from_base(base_landxml)
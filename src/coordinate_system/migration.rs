@@ -0,0 +1,275 @@
+//! J-LandXML version migration
+//!
+//! J-LandXML文書をバージョン間で変換（アップグレード・ダウングレード）します：
+//! - applicationCriterionの書き換え
+//! - projectPhaseの表記の変換（Ver.1.6以前は「詳細設計」、Ver.1.7は「詳細」）
+//! - 変換先バージョンで定義されていない可能性がある構成要素の報告（出典未確認のため削除しない）
+//! - パーサー・ライターを組み合わせたファイル単位の変換
+
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::jlandxml::{JLandXmlDocument, ValidationWarning};
+use super::parser::JLandXmlParser;
+use super::version::JLandXmlVersion;
+use crate::error::LandXMLError;
+use crate::writer::LandXmlWriter;
+
+/// 変換で行った変更
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MigrationChange {
+    /// applicationCriterionを書き換えた
+    CriterionRewritten { from: Option<String>, to: String },
+    /// Projectプロパティの値を書き換えた
+    PropertyRewritten {
+        label: String,
+        from: String,
+        to: String,
+    },
+    /// 変換先で定義されていない可能性がある構成要素を保持した
+    ///
+    /// バージョン間の差異は出典未確認のため、削除せずに報告のみ行います。
+    Incompatible { construct: String },
+}
+
+impl fmt::Display for MigrationChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationChange::CriterionRewritten { from, to } => write!(
+                f,
+                "applicationCriterion rewritten from '{}' to '{}'",
+                from.as_deref().unwrap_or("(none)"),
+                to
+            ),
+            MigrationChange::PropertyRewritten { label, from, to } => {
+                write!(f, "{} rewritten from '{}' to '{}'", label, from, to)
+            }
+            MigrationChange::Incompatible { construct } => {
+                write!(
+                    f,
+                    "kept {}, which may not be defined in the target version",
+                    construct
+                )
+            }
+        }
+    }
+}
+
+/// 変換結果の報告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// 変換元のバージョン（判定できない場合はNone）
+    pub from: Option<JLandXmlVersion>,
+    /// 変換先のバージョン
+    pub to: JLandXmlVersion,
+    /// 行った変更
+    pub changes: Vec<MigrationChange>,
+}
+
+impl MigrationReport {
+    /// 変換先で定義されていない可能性がある構成要素を列挙
+    pub fn incompatible(&self) -> impl Iterator<Item = &MigrationChange> {
+        self.changes
+            .iter()
+            .filter(|c| matches!(c, MigrationChange::Incompatible { .. }))
+    }
+}
+
+/// バージョン変換器
+#[derive(Debug, Clone)]
+pub struct VersionMigrator {
+    target: JLandXmlVersion,
+    writer: LandXmlWriter,
+}
+
+impl VersionMigrator {
+    /// 変換先バージョンを指定して作成
+    pub fn new(target: JLandXmlVersion) -> Self {
+        Self {
+            target,
+            writer: LandXmlWriter::new(),
+        }
+    }
+
+    /// ファイル出力に使用するライターを設定
+    pub fn with_writer(mut self, writer: LandXmlWriter) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    /// 文書を変換先バージョンへ変換
+    pub fn migrate(&self, doc: &JLandXmlDocument) -> (JLandXmlDocument, MigrationReport) {
+        let mut migrated = doc.clone();
+        let mut changes = Vec::new();
+
+        self.rewrite_project_phase(&mut migrated, &mut changes);
+        self.rewrite_criterion(&mut migrated, &mut changes);
+
+        // 変換元の版に由来するバージョン警告は、変換先の版の警告に置き換える
+        migrated.warnings.retain(|w| {
+            !matches!(
+                w,
+                ValidationWarning::UnsupportedVersion { .. }
+                    | ValidationWarning::VersionIncompatibleConstruct { .. }
+            )
+        });
+        for construct in self.target.profile().unsupported_constructs(&migrated) {
+            migrated
+                .warnings
+                .push(ValidationWarning::VersionIncompatibleConstruct {
+                    version: self.target.as_str().to_string(),
+                    construct: construct.clone(),
+                });
+            changes.push(MigrationChange::Incompatible { construct });
+        }
+
+        let report = MigrationReport {
+            from: doc.version(),
+            to: self.target,
+            changes,
+        };
+        (migrated, report)
+    }
+
+    /// ファイルを読み込み、変換して出力
+    pub fn convert_file<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        input: P,
+        output: Q,
+    ) -> Result<MigrationReport, LandXMLError> {
+        let doc = JLandXmlParser::from_file(input)?.parse()?;
+        let (migrated, report) = self.migrate(&doc);
+        self.writer.write_to_file(&migrated, output)?;
+        Ok(report)
+    }
+
+    fn rewrite_criterion(&self, doc: &mut JLandXmlDocument, changes: &mut Vec<MigrationChange>) {
+        let criterion = self.target.application_criterion();
+        if doc.application_criterion.as_deref() != Some(criterion.as_str()) {
+            changes.push(MigrationChange::CriterionRewritten {
+                from: doc.application_criterion.clone(),
+                to: criterion.clone(),
+            });
+        }
+        doc.application_criterion = Some(criterion.clone());
+        doc.j_landxml_version = Some(self.target.as_str().to_string());
+        doc.base
+            .project
+            .get_or_insert_with(Default::default)
+            .application_criterion = Some(criterion);
+    }
+
+    fn rewrite_project_phase(
+        &self,
+        doc: &mut JLandXmlDocument,
+        changes: &mut Vec<MigrationChange>,
+    ) {
+        let Some(project) = doc.base.project.as_mut() else {
+            return;
+        };
+        let (Some(phase), Some(current)) = (project.phase(), project.project_phase.as_ref()) else {
+            return;
+        };
+        let rewritten = if self.target >= JLandXmlVersion::V1_7 {
+            phase.as_str()
+        } else {
+            phase.as_designation()
+        };
+        if current != rewritten {
+            changes.push(MigrationChange::PropertyRewritten {
+                label: "projectPhase".to_string(),
+                from: current.clone(),
+                to: rewritten.to_string(),
+            });
+            project.project_phase = Some(rewritten.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> JLandXmlDocument {
+        JLandXmlParser::from_file("tests/data/j_landxml_sample.xml")
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_downgrade_to_1_6() {
+        let doc = sample();
+        let (migrated, report) = doc.migrate_to(JLandXmlVersion::V1_6);
+
+        assert_eq!(report.from, Some(JLandXmlVersion::V1_7));
+        // 出典未確認の差異は削除せず報告のみ
+        assert!(report.changes.contains(&MigrationChange::Incompatible {
+            construct: "Project property 'stratumMainData'".to_string(),
+        }));
+        assert_eq!(report.incompatible().count(), 2);
+        assert!(report
+            .changes
+            .contains(&MigrationChange::PropertyRewritten {
+                label: "projectPhase".to_string(),
+                from: "詳細".to_string(),
+                to: "詳細設計".to_string(),
+            }));
+
+        assert_eq!(
+            migrated.application_criterion.as_deref(),
+            Some("MlitLandXmlVer.1.6")
+        );
+        assert_eq!(migrated.version(), Some(JLandXmlVersion::V1_6));
+        assert!(migrated
+            .base
+            .project
+            .as_ref()
+            .is_some_and(|p| p.stratum_main_data.is_some()));
+        assert_eq!(
+            migrated
+                .warnings
+                .iter()
+                .filter(|w| matches!(w, ValidationWarning::VersionIncompatibleConstruct { .. }))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_convert_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let v15 = dir.path().join("v15.xml");
+        let v17 = dir.path().join("v17.xml");
+
+        let report = VersionMigrator::new(JLandXmlVersion::V1_5)
+            .convert_file("tests/data/j_landxml_sample.xml", &v15)
+            .unwrap();
+        assert!(report.changes.contains(&MigrationChange::Incompatible {
+            construct: "element 'Surfaces'".to_string(),
+        }));
+
+        let downgraded = JLandXmlParser::from_file(&v15).unwrap().parse().unwrap();
+        assert_eq!(downgraded.version(), Some(JLandXmlVersion::V1_5));
+        assert!(!downgraded.base.surfaces.is_empty());
+        assert!(downgraded.warnings.iter().any(|w| matches!(
+            w,
+            ValidationWarning::VersionIncompatibleConstruct { construct, .. }
+                if construct == "element 'Surfaces'"
+        )));
+
+        let report = VersionMigrator::new(JLandXmlVersion::V1_7)
+            .convert_file(&v15, &v17)
+            .unwrap();
+        assert_eq!(report.incompatible().count(), 0);
+        let upgraded = JLandXmlParser::from_file(&v17).unwrap().parse().unwrap();
+        let project = upgraded.base.project.as_ref().unwrap();
+        assert_eq!(project.project_phase.as_deref(), Some("詳細"));
+        assert_eq!(
+            project.application_criterion.as_deref(),
+            Some("MlitLandXmlVer.1.7")
+        );
+    }
+}
@@ -5,10 +5,18 @@
 //! - J-LandXML extensions (Japan Plane Coordinate System 1-19)
 //! - Horizontal/Vertical datum definitions
 //! - Coordinate system validation
+//! - J-LandXML version profiles (Ver.1.5 / 1.6 / 1.7) and migration between them
 
+pub mod crs_definition;
 pub mod jlandxml;
+pub mod migration;
 pub mod parser;
+pub mod projection;
+pub mod reduction;
 pub mod standard;
+pub mod version;
+pub mod vertical_registry;
+pub mod vertical_transform;
 
 // 標準LandXML座標系
 pub use standard::CoordinateSystem;
@@ -17,9 +25,24 @@ pub use standard::CoordinateSystem;
 pub use jlandxml::{
     CoordinateSystemInfo, CoordinateSystemMapper, CoordinateSystemValidator, HorizontalDatum,
     JLandXmlCoordinateSystem, JLandXmlDocument, JLandXmlProperty, JapanPlaneCoordinateSystem,
-    ValidationWarning, VerticalDatum,
+    NormalizedCoordinateSystemName, ValidationWarning, VerticalDatum,
 };
 
+// CRS定義の解析
+pub use crs_definition::PlaneCrs;
+
+// 平面距離・地上距離の換算
+pub use reduction::DistanceReduction;
+
+// 鉛直原子レジストリ
+pub use vertical_registry::{
+    CalendarDate, DatedTpOffset, TpOffsetQuery, VerticalDatumDefinition, VerticalDatumRegistry,
+};
+
+// バージョンプロファイルと変換
+pub use migration::{MigrationChange, MigrationReport, VersionMigrator};
+pub use version::{JLandXmlVersion, VersionProfile};
+
 // パーサー
 pub use parser::{CoordinateSystemNameParser, JLandXmlParser, ParsingStats};
 
@@ -1,13 +1,24 @@
-//! J-LandXML Ver.1.6 専用パーサー
+//! J-LandXML（Ver.1.5〜1.7）専用パーサー
 //!
-//! J-LandXML特有の属性と要素をパースします。
+//! J-LandXML特有の属性と要素をパースし、applicationCriterionから判定した
+//! バージョンのプロファイルで構成要素を検査します。
+//! 入力はBOM・XML宣言から文字コード（UTF-8 / Shift_JIS / EUC-JP / UTF-16）を判定して読み込みます。
 
 use super::jlandxml::{
-    CoordinateSystemMapper, JLandXmlCoordinateSystem, JLandXmlDocument, JapanPlaneCoordinateSystem,
-    VerticalDatum,
+    CoordinateSystemMapper, HorizontalDatum, JLandXmlCoordinateSystem, JLandXmlDocument,
+    JapanPlaneCoordinateSystem, ValidationWarning, VerticalDatum,
 };
+use super::version::JLandXmlVersion;
+use crate::application::Application;
+use crate::cg_points::CgPoints;
+use crate::coordinate_system::CoordinateSystem;
 use crate::error::LandXMLError;
-use crate::models::LandXML;
+use crate::models::{Alignment, LandXML};
+use crate::project::Project;
+use crate::roadways::Roadways;
+use crate::surfaces::Surfaces;
+use crate::units::Units;
+use crate::xml::{self, XmlElement};
 use quick_xml::events::Event;
 use quick_xml::Reader;
 
@@ -20,6 +31,30 @@ impl CoordinateSystemNameParser {
         CoordinateSystemMapper::parse_horizontal_coordinate_system_name(name)
     }
 
+    /// horizontalCoordinateSystemName属性を表記ゆれを許容してパース
+    ///
+    /// 正規化を行った場合は、その内容を警告として返します。
+    pub fn parse_lenient(
+        name: &str,
+    ) -> (
+        Option<JapanPlaneCoordinateSystem>,
+        Option<ValidationWarning>,
+    ) {
+        match CoordinateSystemMapper::normalize_horizontal_coordinate_system_name(name) {
+            Some(result) => {
+                let warning = result.is_modified().then(|| {
+                    ValidationWarning::NormalizedCoordinateSystemName {
+                        original: name.to_string(),
+                        normalized: result.normalized.clone(),
+                        notes: result.notes.clone(),
+                    }
+                });
+                (Some(result.zone), warning)
+            }
+            None => (None, None),
+        }
+    }
+
     /// 座標系名の妥当性をチェック
     pub fn validate(name: &str) -> bool {
         Self::parse(name).map(|opt| opt.is_some()).unwrap_or(false)
@@ -38,6 +73,8 @@ impl CoordinateSystemNameParser {
 pub struct JLandXmlParser {
     /// ファイルパス（再パース用）
     file_path: std::path::PathBuf,
+    /// 厳格モード（表記ゆれを許容しない）
+    strict: bool,
 }
 
 impl JLandXmlParser {
@@ -50,22 +87,85 @@ impl JLandXmlParser {
                 format!("File not found: {}", file_path.display()),
             )));
         }
-        Ok(Self { file_path })
+        Ok(Self {
+            file_path,
+            strict: false,
+        })
+    }
+
+    /// 厳格モードを設定
+    ///
+    /// 厳格モードではhorizontalCoordinateSystemNameが正規形（"9(X,Y)"）でない場合、
+    /// applicationCriterionのバージョンに対応していない場合（`UnsupportedJLandXmlVersion`）、
+    /// 未定義の点を参照する面がある場合（`InvalidFormat`）に
+    /// エラーとします。既定では表記ゆれを正規化し、いずれも警告として記録します。
+    /// 宣言バージョンで使用できない構成要素は、バージョン間の差異の出典が未確認のため
+    /// 厳格モードでも警告として記録します。
+    pub fn with_strict_mode(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
     }
 
     /// J-LandXML文書をパース
     pub fn parse(self) -> Result<JLandXmlDocument, LandXMLError> {
-        let base_landxml = self.parse_base_landxml()?;
+        let (content, encoding) = xml::read_document(&self.file_path)?;
+        let base_landxml = self.parse_base_landxml(&content)?;
         let mut jlandxml_doc = JLandXmlDocument::from_base(base_landxml);
-        self.parse_jlandxml_extensions(&mut jlandxml_doc)?;
+        jlandxml_doc.encoding = Some(encoding.name().to_string());
+        self.parse_jlandxml_extensions(&mut jlandxml_doc, &content)?;
+        self.apply_version_profile(&mut jlandxml_doc)?;
+        self.check_surface_faces(&mut jlandxml_doc)?;
         Ok(jlandxml_doc)
     }
 
+    /// 三角形として解釈できない面を検査
+    fn check_surface_faces(&self, doc: &mut JLandXmlDocument) -> Result<(), LandXMLError> {
+        for surface in doc.base.surfaces.iter().flat_map(|s| s.surfaces.iter()) {
+            if surface.unresolved_faces.is_empty() {
+                continue;
+            }
+            let warning = ValidationWarning::UnresolvedSurfaceFaces {
+                surface: surface.name.clone().unwrap_or_default(),
+                count: surface.unresolved_faces.len(),
+            };
+            if self.strict {
+                return Err(LandXMLError::InvalidFormat {
+                    message: warning.to_string(),
+                });
+            }
+            doc.warnings.push(warning);
+        }
+        Ok(())
+    }
+
+    /// 宣言バージョンのプロファイルで構成要素を検査（結果は警告のみ）
+    fn apply_version_profile(&self, doc: &mut JLandXmlDocument) -> Result<(), LandXMLError> {
+        let Some(declared) = doc.j_landxml_version.clone() else {
+            return Ok(());
+        };
+        let version = match declared.parse::<JLandXmlVersion>() {
+            Ok(version) => version,
+            Err(e) if self.strict => return Err(e),
+            Err(_) => {
+                doc.warnings
+                    .push(ValidationWarning::UnsupportedVersion { version: declared });
+                return Ok(());
+            }
+        };
+
+        let constructs = version.profile().unsupported_constructs(doc);
+        doc.warnings.extend(constructs.into_iter().map(|construct| {
+            ValidationWarning::VersionIncompatibleConstruct {
+                version: version.as_str().to_string(),
+                construct,
+            }
+        }));
+        Ok(())
+    }
+
     /// 基本LandXML構造をパース（最小限の実装）
-    fn parse_base_landxml(&self) -> Result<LandXML, LandXMLError> {
-        use std::fs;
-        let content = fs::read_to_string(&self.file_path)?;
-        let mut reader = Reader::from_str(&content);
+    fn parse_base_landxml(&self, content: &str) -> Result<LandXML, LandXMLError> {
+        let mut reader = Reader::from_str(content);
         reader.trim_text(true);
 
         let mut buf = Vec::new();
@@ -74,7 +174,7 @@ impl JLandXmlParser {
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.name().as_ref() {
                     b"LandXML" => {
                         for attr in e.attributes() {
                             let attr = attr.map_err(|e| {
@@ -88,6 +188,7 @@ impl JLandXmlParser {
                     b"CoordinateSystem" => {
                         let mut name = String::new();
                         let mut epsg_code = None;
+                        let mut ogc_wkt_code = None;
 
                         for attr in e.attributes() {
                             let attr = attr.map_err(|e| {
@@ -101,6 +202,18 @@ impl JLandXmlParser {
                                     epsg_code =
                                         Some(String::from_utf8_lossy(&attr.value).to_string())
                                 }
+                                b"ogcWktCode" => {
+                                    ogc_wkt_code = Some(
+                                        attr.unescape_value()
+                                            .map_err(|e| {
+                                                LandXMLError::ParseError(format!(
+                                                    "Attribute unescape error: {}",
+                                                    e
+                                                ))
+                                            })?
+                                            .to_string(),
+                                    )
+                                }
                                 _ => {}
                             }
                         }
@@ -110,6 +223,8 @@ impl JLandXmlParser {
                                 name,
                                 epsg_code,
                                 proj4_string: None,
+                                ogc_wkt_code,
+                                extensions: Default::default(),
                             });
                     }
                     _ => {}
@@ -129,19 +244,78 @@ impl JLandXmlParser {
             });
         }
 
-        Ok(LandXML {
+        let mut landxml = LandXML {
             version,
             coordinate_system,
-            alignments: Vec::new(),
-            features: Vec::new(),
-        })
+            ..Default::default()
+        };
+        self.parse_content(content, &mut landxml)?;
+        Ok(landxml)
+    }
+
+    /// Project・Application・Units・Alignments・CgPoints・Roadways・Surfacesの内容をパース
+    fn parse_content(&self, content: &str, landxml: &mut LandXML) -> Result<(), LandXMLError> {
+        let root = XmlElement::parse_str(content)?;
+        landxml.date = root.attr_string("date");
+        landxml.time = root.attr_string("time");
+        // 標準モデルが解釈しない要素・属性はラウンドトリップ用に保持
+        landxml.extensions = root.extensions(
+            &["xmlns", "date", "time", "version"],
+            &[
+                "Project",
+                "Application",
+                "CoordinateSystem",
+                "Units",
+                "CgPoints",
+                "Alignments",
+                "Roadways",
+                "Surfaces",
+            ],
+        );
+
+        for child in &root.children {
+            match child.name.as_str() {
+                "Project" => landxml.project = Some(Project::from_xml(child)),
+                "Application" => landxml.application = Some(Application::from_xml(child)),
+                "Units" => landxml.units = Some(Units::from_xml(child)?),
+                "CoordinateSystem" => {
+                    if let Some(cs) = landxml.coordinate_system.as_mut() {
+                        cs.extensions = CoordinateSystem::extensions_from_xml(child);
+                    }
+                }
+                "Alignments" => {
+                    // 複数のAlignments要素は1つに統合する
+                    let offset =
+                        landxml.alignments.len() + landxml.alignments_extensions.elements.len();
+                    landxml
+                        .alignments_extensions
+                        .append(child.extensions(&[], &["Alignment"]), offset);
+                    for alignment in child.children_named("Alignment") {
+                        landxml.alignments.push(Alignment::from_xml(alignment)?);
+                    }
+                }
+                "CgPoints" => landxml.cg_points.push(CgPoints::from_xml(child)?),
+                "Roadways" => landxml.roadways.push(Roadways::from_xml(child)),
+                "Surfaces" => landxml.surfaces.push(Surfaces::from_xml(child)?),
+                _ => {}
+            }
+        }
+
+        // 長さ・角度をモデルの基準単位（メートル・ラジアン）に正規化
+        if let Some(units) = landxml.units.clone() {
+            units.normalize(landxml);
+        }
+
+        Ok(())
     }
 
     /// J-LandXML拡張属性をパース
-    fn parse_jlandxml_extensions(&self, doc: &mut JLandXmlDocument) -> Result<(), LandXMLError> {
-        use std::fs;
-        let content = fs::read_to_string(&self.file_path)?;
-        let mut reader = Reader::from_str(&content);
+    fn parse_jlandxml_extensions(
+        &self,
+        doc: &mut JLandXmlDocument,
+        content: &str,
+    ) -> Result<(), LandXMLError> {
+        let mut reader = Reader::from_str(content);
         reader.trim_text(true);
 
         let mut buf = Vec::new();
@@ -150,27 +324,45 @@ impl JLandXmlParser {
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => match e.name().as_ref() {
-                    b"CoordinateSystem" => {
-                        in_coordinate_system = true;
-                        if let Some(base_cs) = &doc.base.coordinate_system {
-                            current_coordinate_system =
-                                Some(JLandXmlCoordinateSystem::from_base(base_cs.clone()));
-                        }
-                        self.parse_coordinate_system_attributes(e, &mut current_coordinate_system)?;
+                Ok(Event::Start(ref e)) if e.name().as_ref() == b"CoordinateSystem" => {
+                    in_coordinate_system = true;
+                    if let Some(base_cs) = &doc.base.coordinate_system {
+                        current_coordinate_system =
+                            Some(JLandXmlCoordinateSystem::from_base(base_cs.clone()));
                     }
-                    b"Project" => {
-                        self.parse_project_attributes(e, doc)?;
+                    self.parse_coordinate_system_attributes(
+                        e,
+                        &mut current_coordinate_system,
+                        &mut doc.warnings,
+                    )?;
+                }
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"CoordinateSystem" => {
+                    let mut coordinate_system = doc
+                        .base
+                        .coordinate_system
+                        .clone()
+                        .map(JLandXmlCoordinateSystem::from_base);
+                    self.parse_coordinate_system_attributes(
+                        e,
+                        &mut coordinate_system,
+                        &mut doc.warnings,
+                    )?;
+                    if coordinate_system.is_some() {
+                        doc.coordinate_system = coordinate_system;
                     }
-                    _ => {}
-                },
-                Ok(Event::End(ref e)) => {
-                    if e.name().as_ref() == b"CoordinateSystem" && in_coordinate_system {
-                        if let Some(cs) = current_coordinate_system.take() {
-                            doc.coordinate_system = Some(cs);
-                        }
-                        in_coordinate_system = false;
+                }
+                Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"Property" && in_coordinate_system =>
+                {
+                    self.parse_coordinate_system_property(e, &mut current_coordinate_system)?;
+                }
+                Ok(Event::End(ref e))
+                    if e.name().as_ref() == b"CoordinateSystem" && in_coordinate_system =>
+                {
+                    if let Some(cs) = current_coordinate_system.take() {
+                        doc.coordinate_system = Some(cs);
                     }
+                    in_coordinate_system = false;
                 }
                 Ok(Event::Eof) => break,
                 Err(e) => {
@@ -189,6 +381,7 @@ impl JLandXmlParser {
         &self,
         element: &quick_xml::events::BytesStart<'_>,
         coordinate_system: &mut Option<JLandXmlCoordinateSystem>,
+        warnings: &mut Vec<ValidationWarning>,
     ) -> Result<(), LandXMLError> {
         if let Some(ref mut cs) = coordinate_system {
             for attr in element.attributes() {
@@ -205,6 +398,27 @@ impl JLandXmlParser {
                         *cs = cs
                             .clone()
                             .with_horizontal_coordinate_system_name(value.to_string());
+                        if self.strict {
+                            if CoordinateSystemNameParser::parse(value)?.is_none() {
+                                return Err(LandXMLError::InvalidCoordinateSystemNameFormat {
+                                    name: value.to_string(),
+                                });
+                            }
+                        } else if let (Some(zone), warning) =
+                            CoordinateSystemNameParser::parse_lenient(value)
+                        {
+                            // 正規形の名称を保持し、元の表記は警告にのみ残す
+                            *cs = cs.clone().with_horizontal_coordinate_system_name(format!(
+                                "{}(X,Y)",
+                                zone.zone_number()
+                            ));
+                            warnings.extend(warning);
+                        }
+                    }
+                    "horizontalDatum" => {
+                        if let Ok(horizontal_datum) = HorizontalDatum::from_str(value) {
+                            *cs = cs.clone().with_horizontal_datum(horizontal_datum);
+                        }
                     }
                     "verticalDatum" => {
                         if let Ok(vertical_datum) = VerticalDatum::from_str(value) {
@@ -221,28 +435,32 @@ impl JLandXmlParser {
         Ok(())
     }
 
-    /// Project要素からJ-LandXML識別情報を抽出
-    fn parse_project_attributes(
+    /// CoordinateSystem内のFeature/Property（differTP）をパース
+    fn parse_coordinate_system_property(
         &self,
         element: &quick_xml::events::BytesStart<'_>,
-        doc: &mut JLandXmlDocument,
+        coordinate_system: &mut Option<JLandXmlCoordinateSystem>,
     ) -> Result<(), LandXMLError> {
+        let mut label = None;
+        let mut value = None;
         for attr in element.attributes() {
             let attr = attr
                 .map_err(|e| LandXMLError::ParseError(format!("Attribute parsing error: {}", e)))?;
-            let key = std::str::from_utf8(attr.key.as_ref())
-                .map_err(|e| LandXMLError::ParseError(format!("UTF-8 conversion error: {}", e)))?;
-            let value = std::str::from_utf8(&attr.value)
-                .map_err(|e| LandXMLError::ParseError(format!("UTF-8 conversion error: {}", e)))?;
-
-            if key == "applicationCriterion" {
-                doc.application_criterion = Some(value.to_string());
-                if value.contains("Ver1.6") || value.contains("1.6") {
-                    doc.j_landxml_version = Some("1.6".to_string());
-                }
+            match attr.key.as_ref() {
+                b"label" => label = Some(String::from_utf8_lossy(&attr.value).to_string()),
+                b"value" => value = Some(String::from_utf8_lossy(&attr.value).to_string()),
+                _ => {}
             }
         }
 
+        if let (Some("differTP"), Some(value), Some(cs)) =
+            (label.as_deref(), value, coordinate_system.as_mut())
+        {
+            let differ_tp = value.trim().parse::<f64>().map_err(|e| {
+                LandXMLError::ParseError(format!("Invalid differTP value '{}': {}", value, e))
+            })?;
+            cs.differ_tp = Some(differ_tp);
+        }
         Ok(())
     }
 
@@ -270,6 +488,7 @@ impl JLandXmlParser {
             plane_coordinate_zone: doc.get_plane_coordinate_zone(),
             epsg_code: doc.get_epsg_code(),
             alignment_count: doc.base.alignments.len(),
+            encoding: doc.encoding.clone(),
         }
     }
 }
@@ -287,6 +506,8 @@ pub struct ParsingStats {
     pub epsg_code: Option<u32>,
     /// アライメント数
     pub alignment_count: usize,
+    /// 入力ファイルの文字コード（例: "Shift_JIS"）
+    pub encoding: Option<String>,
 }
 
 impl std::fmt::Display for ParsingStats {
@@ -312,6 +533,10 @@ impl std::fmt::Display for ParsingStats {
 
         writeln!(f, "Alignments: {}", self.alignment_count)?;
 
+        if let Some(ref encoding) = self.encoding {
+            writeln!(f, "Encoding: {}", encoding)?;
+        }
+
         Ok(())
     }
 }
@@ -345,6 +570,267 @@ mod tests {
         assert!(invalid_info.is_none());
     }
 
+    #[test]
+    fn test_lenient_coordinate_system_name() {
+        let cases = [
+            ("９（Ｘ，Ｙ）", JapanPlaneCoordinateSystem::Zone9),
+            ("IX系", JapanPlaneCoordinateSystem::Zone9),
+            ("Ⅸ系", JapanPlaneCoordinateSystem::Zone9),
+            ("9系", JapanPlaneCoordinateSystem::Zone9),
+            ("第12系", JapanPlaneCoordinateSystem::Zone12),
+            (" 8 (X, Y) ", JapanPlaneCoordinateSystem::Zone8),
+            ("xix", JapanPlaneCoordinateSystem::Zone19),
+        ];
+        for (name, expected) in cases {
+            let (zone, warning) = CoordinateSystemNameParser::parse_lenient(name);
+            assert_eq!(zone, Some(expected), "{}", name);
+            assert!(warning.is_some(), "{}", name);
+            // 厳格モードでは受け付けない
+            assert_eq!(CoordinateSystemNameParser::parse(name).unwrap(), None);
+        }
+
+        let (zone, warning) = CoordinateSystemNameParser::parse_lenient("9(X,Y)");
+        assert_eq!(zone, Some(JapanPlaneCoordinateSystem::Zone9));
+        assert!(warning.is_none());
+
+        let (_, warning) = CoordinateSystemNameParser::parse_lenient("IX系");
+        let message = warning.unwrap().to_string();
+        assert!(message.contains("'9(X,Y)'"));
+        assert!(message.contains("roman numeral IX converted to 9"));
+
+        assert_eq!(CoordinateSystemNameParser::parse_lenient("20系").0, None);
+        assert_eq!(CoordinateSystemNameParser::parse_lenient("9(X,Z)").0, None);
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_variant_names() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <CoordinateSystem name="CRS1" horizontalDatum="JGD2011" verticalDatum="T.P" horizontalCoordinateSystemName="IX系"/>
+</LandXML>"#
+        )
+        .unwrap();
+
+        let doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            doc.get_plane_coordinate_zone(),
+            Some(JapanPlaneCoordinateSystem::Zone9)
+        );
+        assert_eq!(doc.warnings.len(), 1);
+        // 正規形の名称を保持し、元の表記は警告にのみ残す
+        let cs = doc.coordinate_system.as_ref().unwrap();
+        assert_eq!(cs.horizontal_coordinate_system_name, "9(X,Y)");
+        assert!(doc.warnings[0].to_string().contains("IX系"));
+        let parser = JLandXmlParser::from_file(file.path()).unwrap();
+        assert!(parser.validate_j_landxml(&doc).unwrap());
+
+        let result = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .with_strict_mode(true)
+            .parse();
+        assert!(matches!(
+            result,
+            Err(LandXMLError::InvalidCoordinateSystemNameFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_detect_version_from_project_feature() {
+        let doc = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(
+            doc.application_criterion.as_deref(),
+            Some("MlitLandXmlVer.1.7")
+        );
+        assert_eq!(doc.j_landxml_version.as_deref(), Some("1.7"));
+
+        let project = doc.base.project.as_ref().unwrap();
+        assert_eq!(project.project_phase.as_deref(), Some("詳細"));
+        assert_eq!(project.stratum_main_data.as_deref(), Some("Surface "));
+        assert!(project.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_version_profile_strict_mode() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Project name="P">
+    <Feature>
+      <Property label="applicationCriterion" value="MlitLandXmlVer.1.6"/>
+      <Property label="stratumMainData" value="Surface"/>
+    </Feature>
+  </Project>
+</LandXML>"#
+        )
+        .unwrap();
+
+        let doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(doc.version(), Some(JLandXmlVersion::V1_6));
+        assert!(matches!(
+            &doc.warnings[..],
+            [ValidationWarning::VersionIncompatibleConstruct { construct, .. }]
+                if construct.contains("stratumMainData")
+        ));
+
+        // バージョン間の差異は出典未確認のため、厳格モードでも警告のみ
+        let strict = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .with_strict_mode(true)
+            .parse()
+            .unwrap();
+        assert!(matches!(
+            &strict.warnings[..],
+            [ValidationWarning::VersionIncompatibleConstruct { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Project name="P">
+    <Feature><Property label="applicationCriterion" value="MlitLandXmlVer.2.0"/></Feature>
+  </Project>
+</LandXML>"#
+        )
+        .unwrap();
+
+        let doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(doc.j_landxml_version.as_deref(), Some("2.0"));
+        assert!(matches!(
+            &doc.warnings[..],
+            [ValidationWarning::UnsupportedVersion { version }] if version == "2.0"
+        ));
+
+        let result = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .with_strict_mode(true)
+            .parse();
+        assert!(matches!(
+            result,
+            Err(LandXMLError::UnsupportedJLandXmlVersion { version }) if version == "2.0"
+        ));
+    }
+
+    #[test]
+    fn test_shift_jis_input() {
+        use std::io::Write;
+        let xml = r#"<?xml version="1.0" encoding="Shift_JIS"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Project name="国道①号">
+    <Feature><Property label="applicationCriterion" value="MlitLandXmlVer.1.6"/><Property label="projectPhase" value="詳細設計"/></Feature>
+  </Project>
+  <CoordinateSystem name="平面直角座標系" horizontalCoordinateSystemName="9(X,Y)"/>
+</LandXML>"#;
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(xml);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&bytes).unwrap();
+
+        let parser = JLandXmlParser::from_file(file.path()).unwrap();
+        let doc = parser.parse().unwrap();
+        let project = doc.base.project.as_ref().unwrap();
+        assert_eq!(project.name.as_deref(), Some("国道①号"));
+        assert_eq!(project.project_phase.as_deref(), Some("詳細設計"));
+        assert_eq!(
+            doc.get_plane_coordinate_zone(),
+            Some(JapanPlaneCoordinateSystem::Zone9)
+        );
+
+        let stats = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .get_parsing_stats(&doc);
+        assert_eq!(stats.encoding.as_deref(), Some("Shift_JIS"));
+        assert!(stats.to_string().contains("Encoding: Shift_JIS"));
+    }
+
+    fn sample_path() -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/j_landxml_sample.xml")
+    }
+
+    #[test]
+    fn test_parse_sample_content() {
+        let doc = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let cs = doc.coordinate_system.as_ref().unwrap();
+        assert_eq!(cs.vertical_datum, VerticalDatum::YP);
+        assert_eq!(cs.differ_tp, Some(-0.8402));
+        assert_eq!(cs.horizontal_datum, HorizontalDatum::JGD2011);
+        assert!(cs
+            .proj4_string
+            .as_deref()
+            .unwrap()
+            .starts_with("+proj=tmerc +lat_0=36 +lon_0=138.5 "));
+        assert_eq!(
+            doc.get_plane_coordinate_zone(),
+            Some(JapanPlaneCoordinateSystem::Zone8)
+        );
+
+        let alignment = &doc.base.alignments[0];
+        assert_eq!(alignment.name, "○○路線");
+        assert_eq!(alignment.coord_geom.as_ref().unwrap().elements.len(), 18);
+        let profile = alignment.profile.as_ref().unwrap();
+        assert_eq!(profile.prof_aligns[0].elements.len(), 4);
+        assert_eq!(alignment.cross_sects.as_ref().unwrap().sections.len(), 2);
+
+        assert_eq!(doc.base.cg_points[0].points.len(), 3);
+        assert!(!doc.base.cg_points[0].points[0].has_elevation);
+        assert_eq!(doc.base.surfaces[0].surfaces[0].points.len(), 2);
+        // 見本データの面は未定義の点を参照している
+        assert!(doc.warnings.iter().any(|w| matches!(
+            w,
+            ValidationWarning::UnresolvedSurfaceFaces { count: 1, .. }
+        )));
+        let strict = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .with_strict_mode(true)
+            .parse();
+        assert!(matches!(strict, Err(LandXMLError::InvalidFormat { .. })));
+    }
+
+    #[test]
+    fn test_sample_vertical_datum_conversion() {
+        let mut doc = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        doc.convert_vertical_datum(VerticalDatum::TP).unwrap();
+
+        let alignment = &doc.base.alignments[0];
+        let first = &alignment.profile.as_ref().unwrap().prof_aligns[0].elements[0];
+        assert!((first.elevation() - (90.90600001 - 0.8402)).abs() < 1e-9);
+        assert_eq!(
+            doc.coordinate_system.as_ref().unwrap().vertical_datum,
+            VerticalDatum::TP
+        );
+    }
+
     #[test]
     fn test_jlandxml_property_creation() {
         let prop = JLandXmlProperty::new("testLabel", "testValue");
@@ -0,0 +1,305 @@
+//! 平面直角座標系の投影計算
+//!
+//! 国土地理院の計算式（河瀬 2011）に基づくガウス・クリューゲル投影：
+//! - 緯度経度 → 平面直角座標（X北・Y東）
+//! - 平面直角座標 → 緯度経度
+//! - 任意点の縮尺係数・子午線収差角
+//!
+//! 楕円体はGRS80、原点の縮尺係数は0.9999を使用します。
+
+use super::jlandxml::JapanPlaneCoordinateSystem;
+use crate::units::Angle;
+
+/// GRS80楕円体の長半径（メートル）
+pub const GRS80_SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+
+/// GRS80楕円体の逆扁平率
+pub const GRS80_INVERSE_FLATTENING: f64 = 298.257_222_101;
+
+/// 平面直角座標系の原点における縮尺係数
+pub const PLANE_ORIGIN_SCALE_FACTOR: f64 = 0.9999;
+
+impl JapanPlaneCoordinateSystem {
+    /// 座標系原点の緯度・経度（度）を取得
+    ///
+    /// 平成14年国土交通省告示第9号に基づく値
+    pub fn origin(&self) -> (f64, f64) {
+        let dms = |d: f64, m: f64| Angle::from_dms(d, m, 0.0).degrees();
+        match self {
+            Self::Zone1 => (33.0, dms(129.0, 30.0)),
+            Self::Zone2 => (33.0, 131.0),
+            Self::Zone3 => (36.0, dms(132.0, 10.0)),
+            Self::Zone4 => (33.0, dms(133.0, 30.0)),
+            Self::Zone5 => (36.0, dms(134.0, 20.0)),
+            Self::Zone6 => (36.0, 136.0),
+            Self::Zone7 => (36.0, dms(137.0, 10.0)),
+            Self::Zone8 => (36.0, dms(138.0, 30.0)),
+            Self::Zone9 => (36.0, dms(139.0, 50.0)),
+            Self::Zone10 => (40.0, dms(140.0, 50.0)),
+            Self::Zone11 => (44.0, dms(140.0, 15.0)),
+            Self::Zone12 => (44.0, dms(142.0, 15.0)),
+            Self::Zone13 => (44.0, dms(144.0, 15.0)),
+            Self::Zone14 => (26.0, 142.0),
+            Self::Zone15 => (26.0, dms(127.0, 30.0)),
+            Self::Zone16 => (26.0, 124.0),
+            Self::Zone17 => (26.0, 131.0),
+            Self::Zone18 => (20.0, 136.0),
+            Self::Zone19 => (26.0, 154.0),
+        }
+    }
+
+    /// 緯度・経度（度）から平面直角座標（X北, Y東）を計算
+    pub fn from_geographic(&self, lat: f64, lon: f64) -> (f64, f64) {
+        let projection = TransverseMercator::for_zone(*self);
+        let (x, y, _, _) = projection.forward(lat.to_radians(), lon.to_radians());
+        (x, y)
+    }
+
+    /// 平面直角座標（X北, Y東）から緯度・経度（度）を計算
+    pub fn to_geographic(&self, x: f64, y: f64) -> (f64, f64) {
+        let projection = TransverseMercator::for_zone(*self);
+        let (lat, lon) = projection.inverse(x, y);
+        (lat.to_degrees(), lon.to_degrees())
+    }
+
+    /// 平面直角座標（X北, Y東）における縮尺係数を計算
+    pub fn grid_scale_factor(&self, x: f64, y: f64) -> f64 {
+        let projection = TransverseMercator::for_zone(*self);
+        let (lat, lon) = projection.inverse(x, y);
+        let (_, _, _, scale) = projection.forward(lat, lon);
+        scale
+    }
+
+    /// 平面直角座標（X北, Y東）における子午線収差角（度）を計算
+    ///
+    /// 座標北から見た真北の方向角（真北方向角）は符号を反転した値です。
+    pub fn meridian_convergence(&self, x: f64, y: f64) -> f64 {
+        let projection = TransverseMercator::for_zone(*self);
+        let (lat, lon) = projection.inverse(x, y);
+        let (_, _, gamma, _) = projection.forward(lat, lon);
+        gamma.to_degrees()
+    }
+}
+
+/// ガウス・クリューゲル投影の計算パラメータ
+pub(crate) struct TransverseMercator {
+    /// 第三扁平率 n
+    n: f64,
+    /// 長半径
+    a: f64,
+    /// 原点緯度（ラジアン）
+    lat0: f64,
+    /// 原点経度（ラジアン）
+    lon0: f64,
+    /// 縮尺係数
+    m0: f64,
+    a_coef: [f64; 6],
+    alpha: [f64; 5],
+    beta: [f64; 5],
+    delta: [f64; 6],
+}
+
+impl TransverseMercator {
+    /// 平面直角座標系の原点パラメータで作成
+    pub(crate) fn for_zone(zone: JapanPlaneCoordinateSystem) -> Self {
+        let (lat0, lon0) = zone.origin();
+        Self::new(
+            GRS80_SEMI_MAJOR_AXIS,
+            GRS80_INVERSE_FLATTENING,
+            lat0.to_radians(),
+            lon0.to_radians(),
+            PLANE_ORIGIN_SCALE_FACTOR,
+        )
+    }
+
+    fn new(a: f64, inverse_flattening: f64, lat0: f64, lon0: f64, m0: f64) -> Self {
+        let n = 1.0 / (2.0 * inverse_flattening - 1.0);
+        let n2 = n * n;
+        let n3 = n2 * n;
+        let n4 = n3 * n;
+        let n5 = n4 * n;
+        let n6 = n5 * n;
+
+        let a_coef = [
+            1.0 + n2 / 4.0 + n4 / 64.0,
+            -1.5 * (n - n3 / 8.0 - n5 / 64.0),
+            15.0 / 16.0 * (n2 - n4 / 4.0),
+            -35.0 / 48.0 * (n3 - 5.0 / 16.0 * n5),
+            315.0 / 512.0 * n4,
+            -693.0 / 1280.0 * n5,
+        ];
+        let alpha = [
+            n / 2.0 - 2.0 / 3.0 * n2 + 5.0 / 16.0 * n3 + 41.0 / 180.0 * n4 - 127.0 / 288.0 * n5,
+            13.0 / 48.0 * n2 - 3.0 / 5.0 * n3 + 557.0 / 1440.0 * n4 + 281.0 / 630.0 * n5,
+            61.0 / 240.0 * n3 - 103.0 / 140.0 * n4 + 15061.0 / 26880.0 * n5,
+            49561.0 / 161280.0 * n4 - 179.0 / 168.0 * n5,
+            34729.0 / 80640.0 * n5,
+        ];
+        let beta = [
+            n / 2.0 - 2.0 / 3.0 * n2 + 37.0 / 96.0 * n3 - n4 / 360.0 - 81.0 / 512.0 * n5,
+            n2 / 48.0 + n3 / 15.0 - 437.0 / 1440.0 * n4 + 46.0 / 105.0 * n5,
+            17.0 / 480.0 * n3 - 37.0 / 840.0 * n4 - 209.0 / 4480.0 * n5,
+            4397.0 / 161280.0 * n4 - 11.0 / 504.0 * n5,
+            4583.0 / 161280.0 * n5,
+        ];
+        let delta = [
+            2.0 * n - 2.0 / 3.0 * n2 - 2.0 * n3 + 116.0 / 45.0 * n4 + 26.0 / 45.0 * n5
+                - 2854.0 / 675.0 * n6,
+            7.0 / 3.0 * n2 - 8.0 / 5.0 * n3 - 227.0 / 45.0 * n4
+                + 2704.0 / 315.0 * n5
+                + 2323.0 / 945.0 * n6,
+            56.0 / 15.0 * n3 - 136.0 / 35.0 * n4 - 1262.0 / 105.0 * n5 + 73814.0 / 2835.0 * n6,
+            4279.0 / 630.0 * n4 - 332.0 / 35.0 * n5 - 399572.0 / 14175.0 * n6,
+            4174.0 / 315.0 * n5 - 144838.0 / 6237.0 * n6,
+            601676.0 / 22275.0 * n6,
+        ];
+
+        Self {
+            n,
+            a,
+            lat0,
+            lon0,
+            m0,
+            a_coef,
+            alpha,
+            beta,
+            delta,
+        }
+    }
+
+    /// Ā = m0·a/(1+n)·A0
+    fn a_bar(&self) -> f64 {
+        self.m0 * self.a / (1.0 + self.n) * self.a_coef[0]
+    }
+
+    /// 原点緯度までの赤道からの子午線弧長 S̄φ0
+    fn s_bar_lat0(&self) -> f64 {
+        let sum: f64 = (1..=5)
+            .map(|j| self.a_coef[j] * (2.0 * j as f64 * self.lat0).sin())
+            .sum();
+        self.m0 * self.a / (1.0 + self.n) * (self.a_coef[0] * self.lat0 + sum)
+    }
+
+    /// 順計算：緯度経度（ラジアン）→（X, Y, 子午線収差角[rad], 縮尺係数）
+    pub(crate) fn forward(&self, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let n = self.n;
+        let lambda_c = (lon - self.lon0).cos();
+        let lambda_s = (lon - self.lon0).sin();
+
+        let k = 2.0 * n.sqrt() / (1.0 + n);
+        let t = (lat.sin().atanh() - k * (k * lat.sin()).atanh()).sinh();
+        let t_bar = (1.0 + t * t).sqrt();
+
+        let xi = (t / lambda_c).atan();
+        let eta = (lambda_s / t_bar).atanh();
+
+        let mut x_sum = xi;
+        let mut y_sum = eta;
+        let mut sigma = 1.0;
+        let mut tau = 0.0;
+        for (i, alpha) in self.alpha.iter().enumerate() {
+            let j2 = 2.0 * (i + 1) as f64;
+            x_sum += alpha * (j2 * xi).sin() * (j2 * eta).cosh();
+            y_sum += alpha * (j2 * xi).cos() * (j2 * eta).sinh();
+            sigma += j2 * alpha * (j2 * xi).cos() * (j2 * eta).cosh();
+            tau += j2 * alpha * (j2 * xi).sin() * (j2 * eta).sinh();
+        }
+
+        let a_bar = self.a_bar();
+        let x = a_bar * x_sum - self.s_bar_lat0();
+        let y = a_bar * y_sum;
+
+        let gamma = ((tau * t_bar * lambda_c + sigma * t * lambda_s)
+            / (sigma * t_bar * lambda_c - tau * t * lambda_s))
+            .atan();
+        let scale = a_bar / self.a
+            * ((sigma * sigma + tau * tau) / (t * t + lambda_c * lambda_c)).sqrt()
+            * (1.0 + ((1.0 - n) / (1.0 + n) * lat.tan()).powi(2)).sqrt();
+
+        (x, y, gamma, scale)
+    }
+
+    /// 逆計算：平面直角座標 → 緯度経度（ラジアン）
+    pub(crate) fn inverse(&self, x: f64, y: f64) -> (f64, f64) {
+        let a_bar = self.a_bar();
+        let xi = (x + self.s_bar_lat0()) / a_bar;
+        let eta = y / a_bar;
+
+        let mut xi_p = xi;
+        let mut eta_p = eta;
+        for (i, beta) in self.beta.iter().enumerate() {
+            let j2 = 2.0 * (i + 1) as f64;
+            xi_p -= beta * (j2 * xi).sin() * (j2 * eta).cosh();
+            eta_p -= beta * (j2 * xi).cos() * (j2 * eta).sinh();
+        }
+
+        let chi = (xi_p.sin() / eta_p.cosh()).asin();
+        let lat = chi
+            + self
+                .delta
+                .iter()
+                .enumerate()
+                .map(|(i, d)| d * (2.0 * (i + 1) as f64 * chi).sin())
+                .sum::<f64>();
+        let lon = self.lon0 + (eta_p.sinh() / xi_p.cos()).atan();
+
+        (lat, lon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_maps_to_zero() {
+        for zone in JapanPlaneCoordinateSystem::all_zones() {
+            let (lat0, lon0) = zone.origin();
+            let (x, y) = zone.from_geographic(lat0, lon0);
+            assert!(x.abs() < 1e-6, "{}: x={}", zone, x);
+            assert!(y.abs() < 1e-6, "{}: y={}", zone, y);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let zone = JapanPlaneCoordinateSystem::Zone8;
+        let (lat, lon) = zone.to_geographic(-5851.24470669, -16562.24159873);
+        let (x, y) = zone.from_geographic(lat, lon);
+        assert!((x + 5851.24470669).abs() < 1e-6);
+        assert!((y + 16562.24159873).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_scale_factor_and_convergence() {
+        let zone = JapanPlaneCoordinateSystem::Zone9;
+        assert!((zone.grid_scale_factor(0.0, 0.0) - PLANE_ORIGIN_SCALE_FACTOR).abs() < 1e-12);
+        assert!(zone.meridian_convergence(10_000.0, 0.0).abs() < 1e-9);
+
+        // m = m0(1 + y²/(2R²m0²)) の近似式と比較
+        let y: f64 = 90_000.0;
+        let r = 6_370_000.0 * PLANE_ORIGIN_SCALE_FACTOR;
+        let approx = PLANE_ORIGIN_SCALE_FACTOR * (1.0 + y * y / (2.0 * r * r));
+        assert!((zone.grid_scale_factor(0.0, y) - approx).abs() < 1e-6);
+
+        // 原点の東側では正（γ ≈ Δλ·sinφ）
+        let (lat, lon) = zone.to_geographic(0.0, y);
+        let (_, lon0) = zone.origin();
+        let approx_gamma = (lon - lon0) * lat.to_radians().sin();
+        let gamma = zone.meridian_convergence(0.0, y);
+        assert!(gamma > 0.0);
+        assert!((gamma - approx_gamma).abs() < 1e-3, "gamma={}", gamma);
+    }
+
+    #[test]
+    fn test_known_point() {
+        // 36°06'51.9251" 140°05'13.8938"（9系）
+        // 期待値はSnyderの級数展開による独立計算値
+        let zone = JapanPlaneCoordinateSystem::Zone9;
+        let lat = 36.0 + 6.0 / 60.0 + 51.9251 / 3600.0;
+        let lon = 140.0 + 5.0 / 60.0 + 13.8938 / 3600.0;
+        let (x, y) = zone.from_geographic(lat, lon);
+        assert!((x - 12725.025).abs() < 0.01, "x={}", x);
+        assert!((y - 22853.525).abs() < 0.01, "y={}", y);
+    }
+}
@@ -0,0 +1,282 @@
+//! 平面距離と地上距離の換算
+//!
+//! 平面直角座標系上の距離（平面距離）と現地で測る距離（地上距離）を相互に換算します：
+//! - 縮尺係数による投影補正（線分はシンプソン則で平均）
+//! - 楕円体高による標高補正（R / (R + h)、Rは地点のガウス平均曲率半径）
+//!
+//! 平面距離 = 地上距離 × 縮尺係数 × 標高補正係数
+
+use super::jlandxml::JapanPlaneCoordinateSystem;
+use super::projection::{GRS80_INVERSE_FLATTENING, GRS80_SEMI_MAJOR_AXIS};
+use crate::alignments::{CoordGeom, GeomElement};
+use crate::models::Point2D;
+
+/// 平面距離・地上距離の換算条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceReduction {
+    /// 平面直角座標系
+    pub zone: JapanPlaneCoordinateSystem,
+    /// 標高が与えられていない区間に用いる標高（メートル）
+    pub default_elevation: f64,
+    /// ジオイド高（メートル、楕円体高 = 標高 + ジオイド高）
+    pub geoid_height: f64,
+}
+
+impl DistanceReduction {
+    /// 標高0m・ジオイド高0mで作成（投影補正のみ）
+    pub fn new(zone: JapanPlaneCoordinateSystem) -> Self {
+        Self {
+            zone,
+            default_elevation: 0.0,
+            geoid_height: 0.0,
+        }
+    }
+
+    /// 既定の標高を設定
+    pub fn with_default_elevation(mut self, elevation: f64) -> Self {
+        self.default_elevation = elevation;
+        self
+    }
+
+    /// ジオイド高を設定
+    pub fn with_geoid_height(mut self, geoid_height: f64) -> Self {
+        self.geoid_height = geoid_height;
+        self
+    }
+
+    /// 標高補正係数 R / (R + h) を計算
+    pub fn elevation_factor(&self, x: f64, y: f64, elevation: f64) -> f64 {
+        let radius = self.mean_radius(x, y);
+        radius / (radius + elevation + self.geoid_height)
+    }
+
+    /// 地点の合成係数（縮尺係数 × 標高補正係数）を計算
+    pub fn combined_factor(&self, x: f64, y: f64, elevation: f64) -> f64 {
+        self.zone.grid_scale_factor(x, y) * self.elevation_factor(x, y, elevation)
+    }
+
+    /// 2点間の線分に対する平均合成係数を計算（シンプソン則）
+    pub fn line_factor(
+        &self,
+        start: Point2D,
+        end: Point2D,
+        start_elevation: Option<f64>,
+        end_elevation: Option<f64>,
+    ) -> f64 {
+        let h1 = start_elevation.unwrap_or(self.default_elevation);
+        let h2 = end_elevation.unwrap_or(self.default_elevation);
+        let mid = Point2D {
+            x: (start.x + end.x) / 2.0,
+            y: (start.y + end.y) / 2.0,
+        };
+
+        let k1 = self.combined_factor(start.x, start.y, h1);
+        let km = self.combined_factor(mid.x, mid.y, (h1 + h2) / 2.0);
+        let k2 = self.combined_factor(end.x, end.y, h2);
+        (k1 + 4.0 * km + k2) / 6.0
+    }
+
+    /// 線形要素の平均合成係数を計算
+    pub fn element_factor(&self, element: &GeomElement) -> f64 {
+        let (start_elevation, end_elevation) = match element {
+            GeomElement::Line(e) => (e.start_elevation, e.end_elevation),
+            GeomElement::Curve(e) => (e.start_elevation, e.end_elevation),
+            GeomElement::Spiral(e) => (e.start_elevation, e.end_elevation),
+        };
+        self.line_factor(
+            element.start(),
+            element.end(),
+            start_elevation,
+            end_elevation,
+        )
+    }
+
+    /// 線形要素上の平面距離を地上距離に換算
+    pub fn grid_to_ground(&self, element: &GeomElement, grid_distance: f64) -> f64 {
+        grid_distance / self.element_factor(element)
+    }
+
+    /// 線形要素上の地上距離を平面距離に換算
+    pub fn ground_to_grid(&self, element: &GeomElement, ground_distance: f64) -> f64 {
+        ground_distance * self.element_factor(element)
+    }
+
+    /// 地点のガウス平均曲率半径 √(MN)
+    fn mean_radius(&self, x: f64, y: f64) -> f64 {
+        let (lat, _) = self.zone.to_geographic(x, y);
+        let f = 1.0 / GRS80_INVERSE_FLATTENING;
+        let e2 = f * (2.0 - f);
+        let w = (1.0 - e2 * lat.to_radians().sin().powi(2)).sqrt();
+        let m = GRS80_SEMI_MAJOR_AXIS * (1.0 - e2) / w.powi(3);
+        let n = GRS80_SEMI_MAJOR_AXIS / w;
+        (m * n).sqrt()
+    }
+}
+
+impl CoordGeom {
+    /// 各要素の平面距離（要素長）を地上距離に換算
+    ///
+    /// 要素長が求められない要素はNoneです。
+    pub fn ground_lengths(&self, reduction: &DistanceReduction) -> Vec<Option<f64>> {
+        self.elements
+            .iter()
+            .map(|element| {
+                element
+                    .length()
+                    .map(|length| reduction.grid_to_ground(element, length))
+            })
+            .collect()
+    }
+
+    /// 地上距離の総延長を計算
+    pub fn total_ground_length(&self, reduction: &DistanceReduction) -> f64 {
+        self.ground_lengths(reduction).into_iter().flatten().sum()
+    }
+
+    /// 線形に沿った平面距離（始点からの累加距離）を地上距離に換算
+    pub fn grid_to_ground_distance(
+        &self,
+        reduction: &DistanceReduction,
+        grid_distance: f64,
+    ) -> f64 {
+        self.convert_along(reduction, grid_distance, true)
+    }
+
+    /// 線形に沿った地上距離（始点からの累加距離）を平面距離に換算
+    pub fn ground_to_grid_distance(
+        &self,
+        reduction: &DistanceReduction,
+        ground_distance: f64,
+    ) -> f64 {
+        self.convert_along(reduction, ground_distance, false)
+    }
+
+    /// 要素ごとの係数で累加距離を換算（総延長を超える分は最終要素の係数を使用）
+    fn convert_along(&self, reduction: &DistanceReduction, distance: f64, to_ground: bool) -> f64 {
+        let convert = |factor: f64, value: f64| {
+            if to_ground {
+                value / factor
+            } else {
+                value * factor
+            }
+        };
+
+        let mut remaining = distance;
+        let mut converted = 0.0;
+        let mut last_factor = None;
+
+        for element in &self.elements {
+            let Some(grid_length) = element.length() else {
+                continue;
+            };
+            let factor = reduction.element_factor(element);
+            // 換算元の距離系での要素長
+            let source_length = if to_ground {
+                grid_length
+            } else {
+                grid_length / factor
+            };
+            if remaining <= source_length {
+                return converted + convert(factor, remaining);
+            }
+            remaining -= source_length;
+            converted += convert(factor, source_length);
+            last_factor = Some(factor);
+        }
+
+        converted + convert(last_factor.unwrap_or(1.0), remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignments::Line;
+    use crate::coordinate_system::projection::PLANE_ORIGIN_SCALE_FACTOR;
+
+    fn line(start: Point2D, end: Point2D, elevation: Option<f64>) -> GeomElement {
+        GeomElement::Line(Line {
+            start,
+            end,
+            start_elevation: elevation,
+            end_elevation: elevation,
+            start_name: None,
+            end_name: None,
+            length: None,
+            dir: None,
+            sta_start: None,
+            name: None,
+            desc: None,
+            state: None,
+            extensions: Default::default(),
+        })
+    }
+
+    #[test]
+    fn test_reduction_on_central_meridian() {
+        let reduction = DistanceReduction::new(JapanPlaneCoordinateSystem::Zone9);
+        let element = line(
+            Point2D { x: -50.0, y: 0.0 },
+            Point2D { x: 50.0, y: 0.0 },
+            None,
+        );
+        let ground = reduction.grid_to_ground(&element, 100.0);
+        assert!((ground - 100.0 / PLANE_ORIGIN_SCALE_FACTOR).abs() < 1e-6);
+
+        // 標高1000mでは地上距離が約1000/6370000だけ長くなる
+        let high = reduction.with_default_elevation(1000.0);
+        let ratio = high.grid_to_ground(&element, 100.0) / ground;
+        assert!((ratio - 1.0 - 1000.0 / 6_371_000.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_coord_geom_round_trip() {
+        let coord_geom = CoordGeom {
+            elements: vec![
+                line(
+                    Point2D {
+                        x: 0.0,
+                        y: 60_000.0,
+                    },
+                    Point2D {
+                        x: 300.0,
+                        y: 60_400.0,
+                    },
+                    Some(120.0),
+                ),
+                line(
+                    Point2D {
+                        x: 300.0,
+                        y: 60_400.0,
+                    },
+                    Point2D {
+                        x: 900.0,
+                        y: 60_400.0,
+                    },
+                    None,
+                ),
+            ],
+            ..Default::default()
+        };
+        let reduction = DistanceReduction::new(JapanPlaneCoordinateSystem::Zone9)
+            .with_default_elevation(50.0)
+            .with_geoid_height(40.0);
+
+        let lengths = coord_geom.ground_lengths(&reduction);
+        assert_eq!(lengths.len(), 2);
+        let total = coord_geom.total_ground_length(&reduction);
+        assert!((coord_geom.grid_to_ground_distance(&reduction, 1100.0) - total).abs() < 1e-6);
+
+        for grid in [0.0, 250.0, 500.0, 1100.0, 1200.0] {
+            let ground = coord_geom.grid_to_ground_distance(&reduction, grid);
+            let back = coord_geom.ground_to_grid_distance(&reduction, ground);
+            assert!(
+                (back - grid).abs() < 1e-6,
+                "{} -> {} -> {}",
+                grid,
+                ground,
+                back
+            );
+        }
+    }
+}
@@ -4,6 +4,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::Extensions;
+use crate::xml::XmlElement;
+
 /// 座標系情報（標準LandXML）
 ///
 /// Reference: LandXML 1.2 CoordinateSystem element
@@ -15,4 +18,40 @@ pub struct CoordinateSystem {
     pub epsg_code: Option<String>,
     /// PROJ.4文字列
     pub proj4_string: Option<String>,
+    /// OGC WKT文字列（ogcWktCode属性）
+    #[serde(default)]
+    pub ogc_wkt_code: Option<String>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl CoordinateSystem {
+    /// CoordinateSystem要素（標準LandXML属性）を生成
+    pub(crate) fn to_xml(&self) -> XmlElement {
+        XmlElement::new("CoordinateSystem")
+            .with_opt_attr("name", Some(self.name.as_str()).filter(|n| !n.is_empty()))
+            .with_opt_attr("epsgCode", self.epsg_code.as_deref())
+            .with_opt_attr("ogcWktCode", self.ogc_wkt_code.as_deref())
+            .with_extensions(&self.extensions)
+    }
+
+    /// CoordinateSystem要素から未知の属性・子要素を取り出し
+    ///
+    /// J-LandXML拡張属性とdifferTPのみを持つFeatureは既知として扱います。
+    pub(crate) fn extensions_from_xml(element: &XmlElement) -> Extensions {
+        element.extensions_by(
+            &[
+                "name",
+                "desc",
+                "epsgCode",
+                "ogcWktCode",
+                "horizontalDatum",
+                "verticalDatum",
+                "horizontalCoordinateSystemName",
+                "geoidName",
+            ],
+            |_, child| child.is_feature_with_only(&["differTP"]),
+        )
+    }
 }
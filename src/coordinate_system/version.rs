@@ -0,0 +1,293 @@
+//! J-LandXML version profiles
+//!
+//! J-LandXMLの各バージョン（Ver.1.5 / 1.6 / 1.7）で使用できる要素・プロパティを定義します：
+//! - applicationCriterionからのバージョン判定
+//! - バージョンごとのルート要素・Projectプロパティ・横断地層情報の可否
+//! - 宣言バージョンで使用できない構成要素の検出（警告として報告）
+//!
+//! 同梱のスキーマはVer.1.7（tests/data/J-LandXML_schema_v17.xsd）のみで、Ver.1.5・1.6との
+//! 差異は出典を確認できていません。検出した構成要素は警告として扱い、拒否・削除には使用しません。
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::coordinate_system::JLandXmlDocument;
+use crate::error::LandXMLError;
+use crate::project::criterion_version;
+
+/// J-LandXMLのバージョン
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum JLandXmlVersion {
+    /// Ver.1.5
+    V1_5,
+    /// Ver.1.6
+    V1_6,
+    /// Ver.1.7
+    V1_7,
+}
+
+impl JLandXmlVersion {
+    /// 対応する全バージョン（古い順）
+    pub const ALL: [JLandXmlVersion; 3] = [
+        JLandXmlVersion::V1_5,
+        JLandXmlVersion::V1_6,
+        JLandXmlVersion::V1_7,
+    ];
+
+    /// 最新の対応バージョン
+    pub fn latest() -> Self {
+        JLandXmlVersion::V1_7
+    }
+
+    /// バージョン番号の文字列表現（例: "1.7"）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JLandXmlVersion::V1_5 => "1.5",
+            JLandXmlVersion::V1_6 => "1.6",
+            JLandXmlVersion::V1_7 => "1.7",
+        }
+    }
+
+    /// applicationCriterionの値（例: "MlitLandXmlVer.1.7"）
+    pub fn application_criterion(&self) -> String {
+        format!("MlitLandXmlVer.{}", self.as_str())
+    }
+
+    /// applicationCriterionからバージョンを判定
+    pub fn from_criterion(criterion: &str) -> Result<Self, LandXMLError> {
+        let version = criterion_version(criterion).ok_or_else(|| {
+            LandXMLError::UnsupportedJLandXmlVersion {
+                version: criterion.to_string(),
+            }
+        })?;
+        version.parse()
+    }
+
+    /// このバージョンのプロファイルを取得
+    pub fn profile(&self) -> &'static VersionProfile {
+        match self {
+            JLandXmlVersion::V1_5 => &PROFILE_V1_5,
+            JLandXmlVersion::V1_6 => &PROFILE_V1_6,
+            JLandXmlVersion::V1_7 => &PROFILE_V1_7,
+        }
+    }
+}
+
+impl FromStr for JLandXmlVersion {
+    type Err = LandXMLError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1.5" => Ok(JLandXmlVersion::V1_5),
+            "1.6" => Ok(JLandXmlVersion::V1_6),
+            "1.7" => Ok(JLandXmlVersion::V1_7),
+            other => Err(LandXMLError::UnsupportedJLandXmlVersion {
+                version: other.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for JLandXmlVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "J-LandXML Ver.{}", self.as_str())
+    }
+}
+
+/// バージョンごとに使用できる構成要素
+///
+/// Ver.1.7のルート要素は同梱のスキーマ（LandXML要素のxs:choice）と一致します。
+/// Ver.1.5・1.6の表、Projectプロパティ、横断地層情報の可否は出典未確認です。
+#[derive(Debug, Clone)]
+pub struct VersionProfile {
+    /// 対象バージョン
+    pub version: JLandXmlVersion,
+    /// LandXML直下に記述できる要素
+    pub root_elements: &'static [&'static str],
+    /// ProjectのFeatureに記述できるプロパティ
+    pub project_properties: &'static [&'static str],
+    /// 横断面の地層線（CrossSectSurfのmaterial Feature）を記述できるか
+    pub cross_section_strata: bool,
+}
+
+/// Ver.1.5のルート要素（Surfacesを含まないとする根拠は未確認）
+const ROOT_ELEMENTS_V1_5: &[&str] = &[
+    "Project",
+    "Application",
+    "CoordinateSystem",
+    "Units",
+    "CgPoints",
+    "Alignments",
+    "Roadways",
+];
+
+/// Ver.1.6・1.7のルート要素（Ver.1.7はJ-LandXML_schema_v17.xsdのLandXML要素と同じ）
+const ROOT_ELEMENTS_V1_6: &[&str] = &[
+    "Project",
+    "Application",
+    "CoordinateSystem",
+    "Units",
+    "CgPoints",
+    "Alignments",
+    "Roadways",
+    "Surfaces",
+];
+
+static PROFILE_V1_5: VersionProfile = VersionProfile {
+    version: JLandXmlVersion::V1_5,
+    root_elements: ROOT_ELEMENTS_V1_5,
+    project_properties: &["applicationCriterion", "projectPhase"],
+    cross_section_strata: false,
+};
+
+static PROFILE_V1_6: VersionProfile = VersionProfile {
+    version: JLandXmlVersion::V1_6,
+    root_elements: ROOT_ELEMENTS_V1_6,
+    project_properties: &["applicationCriterion", "projectPhase"],
+    cross_section_strata: false,
+};
+
+static PROFILE_V1_7: VersionProfile = VersionProfile {
+    version: JLandXmlVersion::V1_7,
+    root_elements: ROOT_ELEMENTS_V1_6,
+    project_properties: &["applicationCriterion", "projectPhase", "stratumMainData"],
+    cross_section_strata: true,
+};
+
+impl VersionProfile {
+    /// LandXML直下の要素を使用できるか
+    pub fn allows_root_element(&self, name: &str) -> bool {
+        self.root_elements.contains(&name)
+    }
+
+    /// Projectプロパティを使用できるか
+    pub fn allows_project_property(&self, label: &str) -> bool {
+        self.project_properties.contains(&label)
+    }
+
+    /// 文書中でこのバージョンに存在しない構成要素を列挙
+    ///
+    /// 結果は出典未確認の表に基づくため、警告としてのみ使用してください。
+    pub fn unsupported_constructs(&self, doc: &JLandXmlDocument) -> Vec<String> {
+        let base = &doc.base;
+        let mut constructs = Vec::new();
+
+        let present = [
+            ("Project", base.project.is_some()),
+            ("Application", base.application.is_some()),
+            ("Units", base.units.is_some()),
+            ("CgPoints", !base.cg_points.is_empty()),
+            ("Alignments", !base.alignments.is_empty()),
+            ("Roadways", !base.roadways.is_empty()),
+            ("Surfaces", !base.surfaces.is_empty()),
+        ];
+        let root_names = present
+            .into_iter()
+            .filter(|(_, present)| *present)
+            .map(|(name, _)| name)
+            .chain(
+                base.extensions
+                    .elements
+                    .iter()
+                    .map(|e| e.element.name.as_str()),
+            );
+        for name in root_names {
+            if !self.allows_root_element(name) {
+                constructs.push(format!("element '{}'", name));
+            }
+        }
+
+        if let Some(project) = &base.project {
+            let labels = [
+                (
+                    "applicationCriterion",
+                    project.application_criterion.is_some(),
+                ),
+                ("projectPhase", project.project_phase.is_some()),
+                ("stratumMainData", project.stratum_main_data.is_some()),
+            ]
+            .into_iter()
+            .filter(|(_, present)| *present)
+            .map(|(label, _)| label)
+            .chain(project.properties.iter().map(|p| p.label.as_str()));
+            for label in labels {
+                if !self.allows_project_property(label) {
+                    constructs.push(format!("Project property '{}'", label));
+                }
+            }
+        }
+
+        if !self.cross_section_strata {
+            let strata = base
+                .alignments
+                .iter()
+                .filter_map(|a| a.cross_sects.as_ref())
+                .flat_map(|c| c.sections.iter())
+                .flat_map(|s| s.surfaces.iter())
+                .filter(|surf| {
+                    surf.extensions.elements_named("Feature").any(|f| {
+                        f.attributes
+                            .iter()
+                            .any(|(k, v)| k == "name" && v == "material")
+                    })
+                })
+                .count();
+            if strata > 0 {
+                constructs.push(format!(
+                    "cross-section strata (material Feature on {} CrossSectSurf)",
+                    strata
+                ));
+            }
+        }
+
+        constructs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate_system::JLandXmlParser;
+
+    #[test]
+    fn test_version_from_criterion() {
+        assert_eq!(
+            JLandXmlVersion::from_criterion("MlitLandXmlVer.1.7").unwrap(),
+            JLandXmlVersion::V1_7
+        );
+        assert_eq!(
+            JLandXmlVersion::from_criterion("MlitLandXmlVer1.5").unwrap(),
+            JLandXmlVersion::V1_5
+        );
+        assert!(matches!(
+            JLandXmlVersion::from_criterion("MlitLandXmlVer.1.8"),
+            Err(LandXMLError::UnsupportedJLandXmlVersion { version }) if version == "1.8"
+        ));
+        assert_eq!(
+            JLandXmlVersion::V1_6.application_criterion(),
+            "MlitLandXmlVer.1.6"
+        );
+    }
+
+    #[test]
+    fn test_sample_against_profiles() {
+        let doc = JLandXmlParser::from_file("tests/data/j_landxml_sample.xml")
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(JLandXmlVersion::V1_7
+            .profile()
+            .unsupported_constructs(&doc)
+            .is_empty());
+
+        let v16 = JLandXmlVersion::V1_6.profile().unsupported_constructs(&doc);
+        assert_eq!(v16.len(), 2, "{:?}", v16);
+        assert!(v16[0].contains("stratumMainData"));
+        assert!(v16[1].contains("strata"));
+
+        let v15 = JLandXmlVersion::V1_5.profile().unsupported_constructs(&doc);
+        assert_eq!(v15[0], "element 'Surfaces'");
+    }
+}
@@ -0,0 +1,463 @@
+//! 鉛直原子レジストリ
+//!
+//! 河川・地域ごとの基準面とT.Pとの差分を管理します：
+//! - 同じ略称でも河川により異なる基準面（荒川のA.Pと吉野川のA.P等）
+//! - 改定に伴う適用開始日付きの差分
+//! - 設定ファイル（JSON）による追加・上書き
+//!
+//! 設定ファイルの例：
+//!
+//! ```json
+//! {
+//!   "datums": [
+//!     { "datum": "A.P", "region": "吉野川",
+//!       "offsets": [{ "tpOffset": -0.8333, "validFrom": "1990-04-01" }] }
+//!   ]
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::jlandxml::VerticalDatum;
+use crate::error::LandXMLError;
+
+/// 暦日（YYYY-MM-DD）
+///
+/// 月・日の0埋めは省略可能です（"2024-1-5"は"2024-01-05"と同じ日）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CalendarDate {
+    /// 年
+    pub year: i32,
+    /// 月（1～12）
+    pub month: u32,
+    /// 日（1～月末）
+    pub day: u32,
+}
+
+impl CalendarDate {
+    /// 年月日から作成（存在しない日付はエラー）
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, LandXMLError> {
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => 0,
+        };
+        if day == 0 || day > days_in_month {
+            return Err(LandXMLError::InvalidFormat {
+                message: format!("Invalid date: {}-{}-{}", year, month, day),
+            });
+        }
+        Ok(Self { year, month, day })
+    }
+}
+
+impl FromStr for CalendarDate {
+    type Err = LandXMLError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || LandXMLError::InvalidFormat {
+            message: format!("Invalid date (expected YYYY-MM-DD): {}", s),
+        };
+        let parts: Vec<&str> = s.trim().split('-').collect();
+        let [year, month, day] = parts[..] else {
+            return Err(invalid());
+        };
+        let number = |part: &str, lengths: std::ops::RangeInclusive<usize>| {
+            if !lengths.contains(&part.len()) || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(invalid());
+            }
+            part.parse::<u32>().map_err(|_| invalid())
+        };
+        Self::new(
+            number(year, 4..=4)? as i32,
+            number(month, 1..=2)?,
+            number(day, 1..=2)?,
+        )
+    }
+}
+
+impl TryFrom<String> for CalendarDate {
+    type Error = LandXMLError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CalendarDate> for String {
+    fn from(date: CalendarDate) -> Self {
+        date.to_string()
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// T.P差分を照会する条件
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TpOffsetQuery {
+    /// 河川・地域の修飾（Noneは地域未指定）
+    pub region: Option<String>,
+    /// 差分の適用日（Noneは最新）
+    pub date: Option<CalendarDate>,
+}
+
+impl TpOffsetQuery {
+    /// 地域・日付を指定しない条件で作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 河川・地域を設定
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// 適用日を設定
+    pub fn with_date(mut self, date: CalendarDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+}
+
+/// 適用開始日付きのT.P差分
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatedTpOffset {
+    /// T.P基準からの差分（メートル）
+    pub tp_offset: f64,
+    /// 適用開始日（未設定は制定時から）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<CalendarDate>,
+    /// 出典・備考
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// 鉛直原子の定義（河川・地域の修飾付き）
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalDatumDefinition {
+    /// 鉛直原子
+    pub datum: VerticalDatum,
+    /// 河川・地域（Noneは既定の定義）
+    pub region: Option<String>,
+    /// 差分の履歴
+    pub offsets: Vec<DatedTpOffset>,
+}
+
+impl VerticalDatumDefinition {
+    /// 指定日に有効な差分を取得（日付未指定は最新）
+    pub fn offset_at(&self, date: Option<CalendarDate>) -> Option<f64> {
+        self.offsets
+            .iter()
+            .filter(|o| match (date, o.valid_from) {
+                (Some(date), Some(from)) => from <= date,
+                _ => true,
+            })
+            .max_by_key(|o| o.valid_from)
+            .map(|o| o.tp_offset)
+    }
+}
+
+/// 設定ファイルの1エントリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VerticalDatumConfigEntry {
+    datum: String,
+    #[serde(default)]
+    region: Option<String>,
+    offsets: Vec<DatedTpOffset>,
+}
+
+/// 設定ファイルのルート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VerticalDatumConfig {
+    datums: Vec<VerticalDatumConfigEntry>,
+}
+
+/// 鉛直原子レジストリ
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerticalDatumRegistry {
+    definitions: Vec<VerticalDatumDefinition>,
+}
+
+impl Default for VerticalDatumRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl VerticalDatumRegistry {
+    /// 組み込みの差分表（`VerticalDatum::tp_offset`）から作成
+    ///
+    /// A.Pは荒川（既定）に加えて吉野川の定義を持ちます。
+    pub fn builtin() -> Self {
+        let datums = [
+            VerticalDatum::TP,
+            VerticalDatum::KP,
+            VerticalDatum::SP,
+            VerticalDatum::YP,
+            VerticalDatum::AP,
+            VerticalDatum::OP,
+            VerticalDatum::TPW,
+            VerticalDatum::BSL,
+        ];
+        let mut definitions: Vec<VerticalDatumDefinition> = datums
+            .iter()
+            .map(|datum| VerticalDatumDefinition {
+                datum: *datum,
+                region: None,
+                offsets: vec![DatedTpOffset {
+                    tp_offset: datum.tp_offset(),
+                    valid_from: None,
+                    source: None,
+                }],
+            })
+            .collect();
+
+        definitions.push(VerticalDatumDefinition {
+            datum: VerticalDatum::AP,
+            region: Some("荒川".to_string()),
+            offsets: vec![DatedTpOffset {
+                tp_offset: VerticalDatum::AP.tp_offset(),
+                valid_from: None,
+                source: None,
+            }],
+        });
+        definitions.push(VerticalDatumDefinition {
+            datum: VerticalDatum::AP,
+            region: Some("吉野川".to_string()),
+            offsets: vec![DatedTpOffset {
+                tp_offset: -0.8333,
+                valid_from: None,
+                source: None,
+            }],
+        });
+
+        Self { definitions }
+    }
+
+    /// 空のレジストリを作成
+    pub fn empty() -> Self {
+        Self {
+            definitions: Vec::new(),
+        }
+    }
+
+    /// 組み込み表に設定ファイル（JSON）の定義を重ねて作成
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> Result<Self, LandXMLError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_config_str(&content)
+    }
+
+    /// 組み込み表に設定文字列（JSON）の定義を重ねて作成
+    pub fn from_config_str(content: &str) -> Result<Self, LandXMLError> {
+        let mut registry = Self::builtin();
+        registry.merge_config_str(content)?;
+        Ok(registry)
+    }
+
+    /// 設定文字列（JSON）の定義を追加・上書き
+    ///
+    /// 鉛直原子と河川・地域が同じ定義は置き換えられます。
+    /// 適用開始日がYYYY-MM-DD形式の実在する日付でない場合はエラーです。
+    pub fn merge_config_str(&mut self, content: &str) -> Result<(), LandXMLError> {
+        let config: VerticalDatumConfig = serde_json::from_str(content)?;
+        for entry in config.datums {
+            let datum = VerticalDatum::from_str(&entry.datum)?;
+            if entry.offsets.is_empty() {
+                return Err(LandXMLError::InvalidFormat {
+                    message: format!("No offsets defined for vertical datum {}", entry.datum),
+                });
+            }
+            self.insert(VerticalDatumDefinition {
+                datum,
+                region: entry.region,
+                offsets: entry.offsets,
+            });
+        }
+        Ok(())
+    }
+
+    /// 定義を追加（同じ鉛直原子・地域の定義は置き換え）
+    pub fn insert(&mut self, definition: VerticalDatumDefinition) {
+        let key = definition.region.as_deref().map(normalize_region);
+        self.definitions.retain(|d| {
+            d.datum != definition.datum || d.region.as_deref().map(normalize_region) != key
+        });
+        self.definitions.push(definition);
+    }
+
+    /// 鉛直原子の定義を列挙
+    pub fn definitions_for(
+        &self,
+        datum: VerticalDatum,
+    ) -> impl Iterator<Item = &VerticalDatumDefinition> {
+        self.definitions.iter().filter(move |d| d.datum == datum)
+    }
+
+    /// 鉛直原子・地域・日付に対応する定義を検索
+    ///
+    /// 地域が一致する定義がなければ既定の定義（地域なし）を返します。
+    pub fn find(
+        &self,
+        datum: VerticalDatum,
+        region: Option<&str>,
+    ) -> Option<&VerticalDatumDefinition> {
+        let key = region.map(normalize_region);
+        key.as_ref()
+            .and_then(|key| {
+                self.definitions_for(datum)
+                    .find(|d| d.region.as_deref().map(normalize_region).as_ref() == Some(key))
+            })
+            .or_else(|| self.definitions_for(datum).find(|d| d.region.is_none()))
+    }
+
+    /// T.P基準からの差分を取得
+    pub fn tp_offset(
+        &self,
+        datum: VerticalDatum,
+        region: Option<&str>,
+        date: Option<CalendarDate>,
+    ) -> Option<f64> {
+        self.find(datum, region)
+            .and_then(|definition| definition.offset_at(date))
+    }
+
+    /// 地域未指定時に候補となり得る差分をすべて取得
+    pub fn candidate_offsets(&self, datum: VerticalDatum, date: Option<CalendarDate>) -> Vec<f64> {
+        self.definitions_for(datum)
+            .filter_map(|d| d.offset_at(date))
+            .collect()
+    }
+}
+
+/// 地域名を照合用に正規化（空白除去・小文字化）
+fn normalize_region(region: &str) -> String {
+    region
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_matches_tp_offset() {
+        let registry = VerticalDatumRegistry::builtin();
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::YP, None, None),
+            Some(VerticalDatum::YP.tp_offset())
+        );
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::AP, Some("吉野川"), None),
+            Some(-0.8333)
+        );
+        // 未登録の地域は既定の定義
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::AP, Some("多摩川"), None),
+            Some(VerticalDatum::AP.tp_offset())
+        );
+    }
+
+    #[test]
+    fn test_config_with_dated_offsets() {
+        let config = r#"{
+            "datums": [
+                { "datum": "O.P", "region": "淀川",
+                  "offsets": [
+                    { "tpOffset": -1.3000 },
+                    { "tpOffset": -1.3200, "validFrom": "2020-04-01", "source": "改定" }
+                  ] }
+            ]
+        }"#;
+        let registry = VerticalDatumRegistry::from_config_str(config).unwrap();
+        let date = |s: &str| Some(s.parse::<CalendarDate>().unwrap());
+
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::OP, Some("淀川"), date("2019-12-31")),
+            Some(-1.3)
+        );
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::OP, Some("淀川"), date("2021-01-01")),
+            Some(-1.32)
+        );
+        // 0埋めの有無によらず日付として比較
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::OP, Some("淀川"), date("2020-4-1")),
+            Some(-1.32)
+        );
+        assert_eq!(
+            registry.tp_offset(VerticalDatum::OP, Some("淀川"), None),
+            Some(-1.32)
+        );
+    }
+
+    #[test]
+    fn test_calendar_date() {
+        let date: CalendarDate = "2024-1-5".parse().unwrap();
+        assert_eq!(date, "2024-01-05".parse().unwrap());
+        assert_eq!(date.to_string(), "2024-01-05");
+        assert!("2024-02-29".parse::<CalendarDate>().is_ok());
+        for invalid in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024/01/05",
+            "24-01-05",
+            "令和6年",
+        ] {
+            assert!(invalid.parse::<CalendarDate>().is_err(), "{}", invalid);
+        }
+
+        let config = r#"{ "datums": [ { "datum": "O.P",
+            "offsets": [ { "tpOffset": -1.3, "validFrom": "2020-04-31" } ] } ] }"#;
+        assert!(VerticalDatumRegistry::from_config_str(config).is_err());
+    }
+
+    #[test]
+    fn test_config_rejects_unknown_datum() {
+        let config = r#"{ "datums": [ { "datum": "X.P", "offsets": [ { "tpOffset": 1.0 } ] } ] }"#;
+        assert!(VerticalDatumRegistry::from_config_str(config).is_err());
+    }
+
+    #[test]
+    fn test_validator_with_regional_datum() {
+        use crate::coordinate_system::{
+            CoordinateSystemValidator, HorizontalDatum, JapanPlaneCoordinateSystem,
+        };
+
+        let registry = VerticalDatumRegistry::builtin();
+        let validate = |query: TpOffsetQuery| {
+            CoordinateSystemValidator::validate_complete_system_with_registry(
+                HorizontalDatum::JGD2011,
+                VerticalDatum::AP,
+                JapanPlaneCoordinateSystem::Zone4,
+                Some(-0.8333),
+                &registry,
+                &query,
+            )
+            .unwrap()
+        };
+
+        assert!(validate(TpOffsetQuery::new().with_region("吉野川")).is_empty());
+        assert_eq!(validate(TpOffsetQuery::new().with_region("荒川")).len(), 1);
+        // 地域未指定はいずれかの地域と一致すれば妥当
+        assert!(validate(TpOffsetQuery::new()).is_empty());
+        let date = CalendarDate::new(2024, 4, 1).unwrap();
+        assert!(validate(TpOffsetQuery::new().with_date(date)).is_empty());
+    }
+}
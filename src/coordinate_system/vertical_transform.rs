@@ -0,0 +1,384 @@
+//! 文書全体の鉛直原子変換
+//!
+//! K.P/Y.P/A.P/O.P等の河川基準面とT.Pの間で、文書内のすべての標高を書き換えます：
+//! - CoordGeomのStart/End標高
+//! - ProfAlignのPVI・縦断曲線の標高
+//! - ProfSurf・CrossSectSurfの標高
+//! - DesignCrossSectSurfの構成点標高
+//! - CgPointの標高、TINの点標高
+//! - 未知要素として保持された点（P・Start・End等）と点列（Breakline等のPntList3D）の標高
+
+use super::jlandxml::{JLandXmlDocument, VerticalDatum};
+use crate::error::LandXMLError;
+use crate::models::{Alignment, LandXML, RawElement};
+use crate::units::conversion::{extension_owners, format_raw};
+
+/// 未知要素のうち「北 東 標高」の3つの座標値を持つ点の要素
+const RAW_POINT_ELEMENTS: &[&str] = &["P", "Start", "End", "Center", "PI"];
+
+/// 未知要素として保持されている点・点列（PntList3D）の標高に差分を加算
+///
+/// 座標値の数が合わない要素（2次元の点等）は変更しません。
+fn shift_raw_elevations(element: &mut RawElement, delta: f64) -> usize {
+    let mut count: usize = element
+        .children
+        .iter_mut()
+        .map(|child| shift_raw_elevations(child, delta))
+        .sum();
+
+    let list = element.name == "PntList3D";
+    if !list && !RAW_POINT_ELEMENTS.contains(&element.name.as_str()) {
+        return count;
+    }
+    let mut tokens: Vec<String> = element.text.split_whitespace().map(String::from).collect();
+    let shaped = if list {
+        !tokens.is_empty() && tokens.len().is_multiple_of(3)
+    } else {
+        tokens.len() == 3
+    };
+    if !shaped || tokens.iter().any(|t| t.parse::<f64>().is_err()) {
+        return count;
+    }
+    // 北・東の座標値は元の表記のまま保持する
+    for token in tokens.iter_mut().skip(2).step_by(3) {
+        let z: f64 = token.parse().unwrap_or_default();
+        *token = format_raw(z + delta);
+        count += 1;
+    }
+    element.text = tokens.join(" ");
+    count
+}
+
+impl LandXML {
+    /// 文書内のすべての標高に一律の差分を加算
+    ///
+    /// 書き換えた標高値の数を返します。標高を持たないCgPointは対象外です。
+    /// 未知要素として保持された点・点列（Breakline等）の標高も書き換えます。
+    pub fn shift_elevations(&mut self, delta: f64) -> usize {
+        let mut count = 0;
+
+        for alignment in &mut self.alignments {
+            count += alignment.shift_elevations(delta);
+        }
+
+        for cg_points in &mut self.cg_points {
+            for point in cg_points.points.iter_mut().filter(|p| p.has_elevation) {
+                point.position.z += delta;
+                count += 1;
+            }
+        }
+
+        for surfaces in &mut self.surfaces {
+            for surface in &mut surfaces.surfaces {
+                for point in &mut surface.points {
+                    point.z += delta;
+                    count += 1;
+                }
+            }
+        }
+
+        for (_, extensions) in extension_owners(self) {
+            for element in &mut extensions.elements {
+                count += shift_raw_elevations(&mut element.element, delta);
+            }
+        }
+
+        count
+    }
+}
+
+impl Alignment {
+    /// 線形内のすべての標高に一律の差分を加算
+    pub fn shift_elevations(&mut self, delta: f64) -> usize {
+        let mut count = 0;
+
+        if let Some(coord_geom) = &mut self.coord_geom {
+            for element in &mut coord_geom.elements {
+                for elevation in element.elevations_mut().into_iter().flatten() {
+                    *elevation += delta;
+                    count += 1;
+                }
+            }
+        }
+
+        if let Some(profile) = &mut self.profile {
+            for prof_align in &mut profile.prof_aligns {
+                for element in &mut prof_align.elements {
+                    *element.elevation_mut() += delta;
+                    count += 1;
+                }
+            }
+            for prof_surf in &mut profile.prof_surfs {
+                for point in prof_surf.point_lists.iter_mut().flatten() {
+                    point.y += delta;
+                    count += 1;
+                }
+            }
+        }
+
+        if let Some(cross_sects) = &mut self.cross_sects {
+            for section in &mut cross_sects.sections {
+                for surf in &mut section.surfaces {
+                    for point in surf.point_lists.iter_mut().flatten() {
+                        point.y += delta;
+                        count += 1;
+                    }
+                }
+                for design in &mut section.design_surfaces {
+                    for point in &mut design.points {
+                        point.elevation += delta;
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        count
+    }
+}
+
+impl JLandXmlDocument {
+    /// 文書全体を指定した鉛直原子に変換（変換先のdifferTPは標準値）
+    ///
+    /// 変換元のdifferTPはファイルの値を優先し、未設定の場合は標準値を使用します。
+    /// 変換後はCoordinateSystemのverticalDatum・differTPも更新されます。
+    pub fn convert_vertical_datum(&mut self, target: VerticalDatum) -> Result<usize, LandXMLError> {
+        self.convert_vertical_datum_with_differ_tp(target, target.tp_offset())
+    }
+
+    /// 文書全体を指定した鉛直原子・differTPに変換
+    ///
+    /// 書き換えた標高値の数を返します。
+    pub fn convert_vertical_datum_with_differ_tp(
+        &mut self,
+        target: VerticalDatum,
+        target_differ_tp: f64,
+    ) -> Result<usize, LandXMLError> {
+        let cs = self
+            .coordinate_system
+            .as_mut()
+            .ok_or_else(|| LandXMLError::MissingElement {
+                element: "CoordinateSystem".to_string(),
+            })?;
+
+        let source_differ_tp = cs
+            .differ_tp
+            .unwrap_or_else(|| cs.vertical_datum.tp_offset());
+        let target_differ_tp = if matches!(target, VerticalDatum::TP) {
+            0.0
+        } else {
+            target_differ_tp
+        };
+
+        // T.P標高 = 元標高 + differTP(元) = 新標高 + differTP(先)
+        let delta = source_differ_tp - target_differ_tp;
+
+        cs.vertical_datum = target;
+        cs.differ_tp = if matches!(target, VerticalDatum::TP) {
+            None
+        } else {
+            Some(target_differ_tp)
+        };
+
+        if delta == 0.0 {
+            return Ok(0);
+        }
+        Ok(self.base.shift_elevations(delta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignments::{
+        CoordGeom, CrossSect, CrossSectSurf, CrossSects, GeomElement, Line, ProfAlign,
+        ProfAlignElement, Profile,
+    };
+    use crate::cg_points::{CgPoint, CgPoints};
+    use crate::coordinate_system::{HorizontalDatum, JLandXmlCoordinateSystem};
+    use crate::models::{Point2D, Point3D};
+
+    fn sample_document() -> JLandXmlDocument {
+        let alignment = Alignment {
+            name: "A1".to_string(),
+            coord_geom: Some(CoordGeom {
+                elements: vec![GeomElement::Line(Line {
+                    start: Point2D { x: 0.0, y: 0.0 },
+                    end: Point2D { x: 100.0, y: 0.0 },
+                    start_elevation: Some(10.0),
+                    end_elevation: None,
+                    start_name: None,
+                    end_name: None,
+                    length: Some(100.0),
+                    dir: None,
+                    sta_start: None,
+                    name: None,
+                    desc: None,
+                    state: None,
+                    extensions: Default::default(),
+                })],
+                ..Default::default()
+            }),
+            profile: Some(Profile {
+                prof_aligns: vec![ProfAlign {
+                    name: "P1".to_string(),
+                    desc: None,
+                    elements: vec![ProfAlignElement::Pvi {
+                        sta: 0.0,
+                        elevation: 10.0,
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            cross_sects: Some(CrossSects {
+                sections: vec![CrossSect {
+                    sta: 0.0,
+                    surfaces: vec![CrossSectSurf {
+                        name: "ExistingGround".to_string(),
+                        desc: None,
+                        point_lists: vec![vec![Point2D { x: -5.0, y: 9.0 }]],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let base = LandXML {
+            version: "1.2".to_string(),
+            alignments: vec![alignment],
+            cg_points: vec![CgPoints {
+                name: None,
+                points: vec![
+                    CgPoint {
+                        name: Some("BM1".to_string()),
+                        desc: None,
+                        code: None,
+                        position: Point3D {
+                            x: 0.0,
+                            y: 0.0,
+                            z: 10.0,
+                        },
+                        has_elevation: true,
+                        point_type: None,
+                        extensions: Default::default(),
+                    },
+                    CgPoint {
+                        name: Some("NO.1".to_string()),
+                        desc: None,
+                        code: None,
+                        position: Point3D {
+                            x: 20.0,
+                            y: 0.0,
+                            z: 0.0,
+                        },
+                        has_elevation: false,
+                        point_type: None,
+                        extensions: Default::default(),
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let cs = JLandXmlCoordinateSystem::new(
+            "CRS1".to_string(),
+            HorizontalDatum::JGD2011,
+            VerticalDatum::YP,
+            "9(X,Y)".to_string(),
+        )
+        .unwrap()
+        .with_differ_tp(-0.84);
+
+        JLandXmlDocument::from_base(base).with_coordinate_system(cs)
+    }
+
+    #[test]
+    fn test_convert_to_tp_uses_file_differ_tp() {
+        let mut doc = sample_document();
+        let count = doc.convert_vertical_datum(VerticalDatum::TP).unwrap();
+        assert_eq!(count, 4);
+
+        let alignment = &doc.base.alignments[0];
+        match &alignment.coord_geom.as_ref().unwrap().elements[0] {
+            GeomElement::Line(line) => {
+                assert!((line.start_elevation.unwrap() - 9.16).abs() < 1e-9);
+                assert_eq!(line.end_elevation, None);
+            }
+            _ => unreachable!(),
+        }
+        let section = &alignment.cross_sects.as_ref().unwrap().sections[0];
+        assert!((section.surfaces[0].point_lists[0][0].y - 8.16).abs() < 1e-9);
+        assert_eq!(doc.base.cg_points[0].points[1].position.z, 0.0);
+
+        let cs = doc.coordinate_system.as_ref().unwrap();
+        assert_eq!(cs.vertical_datum, VerticalDatum::TP);
+        assert_eq!(cs.differ_tp, None);
+    }
+
+    #[test]
+    fn test_convert_between_river_datums() {
+        let mut doc = sample_document();
+        doc.convert_vertical_datum(VerticalDatum::AP).unwrap();
+
+        let expected = 10.0 - 0.84 - VerticalDatum::AP.tp_offset();
+        let z = doc.base.cg_points[0].points[0].position.z;
+        assert!((z - expected).abs() < 1e-9);
+
+        let cs = doc.coordinate_system.as_ref().unwrap();
+        assert_eq!(cs.vertical_datum, VerticalDatum::AP);
+        assert_eq!(cs.differ_tp, Some(VerticalDatum::AP.tp_offset()));
+        // 変換後のT.P標高は変換前と一致する
+        assert!((cs.to_tp_elevation(z) - 9.16).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_shift_preserved_elevations() {
+        let surfaces = r#"<Surfaces>
+            <Surface name="S">
+              <SourceData>
+                <Breaklines>
+                  <Breakline><PntList3D>-35000.125 12000.5 10 -35001 12001 10.5</PntList3D></Breakline>
+                </Breaklines>
+              </SourceData>
+              <Definition surfType="TIN">
+                <Pnts><P id="1">0 0 1</P><P id="2">1 0 1</P><P id="3">0 1 1</P></Pnts>
+                <Faces><F>1 2 3</F></Faces>
+              </Definition>
+              <Feature name="spot"><Pnts><P>5 5 12</P><P>6 6</P></Pnts></Feature>
+            </Surface>
+        </Surfaces>"#;
+        let element = crate::xml::XmlElement::parse_str(surfaces).unwrap();
+        let mut base = LandXML {
+            surfaces: vec![crate::surfaces::Surfaces::from_xml(&element).unwrap()],
+            ..Default::default()
+        };
+
+        // TINの3点、Breaklineの2点、Featureの3次元の点1点（2次元の点は対象外）
+        assert_eq!(base.shift_elevations(-0.84), 6);
+        let extensions = &base.surfaces[0].surfaces[0].extensions;
+        let named = |name: &str| {
+            extensions
+                .elements
+                .iter()
+                .map(|e| &e.element)
+                .find(|e| e.name == name)
+                .unwrap()
+        };
+        let breakline = &named("SourceData").children[0].children[0].children[0];
+        assert_eq!(breakline.text, "-35000.125 12000.5 9.16 -35001 12001 9.66");
+        let feature = named("Feature");
+        assert_eq!(feature.children[0].children[0].text, "5 5 11.16");
+        assert_eq!(feature.children[0].children[1].text, "6 6");
+    }
+
+    #[test]
+    fn test_convert_without_coordinate_system_fails() {
+        let mut doc = JLandXmlDocument::from_base(LandXML::default());
+        assert!(doc.convert_vertical_datum(VerticalDatum::TP).is_err());
+    }
+}
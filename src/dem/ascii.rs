@@ -0,0 +1,209 @@
+//! ESRI ASCII grid format
+//!
+//! 標高格子をESRI ASCII grid形式（.asc）で入出力します：
+//! - ヘッダー（ncols, nrows, xllcorner, yllcorner, cellsize, NODATA_value）
+//! - 東西・南北でセルの大きさが異なる場合はdx・dy（GDAL拡張）
+//!
+//! xllcorner・yllcornerはGISの座標（x東・y北）で格子の南西隅を表します。
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+use super::{DemGrid, NODATA_VALUE};
+use crate::error::LandXMLError;
+
+impl DemGrid {
+    /// ESRI ASCII grid形式の文字列に変換
+    pub fn to_ascii_grid(&self) -> String {
+        let mut buffer = Vec::new();
+        // Vec<u8>への書き込みは失敗しない
+        let _ = self.write_ascii_grid(&mut buffer);
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
+    /// ESRI ASCII grid形式で書き出し
+    pub fn write_ascii_grid<W: Write>(&self, mut output: W) -> Result<(), LandXMLError> {
+        writeln!(output, "ncols {}", self.cols)?;
+        writeln!(output, "nrows {}", self.rows)?;
+        writeln!(output, "xllcorner {}", self.west)?;
+        writeln!(output, "yllcorner {}", self.south())?;
+        if self.x_res == self.y_res {
+            writeln!(output, "cellsize {}", self.x_res)?;
+        } else {
+            writeln!(output, "dx {}", self.x_res)?;
+            writeln!(output, "dy {}", self.y_res)?;
+        }
+        writeln!(output, "NODATA_value {}", NODATA_VALUE)?;
+        for row in self.values.chunks(self.cols.max(1)) {
+            let line: Vec<String> = row
+                .iter()
+                .map(|v| v.unwrap_or(NODATA_VALUE).to_string())
+                .collect();
+            writeln!(output, "{}", line.join(" "))?;
+        }
+        output.flush()?;
+        Ok(())
+    }
+
+    /// ESRI ASCII grid形式のファイルに書き出し
+    pub fn write_ascii_grid_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LandXMLError> {
+        let file = std::fs::File::create(path)?;
+        self.write_ascii_grid(std::io::BufWriter::new(file))
+    }
+
+    /// ESRI ASCII grid形式の文字列から読み込み
+    ///
+    /// xllcenter・yllcenter（セル中心）の表記、dx・dy（GDAL拡張）にも対応します。
+    /// 座標系の情報は含まれないため、必要に応じて[`DemGrid::with_zone`]で設定してください。
+    pub fn from_ascii_grid_str(content: &str) -> Result<Self, LandXMLError> {
+        let invalid = |message: String| LandXMLError::InvalidFormat { message };
+        let parse = |token: &str| {
+            token
+                .parse::<f64>()
+                .map_err(|e| invalid(format!("Invalid ASCII grid value '{}': {}", token, e)))
+        };
+
+        let mut tokens = content.split_whitespace().peekable();
+        let mut header = HashMap::new();
+        while let Some(key) = tokens.next_if(|t| t.starts_with(|c: char| c.is_ascii_alphabetic())) {
+            let value = tokens
+                .next()
+                .ok_or_else(|| invalid(format!("Missing ASCII grid header value: {}", key)))?;
+            header.insert(key.to_ascii_lowercase(), parse(value)?);
+        }
+        let field = |keys: &[&'static str]| {
+            keys.iter()
+                .find_map(|key| header.get(*key).map(|value| (*key, *value)))
+                .ok_or_else(|| invalid(format!("Missing ASCII grid header: {}", keys[0])))
+        };
+
+        // 行数・列数は0以上の整数のみ（小数・負の値を切り捨てない）
+        let count = |key: &'static str| {
+            let (_, value) = field(&[key])?;
+            if value.is_finite() && value >= 0.0 && value.fract() == 0.0 {
+                Ok(value as usize)
+            } else {
+                Err(invalid(format!("Invalid ASCII grid {}: {}", key, value)))
+            }
+        };
+        let cols = count("ncols")?;
+        let rows = count("nrows")?;
+        let (x_res, y_res) = match field(&["cellsize"]) {
+            Ok((_, size)) => (size, size),
+            Err(_) => (field(&["dx"])?.1, field(&["dy"])?.1),
+        };
+        let (x_key, x_ll) = field(&["xllcorner", "xllcenter"])?;
+        let (y_key, y_ll) = field(&["yllcorner", "yllcenter"])?;
+        let west = if x_key == "xllcenter" {
+            x_ll - x_res / 2.0
+        } else {
+            x_ll
+        };
+        let south = if y_key == "yllcenter" {
+            y_ll - y_res / 2.0
+        } else {
+            y_ll
+        };
+        let nodata = header.get("nodata_value").copied();
+
+        let values = tokens
+            .map(|token| {
+                let value = parse(token)?;
+                Ok((Some(value) != nodata).then_some(value))
+            })
+            .collect::<Result<Vec<_>, LandXMLError>>()?;
+        Self::from_values(
+            rows,
+            cols,
+            south + rows as f64 * y_res,
+            west,
+            x_res,
+            y_res,
+            values,
+        )
+    }
+
+    /// ESRI ASCII grid形式のファイルから読み込み
+    pub fn from_ascii_grid_file<P: AsRef<Path>>(path: P) -> Result<Self, LandXMLError> {
+        Self::from_ascii_grid_str(&std::fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_ascii_grid() {
+        let grid = DemGrid::from_values(
+            2,
+            3,
+            -1000.0,
+            2000.0,
+            0.5,
+            0.5,
+            vec![
+                Some(1.0),
+                Some(1.25),
+                None,
+                Some(2.0),
+                Some(-0.5),
+                Some(3.0),
+            ],
+        )
+        .unwrap();
+        let text = grid.to_ascii_grid();
+        let expected = "ncols 3\nnrows 2\nxllcorner 2000\nyllcorner -1001\ncellsize 0.5\n\
+                        NODATA_value -9999\n1 1.25 -9999\n2 -0.5 3\n";
+        assert_eq!(text, expected);
+
+        let grid = DemGrid::new(1, 1, 0.0, 0.0, 1.0, 2.0).unwrap();
+        assert!(grid.to_ascii_grid().contains("dx 1\ndy 2\n"));
+    }
+
+    #[test]
+    fn test_read_ascii_grid() {
+        let text = "ncols 3\nnrows 2\nxllcorner 2000\nyllcorner -1001\ncellsize 0.5\n\
+                    NODATA_value -9999\n1 1.25 -9999\n2 -0.5 3\n";
+        let grid = DemGrid::from_ascii_grid_str(text).unwrap();
+        assert_eq!((grid.rows, grid.cols), (2, 3));
+        assert_eq!((grid.north, grid.west), (-1000.0, 2000.0));
+        assert_eq!(grid.value(0, 2).unwrap(), None);
+        assert_eq!(grid.value(1, 1).unwrap(), Some(-0.5));
+        assert_eq!(grid.to_ascii_grid(), text);
+
+        // セル中心の表記とGDAL拡張のセルの大きさ
+        let grid = DemGrid::from_ascii_grid_str(
+            "NCOLS 2\nNROWS 1\nXLLCENTER 10.5\nYLLCENTER 21\nDX 1\nDY 2\n5 6\n",
+        )
+        .unwrap();
+        assert_eq!((grid.north, grid.west), (22.0, 10.0));
+        assert_eq!((grid.x_res, grid.y_res), (1.0, 2.0));
+        assert_eq!(grid.values, vec![Some(5.0), Some(6.0)]);
+
+        assert!(matches!(
+            DemGrid::from_ascii_grid_str(
+                "ncols 2\nnrows 2\nxllcorner 0\nyllcorner 0\ncellsize 1\n1 2 3\n"
+            ),
+            Err(LandXMLError::InvalidGridSize {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            DemGrid::from_ascii_grid_str("ncols 1\nnrows 1\n1\n"),
+            Err(LandXMLError::InvalidFormat { .. })
+        ));
+
+        // 不正な行数・列数、データに対して過大な格子
+        for header in [
+            "ncols -3\nnrows 2.9",
+            "ncols inf\nnrows 1",
+            "ncols 1e12\nnrows 1e12",
+        ] {
+            let text = format!("{}\nxllcorner 0\nyllcorner 0\ncellsize 1\n1 2\n", header);
+            assert!(DemGrid::from_ascii_grid_str(&text).is_err(), "{}", header);
+        }
+    }
+}
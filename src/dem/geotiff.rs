@@ -0,0 +1,792 @@
+//! GeoTIFF format
+//!
+//! 標高格子をGeoTIFF形式で入出力します：
+//! - 非圧縮・32bit浮動小数点・1行1ストリップのリトルエンディアンTIFF
+//! - ModelPixelScale・ModelTiepointによる格子の位置（GISの座標：x東・y北）
+//! - GeoKeyDirectoryによる平面直角座標系のEPSGコード（測地原子別、既定はJGD2011: 6669～6687）
+//!   または緯度経度（JGD2000: 4612、JGD2011: 6668、旧日本測地系: 4301）
+//! - GDAL_NODATAタグによる欠測値
+//!
+//! 平面直角座標系が設定されていない格子では座標系のGeoKeyを省略します。
+//! 読み込んだEPSGコードは平面直角座標系以外（UTM等）も[`DemGrid::epsg_code`]に保持します。
+//! 読み込みは非圧縮の単一バンドの画像に対応し、PixelIsPointの格子はセル中心の値として扱います。
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+use super::{DemGrid, NODATA_VALUE};
+use crate::coordinate_system::{HorizontalDatum, PlaneCrs};
+use crate::error::LandXMLError;
+
+// ============================================================================
+// TIFFタグ・GeoKey
+// ============================================================================
+
+const TAG_IMAGE_WIDTH: u16 = 256;
+const TAG_IMAGE_LENGTH: u16 = 257;
+const TAG_BITS_PER_SAMPLE: u16 = 258;
+const TAG_COMPRESSION: u16 = 259;
+const TAG_PHOTOMETRIC: u16 = 262;
+const TAG_STRIP_OFFSETS: u16 = 273;
+const TAG_SAMPLES_PER_PIXEL: u16 = 277;
+const TAG_ROWS_PER_STRIP: u16 = 278;
+const TAG_STRIP_BYTE_COUNTS: u16 = 279;
+const TAG_PLANAR_CONFIGURATION: u16 = 284;
+const TAG_SAMPLE_FORMAT: u16 = 339;
+const TAG_MODEL_PIXEL_SCALE: u16 = 33550;
+const TAG_MODEL_TIEPOINT: u16 = 33922;
+const TAG_GEO_KEY_DIRECTORY: u16 = 34735;
+const TAG_GDAL_NODATA: u16 = 42113;
+
+const TYPE_ASCII: u16 = 2;
+const TYPE_SHORT: u16 = 3;
+const TYPE_LONG: u16 = 4;
+const TYPE_DOUBLE: u16 = 12;
+
+const KEY_MODEL_TYPE: u16 = 1024;
+const KEY_RASTER_TYPE: u16 = 1025;
+const KEY_GEOGRAPHIC_TYPE: u16 = 2048;
+const KEY_PROJECTED_CS_TYPE: u16 = 3072;
+const KEY_PROJ_LINEAR_UNITS: u16 = 3076;
+
+/// GTModelTypeGeoKey: 投影座標系
+const MODEL_TYPE_PROJECTED: u16 = 1;
+/// GTModelTypeGeoKey: 地理座標系（緯度経度）
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+/// GTRasterTypeGeoKey: 値はセル全体を表す
+const RASTER_PIXEL_IS_AREA: u16 = 1;
+/// GTRasterTypeGeoKey: 値はセルの左上隅の点を表す
+const RASTER_PIXEL_IS_POINT: u16 = 2;
+/// ProjLinearUnitsGeoKey: メートル（EPSG:9001）
+const LINEAR_UNIT_METRE: u16 = 9001;
+
+/// IFDのエントリ（値はリトルエンディアンのバイト列）
+struct Entry {
+    tag: u16,
+    field_type: u16,
+    count: u32,
+    data: Vec<u8>,
+}
+
+impl Entry {
+    fn shorts(tag: u16, values: &[u16]) -> Self {
+        Self {
+            tag,
+            field_type: TYPE_SHORT,
+            count: values.len() as u32,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn longs(tag: u16, values: &[u32]) -> Self {
+        Self {
+            tag,
+            field_type: TYPE_LONG,
+            count: values.len() as u32,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn doubles(tag: u16, values: &[f64]) -> Self {
+        Self {
+            tag,
+            field_type: TYPE_DOUBLE,
+            count: values.len() as u32,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn ascii(tag: u16, value: &str) -> Self {
+        let mut data = value.as_bytes().to_vec();
+        data.push(0);
+        Self {
+            tag,
+            field_type: TYPE_ASCII,
+            count: data.len() as u32,
+            data,
+        }
+    }
+}
+
+impl DemGrid {
+    /// GeoTIFF形式のバイト列に変換
+    pub fn to_geotiff(&self) -> Result<Vec<u8>, LandXMLError> {
+        let row_bytes = self.cols * 4;
+        let image_bytes = self.rows * row_bytes;
+        if self.rows == 0 || self.cols == 0 || image_bytes > u32::MAX as usize / 2 {
+            return Err(LandXMLError::InvalidGridSize {
+                expected: self.rows.max(1) * self.cols.max(1),
+                actual: self.values.len(),
+            });
+        }
+
+        // ヘッダー（8バイト）の直後に画像、その後にタグの値とIFDを置く
+        const HEADER: usize = 8;
+        let strip_offsets: Vec<u32> = (0..self.rows)
+            .map(|row| (HEADER + row * row_bytes) as u32)
+            .collect();
+
+        // 測地原子の既定はJGD2011、判定できない座標系は読み込んだEPSGコードを書き出す
+        const DEFAULT_DATUM: HorizontalDatum = HorizontalDatum::JGD2011;
+        let code = match (self.geographic, self.horizontal_datum, self.zone) {
+            (true, Some(datum), _) => Some(datum.geographic_epsg_code()),
+            (true, None, _) => Some(
+                self.epsg_code
+                    .unwrap_or(DEFAULT_DATUM.geographic_epsg_code()),
+            ),
+            (false, datum, Some(zone)) => {
+                Some(datum.unwrap_or(DEFAULT_DATUM).projected_epsg_code(zone))
+            }
+            (false, _, None) => self.epsg_code,
+        }
+        .and_then(|code| u16::try_from(code).ok());
+        let mut keys = Vec::new();
+        if self.geographic {
+            keys.push([KEY_MODEL_TYPE, 0, 1, MODEL_TYPE_GEOGRAPHIC]);
+            keys.push([KEY_RASTER_TYPE, 0, 1, RASTER_PIXEL_IS_AREA]);
+            if let Some(code) = code {
+                keys.push([KEY_GEOGRAPHIC_TYPE, 0, 1, code]);
+            }
+        } else {
+            keys.push([KEY_MODEL_TYPE, 0, 1, MODEL_TYPE_PROJECTED]);
+            keys.push([KEY_RASTER_TYPE, 0, 1, RASTER_PIXEL_IS_AREA]);
+            if let Some(code) = code {
+                keys.push([KEY_PROJECTED_CS_TYPE, 0, 1, code]);
+            }
+            keys.push([KEY_PROJ_LINEAR_UNITS, 0, 1, LINEAR_UNIT_METRE]);
+        }
+        let mut directory = vec![1, 1, 0, keys.len() as u16];
+        directory.extend(keys.iter().flatten());
+
+        let mut entries = vec![
+            Entry::longs(TAG_IMAGE_WIDTH, &[self.cols as u32]),
+            Entry::longs(TAG_IMAGE_LENGTH, &[self.rows as u32]),
+            Entry::shorts(TAG_BITS_PER_SAMPLE, &[32]),
+            Entry::shorts(TAG_COMPRESSION, &[1]),
+            Entry::shorts(TAG_PHOTOMETRIC, &[1]),
+            Entry::longs(TAG_STRIP_OFFSETS, &strip_offsets),
+            Entry::shorts(TAG_SAMPLES_PER_PIXEL, &[1]),
+            Entry::longs(TAG_ROWS_PER_STRIP, &[1]),
+            Entry::longs(TAG_STRIP_BYTE_COUNTS, &vec![row_bytes as u32; self.rows]),
+            Entry::shorts(TAG_PLANAR_CONFIGURATION, &[1]),
+            // IEEE浮動小数点
+            Entry::shorts(TAG_SAMPLE_FORMAT, &[3]),
+            Entry::doubles(TAG_MODEL_PIXEL_SCALE, &[self.x_res, self.y_res, 0.0]),
+            Entry::doubles(
+                TAG_MODEL_TIEPOINT,
+                &[0.0, 0.0, 0.0, self.west, self.north, 0.0],
+            ),
+            Entry::shorts(TAG_GEO_KEY_DIRECTORY, &directory),
+            Entry::ascii(TAG_GDAL_NODATA, &NODATA_VALUE.to_string()),
+        ];
+        entries.sort_by_key(|e| e.tag);
+
+        let mut bytes = Vec::with_capacity(HEADER + image_bytes + 1024);
+        bytes.extend_from_slice(b"II");
+        bytes.extend_from_slice(&42u16.to_le_bytes());
+        bytes.extend_from_slice(&[0; 4]);
+        for value in &self.values {
+            bytes.extend_from_slice(&(value.unwrap_or(NODATA_VALUE) as f32).to_le_bytes());
+        }
+
+        // 4バイトに収まらない値はIFDの外に置く（ワード境界に揃える）
+        let mut fields = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            if entry.data.len() <= 4 {
+                let mut inline = entry.data.clone();
+                inline.resize(4, 0);
+                fields.push(inline);
+            } else {
+                if bytes.len() % 2 == 1 {
+                    bytes.push(0);
+                }
+                fields.push((bytes.len() as u32).to_le_bytes().to_vec());
+                bytes.extend_from_slice(&entry.data);
+            }
+        }
+        if bytes.len() % 2 == 1 {
+            bytes.push(0);
+        }
+        let ifd_offset = bytes.len() as u32;
+        bytes[4..8].copy_from_slice(&ifd_offset.to_le_bytes());
+        bytes.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        for (entry, field) in entries.iter().zip(&fields) {
+            bytes.extend_from_slice(&entry.tag.to_le_bytes());
+            bytes.extend_from_slice(&entry.field_type.to_le_bytes());
+            bytes.extend_from_slice(&entry.count.to_le_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// GeoTIFF形式で書き出し
+    pub fn write_geotiff<W: Write>(&self, mut output: W) -> Result<(), LandXMLError> {
+        output.write_all(&self.to_geotiff()?)?;
+        output.flush()?;
+        Ok(())
+    }
+
+    /// GeoTIFF形式のファイルに書き出し
+    pub fn write_geotiff_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LandXMLError> {
+        std::fs::write(path, self.to_geotiff()?)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 読み込み
+// ============================================================================
+
+const TAG_TILE_WIDTH: u16 = 322;
+const TAG_TILE_LENGTH: u16 = 323;
+const TAG_TILE_OFFSETS: u16 = 324;
+const TAG_TILE_BYTE_COUNTS: u16 = 325;
+const TAG_MODEL_TRANSFORMATION: u16 = 34264;
+
+fn invalid(message: impl Into<String>) -> LandXMLError {
+    LandXMLError::InvalidFormat {
+        message: message.into(),
+    }
+}
+
+/// TIFFのバイト列（ファイルのバイト順で数値を読む）
+struct TiffReader<'a> {
+    bytes: &'a [u8],
+    big_endian: bool,
+}
+
+impl TiffReader<'_> {
+    fn slice(&self, offset: usize, length: usize) -> Result<&[u8], LandXMLError> {
+        offset
+            .checked_add(length)
+            .and_then(|end| self.bytes.get(offset..end))
+            .ok_or_else(|| invalid("Truncated TIFF data"))
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, LandXMLError> {
+        Ok(self.number(self.slice(offset, 2)?, 1) as u16)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, LandXMLError> {
+        Ok(self.number(self.slice(offset, 4)?, 1) as u32)
+    }
+
+    /// 数値を読む（format: 1=符号なし整数, 2=符号付き整数, 3=浮動小数点）
+    fn number(&self, data: &[u8], format: u16) -> f64 {
+        let mut le = [0u8; 8];
+        le[..data.len()].copy_from_slice(data);
+        if self.big_endian {
+            le[..data.len()].reverse();
+        }
+        let [b0, b1, b2, b3, ..] = le;
+        match (format, data.len()) {
+            (3, 4) => f32::from_le_bytes([b0, b1, b2, b3]) as f64,
+            (3, _) => f64::from_le_bytes(le),
+            (2, 1) => b0 as i8 as f64,
+            (2, 2) => i16::from_le_bytes([b0, b1]) as f64,
+            (2, 4) => i32::from_le_bytes([b0, b1, b2, b3]) as f64,
+            (2, _) => i64::from_le_bytes(le) as f64,
+            _ => u64::from_le_bytes(le) as f64,
+        }
+    }
+
+    /// IFDエントリの型・個数と値のバイト列
+    fn entry_data(&self, entry: usize) -> Result<(u16, usize, &[u8]), LandXMLError> {
+        let field_type = self.u16(entry + 2)?;
+        let count = self.u32(entry + 4)? as usize;
+        let size = match field_type {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 | 16 => 8,
+            _ => {
+                return Err(invalid(format!(
+                    "Unsupported TIFF field type: {}",
+                    field_type
+                )))
+            }
+        };
+        let total = count
+            .checked_mul(size)
+            .ok_or_else(|| invalid("Truncated TIFF data"))?;
+        let offset = if total <= 4 {
+            entry + 8
+        } else {
+            self.u32(entry + 8)? as usize
+        };
+        Ok((field_type, count, self.slice(offset, total)?))
+    }
+
+    /// IFDエントリの値を数値列として取得
+    fn values(&self, entry: usize) -> Result<Vec<f64>, LandXMLError> {
+        let (field_type, count, data) = self.entry_data(entry)?;
+        let size = data.len() / count.max(1);
+        Ok(data
+            .chunks_exact(size.max(1))
+            .map(|value| match field_type {
+                // 有理数（分子・分母）
+                5 | 10 => {
+                    let format = if field_type == 5 { 1 } else { 2 };
+                    self.number(&value[..4], format) / self.number(&value[4..], format)
+                }
+                6 | 8 | 9 => self.number(value, 2),
+                11 | 12 => self.number(value, 3),
+                _ => self.number(value, 1),
+            })
+            .collect())
+    }
+
+    /// ASCII型のIFDエントリの値
+    fn text(&self, entry: usize) -> Result<String, LandXMLError> {
+        let (_, _, data) = self.entry_data(entry)?;
+        Ok(String::from_utf8_lossy(data)
+            .trim_end_matches('\0')
+            .to_string())
+    }
+}
+
+impl DemGrid {
+    /// GeoTIFF形式のバイト列から読み込み
+    ///
+    /// 非圧縮の単一バンド（整数・浮動小数点）のストリップ・タイル形式に対応します。
+    /// 最初のIFD（画像）のみを読み、複数サンプルの場合は先頭のサンプルを使用します。
+    pub fn from_geotiff(bytes: &[u8]) -> Result<Self, LandXMLError> {
+        let big_endian = match bytes.get(0..2) {
+            Some(b"II") => false,
+            Some(b"MM") => true,
+            _ => return Err(invalid("Not a TIFF file")),
+        };
+        let reader = TiffReader { bytes, big_endian };
+        match reader.u16(2)? {
+            42 => {}
+            43 => return Err(invalid("BigTIFF is not supported")),
+            _ => return Err(invalid("Not a TIFF file")),
+        }
+
+        let ifd = reader.u32(4)? as usize;
+        let mut tags = HashMap::new();
+        for i in 0..reader.u16(ifd)? as usize {
+            let entry = ifd + 2 + i * 12;
+            tags.insert(reader.u16(entry)?, entry);
+        }
+        let get = |tag: u16| {
+            tags.get(&tag)
+                .map(|&entry| reader.values(entry))
+                .transpose()
+        };
+        let first = |tag: u16, default: Option<f64>| -> Result<f64, LandXMLError> {
+            get(tag)?
+                .and_then(|values| values.first().copied())
+                .or(default)
+                .ok_or_else(|| invalid(format!("Missing TIFF tag {}", tag)))
+        };
+
+        let cols = first(TAG_IMAGE_WIDTH, None)? as usize;
+        let rows = first(TAG_IMAGE_LENGTH, None)? as usize;
+        let compression = first(TAG_COMPRESSION, Some(1.0))?;
+        if compression != 1.0 {
+            return Err(invalid(format!(
+                "Unsupported TIFF compression: {}",
+                compression
+            )));
+        }
+        let bits = first(TAG_BITS_PER_SAMPLE, Some(1.0))? as usize;
+        let format = first(TAG_SAMPLE_FORMAT, Some(1.0))? as u16;
+        if !matches!(bits, 8 | 16 | 32 | 64)
+            || !(1..=3).contains(&format)
+            || (format == 3 && bits < 32)
+        {
+            return Err(invalid(format!(
+                "Unsupported TIFF sample format: {} bits, format {}",
+                bits, format
+            )));
+        }
+        let sample_bytes = bits / 8;
+        let samples = first(TAG_SAMPLES_PER_PIXEL, Some(1.0))? as usize;
+        // 色ごとに分かれた配置（PlanarConfiguration=2）では先頭の面が最初に並ぶ
+        let pixel_bytes = if first(TAG_PLANAR_CONFIGURATION, Some(1.0))? == 2.0 {
+            sample_bytes
+        } else {
+            sample_bytes * samples.max(1)
+        };
+
+        let (block_width, block_height, offsets) = match get(TAG_TILE_OFFSETS)? {
+            Some(offsets) => (
+                first(TAG_TILE_WIDTH, None)? as usize,
+                first(TAG_TILE_LENGTH, None)? as usize,
+                offsets,
+            ),
+            None => (
+                cols,
+                (first(TAG_ROWS_PER_STRIP, Some(rows as f64))? as usize).min(rows),
+                get(TAG_STRIP_OFFSETS)?.ok_or_else(|| invalid("Missing TIFF strip offsets"))?,
+            ),
+        };
+        if block_width == 0 || block_height == 0 {
+            return Err(invalid("Invalid TIFF block size"));
+        }
+        let blocks_across = cols.div_ceil(block_width);
+
+        // 画像の大きさは確保の前にストリップ・タイルの位置と大きさで検証する
+        let truncated = || invalid("Truncated TIFF data");
+        rows.checked_mul(cols)
+            .and_then(|cells| cells.checked_mul(pixel_bytes))
+            .filter(|&image_bytes| image_bytes <= bytes.len())
+            .ok_or_else(truncated)?;
+        let tiled = tags.contains_key(&TAG_TILE_OFFSETS);
+        let blocks = blocks_across
+            .checked_mul(rows.div_ceil(block_height))
+            .filter(|&blocks| blocks <= offsets.len())
+            .ok_or_else(truncated)?;
+        let byte_counts = get(if tiled {
+            TAG_TILE_BYTE_COUNTS
+        } else {
+            TAG_STRIP_BYTE_COUNTS
+        })?;
+        for (block, &offset) in offsets.iter().take(blocks).enumerate() {
+            // 最後のストリップは残りの行のみ
+            let block_rows = if tiled {
+                block_height
+            } else {
+                block_height.min(rows - block * block_height)
+            };
+            let length = block_width
+                .checked_mul(block_rows)
+                .and_then(|pixels| pixels.checked_mul(pixel_bytes))
+                .ok_or_else(truncated)?;
+            let counted = byte_counts
+                .as_ref()
+                .is_none_or(|counts| counts.get(block).is_some_and(|&n| n as usize >= length));
+            if !counted || reader.slice(offset as usize, length).is_err() {
+                return Err(truncated());
+            }
+        }
+
+        let nodata: Option<f64> = tags
+            .get(&TAG_GDAL_NODATA)
+            .map(|&entry| reader.text(entry))
+            .transpose()?
+            .and_then(|text| text.trim().parse().ok());
+        let mut values = Vec::with_capacity(rows * cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                let block = (row / block_height) * blocks_across + col / block_width;
+                let offset = offsets[block] as usize;
+                let within = ((row % block_height) * block_width + col % block_width) * pixel_bytes;
+                let value = reader.number(reader.slice(offset + within, sample_bytes)?, format);
+                let missing = !value.is_finite()
+                    || nodata.is_some_and(|n| value == n || value as f32 == n as f32);
+                values.push((!missing).then_some(value));
+            }
+        }
+
+        let scale = get(TAG_MODEL_PIXEL_SCALE)?;
+        let tiepoint = get(TAG_MODEL_TIEPOINT)?;
+        let transformation = get(TAG_MODEL_TRANSFORMATION)?;
+        let (x_res, y_res, mut west, mut north) = match (scale, tiepoint, transformation) {
+            (Some(s), Some(t), _) if s.len() >= 2 && t.len() >= 6 => {
+                (s[0], s[1], t[3] - t[0] * s[0], t[4] + t[1] * s[1])
+            }
+            (_, _, Some(m)) if m.len() >= 8 && m[1] == 0.0 && m[4] == 0.0 => {
+                (m[0], -m[5], m[3], m[7])
+            }
+            _ => return Err(invalid("Missing or unsupported GeoTIFF georeferencing")),
+        };
+
+        let mut geo_keys = HashMap::new();
+        if let Some(directory) = get(TAG_GEO_KEY_DIRECTORY)? {
+            for key in directory.chunks_exact(4).skip(1) {
+                // 値がGeoKeyDirectory内にある（格納先タグが0の）キーのみ
+                if key[1] == 0.0 {
+                    geo_keys.insert(key[0] as u16, key[3] as u32);
+                }
+            }
+        }
+        if geo_keys.get(&KEY_RASTER_TYPE) == Some(&(RASTER_PIXEL_IS_POINT as u32)) {
+            // 格子点の値をセル中心の値として扱う
+            west -= x_res / 2.0;
+            north += y_res / 2.0;
+        }
+
+        let mut grid = Self::from_values(rows, cols, north, west, x_res, y_res, values)?;
+        grid.geographic = geo_keys.get(&KEY_MODEL_TYPE) == Some(&(MODEL_TYPE_GEOGRAPHIC as u32));
+        if grid.geographic {
+            grid.epsg_code = geo_keys.get(&KEY_GEOGRAPHIC_TYPE).copied();
+            grid.horizontal_datum = grid
+                .epsg_code
+                .and_then(|code| HorizontalDatum::from_geographic_epsg(code).ok());
+        } else {
+            grid.epsg_code = geo_keys.get(&KEY_PROJECTED_CS_TYPE).copied();
+            let crs = grid
+                .epsg_code
+                .and_then(|code| PlaneCrs::from_epsg(code).ok());
+            grid.zone = crs.map(|crs| crs.zone);
+            grid.horizontal_datum = crs.map(|crs| crs.horizontal_datum);
+        }
+        Ok(grid)
+    }
+
+    /// GeoTIFF形式のファイルから読み込み
+    pub fn from_geotiff_file<P: AsRef<Path>>(path: P) -> Result<Self, LandXMLError> {
+        Self::from_geotiff(&std::fs::read(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate_system::JapanPlaneCoordinateSystem;
+
+    fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_write_geotiff() {
+        let grid = DemGrid::from_values(
+            2,
+            2,
+            -35000.0,
+            12000.0,
+            5.0,
+            5.0,
+            vec![Some(10.5), None, Some(-2.0), Some(100.25)],
+        )
+        .unwrap()
+        .with_zone(JapanPlaneCoordinateSystem::Zone9);
+        let bytes = grid.to_geotiff().unwrap();
+
+        assert_eq!(&bytes[0..4], b"II\x2a\x00");
+        // 画像データ（1行目）
+        assert_eq!(f32::from_le_bytes(bytes[8..12].try_into().unwrap()), 10.5);
+        assert_eq!(
+            f32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+            -9999.0
+        );
+
+        let ifd = u32_at(&bytes, 4) as usize;
+        let count = u16_at(&bytes, ifd) as usize;
+        let entry = |tag: u16| {
+            (0..count)
+                .map(|i| ifd + 2 + i * 12)
+                .find(|&offset| u16_at(&bytes, offset) == tag)
+                .unwrap()
+        };
+        assert_eq!(u32_at(&bytes, entry(TAG_IMAGE_WIDTH) + 8), 2);
+        assert_eq!(u16_at(&bytes, entry(TAG_SAMPLE_FORMAT) + 8), 3);
+
+        let tiepoint = u32_at(&bytes, entry(TAG_MODEL_TIEPOINT) + 8) as usize;
+        let west = f64::from_le_bytes(bytes[tiepoint + 24..tiepoint + 32].try_into().unwrap());
+        let north = f64::from_le_bytes(bytes[tiepoint + 32..tiepoint + 40].try_into().unwrap());
+        assert_eq!((west, north), (12000.0, -35000.0));
+
+        // GeoKeyDirectoryにJGD2011 / 9系（EPSG:6677）
+        let keys = u32_at(&bytes, entry(TAG_GEO_KEY_DIRECTORY) + 8) as usize;
+        let key_count = u16_at(&bytes, keys + 6) as usize;
+        let epsg = (0..key_count)
+            .map(|i| keys + 8 + i * 8)
+            .find(|&offset| u16_at(&bytes, offset) == KEY_PROJECTED_CS_TYPE)
+            .map(|offset| u16_at(&bytes, offset + 6));
+        assert_eq!(epsg, Some(6677));
+
+        assert!(DemGrid::new(0, 3, 0.0, 0.0, 1.0, 1.0)
+            .unwrap()
+            .to_geotiff()
+            .is_err());
+    }
+
+    #[test]
+    fn test_read_geotiff() {
+        let grid = DemGrid::from_values(
+            2,
+            3,
+            -35000.0,
+            12000.0,
+            5.0,
+            2.5,
+            vec![Some(10.5), None, Some(-2.0), Some(100.25), Some(0.0), None],
+        )
+        .unwrap()
+        .with_zone(JapanPlaneCoordinateSystem::Zone9);
+        let read = DemGrid::from_geotiff(&grid.to_geotiff().unwrap()).unwrap();
+        assert_eq!((read.rows, read.cols), (2, 3));
+        assert_eq!((read.north, read.west), (-35000.0, 12000.0));
+        assert_eq!((read.x_res, read.y_res), (5.0, 2.5));
+        assert_eq!(read.zone, Some(JapanPlaneCoordinateSystem::Zone9));
+        assert!(!read.geographic);
+        assert_eq!(read.values, grid.values);
+
+        let mut geographic =
+            DemGrid::from_values(1, 1, 36.0, 139.0, 0.5, 0.5, vec![Some(1.0)]).unwrap();
+        geographic.geographic = true;
+        let read = DemGrid::from_geotiff(&geographic.to_geotiff().unwrap()).unwrap();
+        assert!(read.geographic);
+        assert_eq!(read.zone, None);
+        assert_eq!(read.horizontal_datum, Some(HorizontalDatum::JGD2011));
+        assert_eq!(read.epsg_code, Some(6668));
+
+        // 旧日本測地系の緯度経度（EPSG:4301）・9系（EPSG:30169）は測地原子を保持
+        geographic.horizontal_datum = Some(HorizontalDatum::TD);
+        let read = DemGrid::from_geotiff(&geographic.to_geotiff().unwrap()).unwrap();
+        assert_eq!(read.horizontal_datum, Some(HorizontalDatum::TD));
+        assert_eq!(read.epsg_code, Some(4301));
+        let tokyo = grid.clone().with_crs(PlaneCrs {
+            zone: JapanPlaneCoordinateSystem::Zone9,
+            horizontal_datum: HorizontalDatum::TD,
+        });
+        let read = DemGrid::from_geotiff(&tokyo.to_geotiff().unwrap()).unwrap();
+        assert_eq!(read.zone, Some(JapanPlaneCoordinateSystem::Zone9));
+        assert_eq!(read.horizontal_datum, Some(HorizontalDatum::TD));
+        assert_eq!(read.epsg_code, Some(30169));
+
+        // 平面直角座標系以外（UTM 54N、EPSG:32654）はコードのみ保持して書き戻す
+        let mut utm = grid.clone();
+        utm.zone = None;
+        utm.epsg_code = Some(32654);
+        let read = DemGrid::from_geotiff(&utm.to_geotiff().unwrap()).unwrap();
+        assert_eq!(read.zone, None);
+        assert_eq!(read.horizontal_datum, None);
+        assert_eq!(read.epsg_code, Some(32654));
+
+        assert!(DemGrid::from_geotiff(b"P5 1 1").is_err());
+    }
+
+    #[test]
+    fn test_read_forged_geotiff() {
+        let grid = DemGrid::from_values(2, 2, 0.0, 0.0, 1.0, 1.0, vec![Some(1.0); 4]).unwrap();
+        let bytes = grid.to_geotiff().unwrap();
+        let ifd = u32_at(&bytes, 4) as usize;
+        let count = u16_at(&bytes, ifd) as usize;
+        // 指定したタグの値（4バイト以内の先頭の値、またはその格納位置）を書き換え
+        let forge = |tag: u16, value: u32, indirect: bool| {
+            let mut forged = bytes.clone();
+            let entry = (0..count)
+                .map(|i| ifd + 2 + i * 12)
+                .find(|&offset| u16_at(&bytes, offset) == tag)
+                .unwrap();
+            let at = if indirect {
+                u32_at(&bytes, entry + 8) as usize
+            } else {
+                entry + 8
+            };
+            forged[at..at + 4].copy_from_slice(&value.to_le_bytes());
+            DemGrid::from_geotiff(&forged)
+        };
+        assert!(forge(TAG_IMAGE_WIDTH, 2, false).is_ok());
+
+        // データに収まらない画像の大きさ・ストリップの不足・バイト数の不足
+        for result in [
+            forge(TAG_IMAGE_WIDTH, u32::MAX, false),
+            forge(TAG_IMAGE_LENGTH, 0x1000_0000, false),
+            forge(TAG_IMAGE_LENGTH, 3, false),
+            forge(TAG_STRIP_BYTE_COUNTS, 4, true),
+            forge(TAG_STRIP_OFFSETS, u32::MAX - 4, true),
+        ] {
+            assert!(matches!(result, Err(LandXMLError::InvalidFormat { .. })));
+        }
+    }
+
+    #[test]
+    fn test_read_big_endian_geotiff() {
+        // ビッグエンディアン・16bit符号付き整数・PixelIsPoint・9系（EPSG:6677）
+        let doubles =
+            |values: &[f64]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+        let shorts =
+            |values: &[u16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+        let pixels: Vec<u8> = [100i16, -32768, -5, 7]
+            .iter()
+            .flat_map(|v| v.to_be_bytes())
+            .collect();
+        let entries: Vec<(u16, u16, u32, Vec<u8>)> = vec![
+            (TAG_IMAGE_WIDTH, TYPE_SHORT, 1, shorts(&[2])),
+            (TAG_IMAGE_LENGTH, TYPE_SHORT, 1, shorts(&[2])),
+            (TAG_BITS_PER_SAMPLE, TYPE_SHORT, 1, shorts(&[16])),
+            (TAG_COMPRESSION, TYPE_SHORT, 1, shorts(&[1])),
+            (TAG_STRIP_OFFSETS, TYPE_LONG, 1, Vec::new()),
+            (TAG_ROWS_PER_STRIP, TYPE_SHORT, 1, shorts(&[2])),
+            (TAG_SAMPLE_FORMAT, TYPE_SHORT, 1, shorts(&[2])),
+            (
+                TAG_MODEL_PIXEL_SCALE,
+                TYPE_DOUBLE,
+                3,
+                doubles(&[10.0, 10.0, 0.0]),
+            ),
+            (
+                TAG_MODEL_TIEPOINT,
+                TYPE_DOUBLE,
+                6,
+                doubles(&[0.0, 0.0, 0.0, 1000.0, 2000.0, 0.0]),
+            ),
+            (
+                TAG_GEO_KEY_DIRECTORY,
+                TYPE_SHORT,
+                12,
+                shorts(&[
+                    1,
+                    1,
+                    0,
+                    2,
+                    KEY_RASTER_TYPE,
+                    0,
+                    1,
+                    RASTER_PIXEL_IS_POINT,
+                    KEY_PROJECTED_CS_TYPE,
+                    0,
+                    1,
+                    6677,
+                ]),
+            ),
+            (TAG_GDAL_NODATA, TYPE_ASCII, 7, b"-32768\0".to_vec()),
+        ];
+
+        let ifd_size = 2 + entries.len() * 12 + 4;
+        let mut bytes = b"MM\x00\x2a\x00\x00\x00\x08".to_vec();
+        let mut data = Vec::new();
+        let data_start = 8 + ifd_size;
+        bytes.extend((entries.len() as u16).to_be_bytes());
+        for (tag, field_type, count, mut value) in entries {
+            if tag == TAG_STRIP_OFFSETS {
+                value = ((data_start + 200) as u32).to_be_bytes().to_vec();
+            }
+            bytes.extend(tag.to_be_bytes());
+            bytes.extend(field_type.to_be_bytes());
+            bytes.extend(count.to_be_bytes());
+            if value.len() <= 4 {
+                value.resize(4, 0);
+                bytes.extend(value);
+            } else {
+                bytes.extend(((data_start + data.len()) as u32).to_be_bytes());
+                data.extend(value);
+            }
+        }
+        bytes.extend([0; 4]);
+        data.resize(200, 0);
+        bytes.extend(data);
+        bytes.extend(pixels);
+
+        let grid = DemGrid::from_geotiff(&bytes).unwrap();
+        assert_eq!((grid.rows, grid.cols), (2, 2));
+        // 格子点の値をセル中心とするため半セル分ずらす
+        assert_eq!((grid.north, grid.west), (2005.0, 995.0));
+        assert_eq!(grid.zone, Some(JapanPlaneCoordinateSystem::Zone9));
+        assert_eq!(grid.values, vec![Some(100.0), None, Some(-5.0), Some(7.0)]);
+
+        let compressed = {
+            let mut bytes = bytes.clone();
+            // Compressionエントリの値（4番目のエントリ）を5（LZW）に変更
+            let offset = 8 + 2 + 3 * 12 + 8;
+            bytes[offset..offset + 2].copy_from_slice(&5u16.to_be_bytes());
+            bytes
+        };
+        assert!(matches!(
+            DemGrid::from_geotiff(&compressed),
+            Err(LandXMLError::InvalidFormat { .. })
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+//! GSI fundamental geospatial data DEM
+//!
+//! 国土地理院 基盤地図情報 数値標高モデル（JPGIS（GML）形式）を読み込みます：
+//! - 5mメッシュ・10mメッシュの標高（DEM要素）
+//! - 包含矩形（緯度経度）と格子の範囲から緯度経度の格子を作成
+//! - 開始点（startPoint）より前・データの末尾より後のセル、および-9999の値は欠測
+//! - 包含矩形の参照系（fguuid:jgd2011.bl・fguuid:jgd2000.bl）による測地原子
+//!
+//! 標高はT.P.（東京湾平均海面）基準です。
+
+use std::path::Path;
+
+use super::DemGrid;
+use crate::coordinate_system::HorizontalDatum;
+use crate::error::LandXMLError;
+use crate::xml::{read_document, XmlElement};
+
+/// 基盤地図情報で欠測を表す値
+const GSI_NODATA_VALUE: f64 = -9999.0;
+
+/// 格子のセル数の上限（10mメッシュの1125×750を十分に含む）
+const MAX_GSI_CELLS: usize = 4_000_000;
+
+/// 子孫要素を深さ優先で探索
+fn descendant<'a>(element: &'a XmlElement, name: &str) -> Option<&'a XmlElement> {
+    element.children.iter().find_map(|child| {
+        if child.name == name {
+            Some(child)
+        } else {
+            descendant(child, name)
+        }
+    })
+}
+
+fn required<'a>(element: &'a XmlElement, name: &str) -> Result<&'a XmlElement, LandXMLError> {
+    descendant(element, name).ok_or_else(|| LandXMLError::MissingElement {
+        element: format!("{}//{}", element.name, name),
+    })
+}
+
+/// 空白区切りの2つの数値
+fn pair(element: &XmlElement) -> Result<(f64, f64), LandXMLError> {
+    match element.text_numbers()?.as_slice() {
+        [a, b, ..] => Ok((*a, *b)),
+        _ => Err(LandXMLError::InvalidFormat {
+            message: format!("Expected two numbers in {}: {}", element.name, element.text),
+        }),
+    }
+}
+
+impl DemGrid {
+    /// 基盤地図情報 数値標高モデル（JPGIS（GML）形式）の文字列から読み込み
+    pub fn from_gsi_xml_str(content: &str) -> Result<Self, LandXMLError> {
+        let root = XmlElement::parse_str(content)?;
+        let dem = if root.name == "DEM" {
+            &root
+        } else {
+            required(&root, "DEM")?
+        };
+
+        let envelope = required(dem, "Envelope")?;
+        // 参照系の省略時は現行の基盤地図情報と同じJGD2011
+        let datum = match envelope.attr("srsName").map(str::to_ascii_lowercase) {
+            None => HorizontalDatum::JGD2011,
+            Some(name) if name.contains("jgd2011") => HorizontalDatum::JGD2011,
+            Some(name) if name.contains("jgd2000") => HorizontalDatum::JGD2000,
+            Some(name) => {
+                return Err(LandXMLError::InvalidCoordinateSystem(format!(
+                    "Unsupported GSI DEM reference system: {}",
+                    name
+                )))
+            }
+        };
+        let (lat_min, lon_min) = pair(envelope.required_child("lowerCorner")?)?;
+        let (lat_max, lon_max) = pair(envelope.required_child("upperCorner")?)?;
+        let grid_envelope = required(dem, "GridEnvelope")?;
+        let (low_x, low_y) = pair(grid_envelope.required_child("low")?)?;
+        let (high_x, high_y) = pair(grid_envelope.required_child("high")?)?;
+        let cols = (high_x - low_x + 1.0).max(0.0) as usize;
+        let rows = (high_y - low_y + 1.0).max(0.0) as usize;
+        let cells = rows
+            .checked_mul(cols)
+            .filter(|&cells| cells <= MAX_GSI_CELLS)
+            .ok_or_else(|| LandXMLError::InvalidFormat {
+                message: format!("DEM grid is too large: {} x {}", cols, rows),
+            })?;
+
+        if let Some(rule) = descendant(dem, "sequenceRule") {
+            let order = rule.attr("order").unwrap_or("+x-y");
+            if order != "+x-y" {
+                return Err(LandXMLError::InvalidFormat {
+                    message: format!("Unsupported DEM sequence order: {}", order),
+                });
+            }
+        }
+        let start = match descendant(dem, "startPoint") {
+            Some(point) => {
+                let (x, y) = pair(point)?;
+                (y as usize)
+                    .checked_mul(cols)
+                    .and_then(|start| start.checked_add(x as usize))
+                    .filter(|&start| start <= cells)
+                    .ok_or_else(|| LandXMLError::InvalidFormat {
+                        message: format!("DEM start point is outside the grid: {}", point.text),
+                    })?
+            }
+            None => 0,
+        };
+
+        let mut values = vec![None; start];
+        for tuple in required(dem, "tupleList")?.text.split_whitespace() {
+            let token = tuple.rsplit(',').next().unwrap_or(tuple);
+            let value: f64 = token.parse().map_err(|e| LandXMLError::InvalidFormat {
+                message: format!("Invalid DEM value '{}': {}", tuple, e),
+            })?;
+            values.push((value > GSI_NODATA_VALUE).then_some(value));
+        }
+        if values.len() > cells {
+            return Err(LandXMLError::InvalidGridSize {
+                expected: cells,
+                actual: values.len(),
+            });
+        }
+        values.resize(cells, None);
+
+        let mut grid = Self::from_values(
+            rows,
+            cols,
+            lat_max,
+            lon_min,
+            (lon_max - lon_min) / cols.max(1) as f64,
+            (lat_max - lat_min) / rows.max(1) as f64,
+            values,
+        )?;
+        grid.geographic = true;
+        grid.horizontal_datum = Some(datum);
+        grid.epsg_code = Some(datum.geographic_epsg_code());
+        Ok(grid)
+    }
+
+    /// 基盤地図情報 数値標高モデル（JPGIS（GML）形式）のファイルから読み込み
+    pub fn from_gsi_xml_file<P: AsRef<Path>>(path: P) -> Result<Self, LandXMLError> {
+        let (content, _) = read_document(path)?;
+        Self::from_gsi_xml_str(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../tests/data/gsi_dem5a_sample.xml");
+
+    #[test]
+    fn test_gsi_dem() {
+        let grid = DemGrid::from_gsi_xml_str(SAMPLE).unwrap();
+        assert!(grid.geographic);
+        assert_eq!(grid.horizontal_datum, Some(HorizontalDatum::JGD2011));
+        assert_eq!((grid.rows, grid.cols), (3, 3));
+        assert_eq!((grid.north, grid.west), (36.0003, 139.0));
+        assert!((grid.x_res - 0.0001).abs() < 1e-12);
+        assert!((grid.y_res - 0.0001).abs() < 1e-12);
+        // 開始点より前・末尾・-9999は欠測
+        assert_eq!(
+            grid.values,
+            vec![
+                None,
+                Some(33.27),
+                Some(33.25),
+                Some(31.5),
+                Some(32.0),
+                Some(32.1),
+                Some(30.0),
+                None,
+                None
+            ]
+        );
+
+        let overflow = SAMPLE.replace("<gml:startPoint>1 0", "<gml:startPoint>0 1");
+        assert!(matches!(
+            DemGrid::from_gsi_xml_str(&overflow),
+            Err(LandXMLError::InvalidGridSize {
+                expected: 9,
+                actual: 10
+            })
+        ));
+        assert!(DemGrid::from_gsi_xml_str("<Dataset/>").is_err());
+
+        // データに対して過大な格子・格子外の開始点
+        for forged in [
+            SAMPLE.replace("<gml:high>2 2", "<gml:high>1e9 1e9"),
+            SAMPLE.replace("<gml:startPoint>1 0", "<gml:startPoint>0 1e19"),
+            SAMPLE.replace("<gml:startPoint>1 0", "<gml:startPoint>0 4"),
+        ] {
+            assert!(matches!(
+                DemGrid::from_gsi_xml_str(&forged),
+                Err(LandXMLError::InvalidFormat { .. })
+            ));
+        }
+
+        let jgd2000 = SAMPLE.replace("jgd2011.bl", "jgd2000.bl");
+        let grid = DemGrid::from_gsi_xml_str(&jgd2000).unwrap();
+        assert_eq!(grid.horizontal_datum, Some(HorizontalDatum::JGD2000));
+        assert_eq!(grid.epsg_code, Some(4612));
+        assert!(matches!(
+            DemGrid::from_gsi_xml_str(&SAMPLE.replace("jgd2011.bl", "tky.bl")),
+            Err(LandXMLError::InvalidCoordinateSystem(_))
+        ));
+    }
+}
@@ -0,0 +1,290 @@
+//! DEM module for J-LandXML parser
+//!
+//! This module handles regular elevation grids (DEM):
+//! - 三角網（TIN）からの格子化（see [`raster`]）
+//! - ESRI ASCII grid形式の入出力（see [`ascii`]）
+//! - GeoTIFF形式の入出力（平面直角座標系のEPSGコード付き、see [`geotiff`]）
+//! - 国土地理院 基盤地図情報 数値標高モデルの読み込み（see [`gsi`]）
+//! - 格子から三角網（TIN）への変換（緯度経度の投影・間引き、see [`surface`]）
+//!
+//! GDAL等の外部ライブラリには依存しません。
+//! 格子は北端の行から南へ、各行は西端の列から東へ並びます。
+//! 格子の位置は測量座標（X北・Y東）、セルの大きさはGISのx（東西）・y（南北）方向で表します。
+//! 緯度経度の格子では北端を緯度、西端を経度、セルの大きさを度で表します。
+
+pub mod ascii;
+pub mod geotiff;
+pub mod gsi;
+pub mod raster;
+pub mod surface;
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::coordinate_system::{HorizontalDatum, JapanPlaneCoordinateSystem, PlaneCrs};
+use crate::error::LandXMLError;
+use crate::models::Point2D;
+use crate::units::gis_to_survey;
+use crate::xml::decode_document;
+
+pub use self::raster::RasterOptions;
+pub use self::surface::DemSurfaceOptions;
+
+/// 出力形式で欠測を表す値
+pub const NODATA_VALUE: f64 = -9999.0;
+
+/// 作成できる格子のセル数の上限
+pub const MAX_GRID_CELLS: usize = 100_000_000;
+
+/// 標高格子（DEM）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DemGrid {
+    /// 行数（南北方向）
+    pub rows: usize,
+    /// 列数（東西方向）
+    pub cols: usize,
+    /// 北端のX座標（m）
+    pub north: f64,
+    /// 西端のY座標（m）
+    pub west: f64,
+    /// セルの東西方向の幅（m）
+    pub x_res: f64,
+    /// セルの南北方向の高さ（m）
+    pub y_res: f64,
+    /// 平面直角座標系（GeoTIFFのEPSGコードに使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<JapanPlaneCoordinateSystem>,
+    /// 水平測地原子（Noneは不明・未設定）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizontal_datum: Option<HorizontalDatum>,
+    /// 読み込んだ座標系のEPSGコード（平面直角座標系以外のコードも保持）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epsg_code: Option<u32>,
+    /// 緯度経度の格子（northは緯度、westは経度、セルの大きさは度）
+    #[serde(default)]
+    pub geographic: bool,
+    /// 標高値（行優先、欠測はNone）
+    pub values: Vec<Option<f64>>,
+}
+
+impl DemGrid {
+    /// 全セル欠測の格子を作成
+    ///
+    /// セル数が[`MAX_GRID_CELLS`]を超える場合はエラーとします。
+    pub fn new(
+        rows: usize,
+        cols: usize,
+        north: f64,
+        west: f64,
+        x_res: f64,
+        y_res: f64,
+    ) -> Result<Self, LandXMLError> {
+        let cells = rows
+            .checked_mul(cols)
+            .filter(|&cells| cells <= MAX_GRID_CELLS)
+            .ok_or(LandXMLError::InvalidGridSize {
+                expected: MAX_GRID_CELLS,
+                actual: rows.saturating_mul(cols),
+            })?;
+        Self::from_values(rows, cols, north, west, x_res, y_res, vec![None; cells])
+    }
+
+    /// 標高値から格子を作成
+    pub fn from_values(
+        rows: usize,
+        cols: usize,
+        north: f64,
+        west: f64,
+        x_res: f64,
+        y_res: f64,
+        values: Vec<Option<f64>>,
+    ) -> Result<Self, LandXMLError> {
+        if !(x_res.is_finite() && x_res > 0.0 && y_res.is_finite() && y_res > 0.0) {
+            return Err(LandXMLError::InvalidResolution { x_res, y_res });
+        }
+        if rows.checked_mul(cols) != Some(values.len()) {
+            return Err(LandXMLError::InvalidGridSize {
+                expected: rows.saturating_mul(cols),
+                actual: values.len(),
+            });
+        }
+        Ok(Self {
+            rows,
+            cols,
+            north,
+            west,
+            x_res,
+            y_res,
+            zone: None,
+            horizontal_datum: None,
+            epsg_code: None,
+            geographic: false,
+            values,
+        })
+    }
+
+    /// 平面直角座標系を設定
+    ///
+    /// 水平測地原子は変更せず、EPSGコードは設定した系に合わせます。
+    pub fn with_zone(mut self, zone: JapanPlaneCoordinateSystem) -> Self {
+        self.zone = Some(zone);
+        self.epsg_code = self
+            .horizontal_datum
+            .map(|datum| datum.projected_epsg_code(zone));
+        self
+    }
+
+    /// 平面直角座標系と水平測地原子を設定
+    pub fn with_crs(mut self, crs: PlaneCrs) -> Self {
+        self.zone = Some(crs.zone);
+        self.horizontal_datum = Some(crs.horizontal_datum);
+        self.epsg_code = Some(crs.epsg_code());
+        self
+    }
+
+    /// 南端のX座標
+    pub fn south(&self) -> f64 {
+        self.north - self.rows as f64 * self.y_res
+    }
+
+    /// 東端のY座標
+    pub fn east(&self) -> f64 {
+        self.west + self.cols as f64 * self.x_res
+    }
+
+    fn check_index(&self, row: usize, col: usize) -> Result<usize, LandXMLError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(LandXMLError::InvalidGridIndex {
+                row,
+                col,
+                max_row: self.rows.saturating_sub(1),
+                max_col: self.cols.saturating_sub(1),
+            });
+        }
+        Ok(row * self.cols + col)
+    }
+
+    /// セルの標高を取得
+    pub fn value(&self, row: usize, col: usize) -> Result<Option<f64>, LandXMLError> {
+        Ok(self.values[self.check_index(row, col)?])
+    }
+
+    /// セルの標高を設定
+    pub fn set_value(
+        &mut self,
+        row: usize,
+        col: usize,
+        value: Option<f64>,
+    ) -> Result<(), LandXMLError> {
+        let i = self.check_index(row, col)?;
+        self.values[i] = value;
+        Ok(())
+    }
+
+    /// セル中心の座標（X北, Y東）
+    pub fn cell_center(&self, row: usize, col: usize) -> Point2D {
+        gis_to_survey(geo::Coord {
+            x: self.west + (col as f64 + 0.5) * self.x_res,
+            y: self.north - (row as f64 + 0.5) * self.y_res,
+        })
+    }
+
+    /// 欠測でないセルの数
+    pub fn valid_count(&self) -> usize {
+        self.values.iter().filter(|v| v.is_some()).count()
+    }
+
+    /// 欠測を除いた標高の最小値・最大値
+    pub fn elevation_range(&self) -> Option<(f64, f64)> {
+        self.values
+            .iter()
+            .flatten()
+            .fold(None, |range, &z| match range {
+                None => Some((z, z)),
+                Some((lo, hi)) => Some((f64::min(lo, z), f64::max(hi, z))),
+            })
+    }
+
+    /// DEMファイルを読み込み
+    ///
+    /// 形式は内容から判定します（GeoTIFF、基盤地図情報のXML、ESRI ASCII grid）。
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LandXMLError> {
+        let bytes = std::fs::read(path)?;
+        if bytes.starts_with(b"II") || bytes.starts_with(b"MM") {
+            return Self::from_geotiff(&bytes);
+        }
+        let (content, _) = decode_document(&bytes)?;
+        if content.trim_start().starts_with('<') {
+            Self::from_gsi_xml_str(&content)
+        } else {
+            Self::from_ascii_grid_str(&content)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_access() {
+        let mut grid = DemGrid::new(2, 3, 100.0, 50.0, 1.0, 2.0).unwrap();
+        assert_eq!(grid.south(), 96.0);
+        assert_eq!(grid.east(), 53.0);
+        assert_eq!(grid.cell_center(1, 2), Point2D { x: 97.0, y: 52.5 });
+
+        grid.set_value(1, 2, Some(12.5)).unwrap();
+        assert_eq!(grid.value(1, 2).unwrap(), Some(12.5));
+        assert_eq!(grid.valid_count(), 1);
+        assert_eq!(grid.elevation_range(), Some((12.5, 12.5)));
+        assert!(matches!(
+            grid.value(2, 0),
+            Err(LandXMLError::InvalidGridIndex { max_row: 1, .. })
+        ));
+
+        assert!(matches!(
+            DemGrid::new(2, 2, 0.0, 0.0, 0.0, 1.0),
+            Err(LandXMLError::InvalidResolution { .. })
+        ));
+        assert!(matches!(
+            DemGrid::from_values(2, 2, 0.0, 0.0, 1.0, 1.0, vec![None; 3]),
+            Err(LandXMLError::InvalidGridSize {
+                expected: 4,
+                actual: 3
+            })
+        ));
+        for (rows, cols) in [(usize::MAX, 2), (20_000, 20_000)] {
+            assert!(matches!(
+                DemGrid::new(rows, cols, 0.0, 0.0, 1.0, 1.0),
+                Err(LandXMLError::InvalidGridSize {
+                    expected: MAX_GRID_CELLS,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let grid = DemGrid::from_values(1, 2, 10.0, 20.0, 1.0, 1.0, vec![Some(1.5), None])
+            .unwrap()
+            .with_zone(JapanPlaneCoordinateSystem::Zone9);
+
+        let tiff = dir.path().join("dem.tif");
+        grid.write_geotiff_to_file(&tiff).unwrap();
+        let read = DemGrid::from_file(&tiff).unwrap();
+        assert_eq!(read.zone, Some(JapanPlaneCoordinateSystem::Zone9));
+        assert_eq!(read.values, grid.values);
+
+        let ascii = dir.path().join("dem.asc");
+        grid.write_ascii_grid_to_file(&ascii).unwrap();
+        let read = DemGrid::from_file(&ascii).unwrap();
+        assert_eq!((read.north, read.west), (10.0, 20.0));
+        assert_eq!(read.values, grid.values);
+
+        let gsi = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/gsi_dem5a_sample.xml");
+        assert!(DemGrid::from_file(gsi).unwrap().geographic);
+    }
+}
@@ -0,0 +1,185 @@
+//! TIN rasterization
+//!
+//! 三角網（TIN）から標高格子を生成します：
+//! - 指定した解像度・範囲のセル中心の標高を三角形内で線形補間
+//! - 三角網の外側のセルは欠測
+//! - 範囲を省略した場合は三角網の外接矩形を解像度の整数倍に揃えた範囲
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::DemGrid;
+use crate::coordinate_system::JapanPlaneCoordinateSystem;
+use crate::error::LandXMLError;
+use crate::models::Point2D;
+use crate::surfaces::Surface;
+use crate::units::survey_to_gis;
+
+/// 格子化の条件
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RasterOptions {
+    /// セルの東西方向の幅（m）
+    pub x_res: f64,
+    /// セルの南北方向の高さ（m）
+    pub y_res: f64,
+    /// 範囲（最小点, 最大点）。Noneの場合は三角網の外接矩形
+    pub extent: Option<(Point2D, Point2D)>,
+    /// 平面直角座標系
+    pub zone: Option<JapanPlaneCoordinateSystem>,
+}
+
+impl RasterOptions {
+    /// 正方形のセルの大きさを指定して作成
+    pub fn new(resolution: f64) -> Self {
+        Self {
+            x_res: resolution,
+            y_res: resolution,
+            extent: None,
+            zone: None,
+        }
+    }
+
+    /// 東西・南北のセルの大きさを設定
+    pub fn with_resolution(mut self, x_res: f64, y_res: f64) -> Self {
+        self.x_res = x_res;
+        self.y_res = y_res;
+        self
+    }
+
+    /// 範囲（X北・Y東の最小点, 最大点）を設定
+    pub fn with_extent(mut self, min: Point2D, max: Point2D) -> Self {
+        self.extent = Some((min, max));
+        self
+    }
+
+    /// 平面直角座標系を設定
+    pub fn with_zone(mut self, zone: JapanPlaneCoordinateSystem) -> Self {
+        self.zone = Some(zone);
+        self
+    }
+}
+
+impl Surface {
+    /// 標高格子（DEM）を生成
+    ///
+    /// 各セルの値はセル中心の標高です。範囲が三角網より広い場合、
+    /// 三角網の外側のセルは欠測となります。
+    pub fn rasterize(&self, options: &RasterOptions) -> Result<DemGrid, LandXMLError> {
+        let RasterOptions { x_res, y_res, .. } = *options;
+        if !(x_res.is_finite() && x_res > 0.0 && y_res.is_finite() && y_res > 0.0) {
+            return Err(LandXMLError::InvalidResolution { x_res, y_res });
+        }
+        let index = self.index();
+        // 範囲はGIS座標（x東・y北）の南西隅・北東隅で扱う
+        let (south_west, north_east) = match options.extent {
+            Some((min, max)) => (survey_to_gis(min), survey_to_gis(max)),
+            None => {
+                let (min, max) = index
+                    .bounds()
+                    .ok_or(LandXMLError::MissingSurfaceDefinition)?;
+                let (min, max) = (survey_to_gis(min), survey_to_gis(max));
+                (
+                    geo::Coord {
+                        x: (min.x / x_res).floor() * x_res,
+                        y: (min.y / y_res).floor() * y_res,
+                    },
+                    geo::Coord {
+                        x: (max.x / x_res).ceil() * x_res,
+                        y: (max.y / y_res).ceil() * y_res,
+                    },
+                )
+            }
+        };
+        if !(north_east.x > south_west.x && north_east.y > south_west.y) {
+            return Err(LandXMLError::GeometryError {
+                message: format!(
+                    "Invalid raster extent: ({}, {}) - ({}, {})",
+                    south_west.x, south_west.y, north_east.x, north_east.y
+                ),
+            });
+        }
+
+        // 範囲の端数は1セルに切り上げる（浮動小数点の誤差分は切り捨て）
+        let count = |length: f64, res: f64| ((length / res - 1e-9).ceil() as usize).max(1);
+        let rows = count(north_east.y - south_west.y, y_res);
+        let cols = count(north_east.x - south_west.x, x_res);
+        let mut grid = DemGrid::new(rows, cols, north_east.y, south_west.x, x_res, y_res)?;
+        grid.zone = options.zone;
+
+        let centers: Vec<Point2D> = (0..rows)
+            .into_par_iter()
+            .flat_map_iter(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| grid.cell_center(row, col))
+            .collect();
+        grid.values = index.elevations_at(&centers);
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Point3D;
+    use crate::surfaces::TinBuilder;
+
+    #[test]
+    fn test_rasterize() {
+        // 平面 z = X + 2Y の三角形（X: 0～10, Y: 0～10, X + Y ≤ 10）
+        let surface = TinBuilder::new()
+            .with_points([
+                Point3D {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                Point3D {
+                    x: 10.0,
+                    y: 0.0,
+                    z: 10.0,
+                },
+                Point3D {
+                    x: 0.0,
+                    y: 10.0,
+                    z: 20.0,
+                },
+            ])
+            .build()
+            .unwrap();
+
+        let grid = surface.rasterize(&RasterOptions::new(2.0)).unwrap();
+        assert_eq!((grid.rows, grid.cols), (5, 5));
+        assert_eq!((grid.north, grid.west), (10.0, 0.0));
+        // 南西のセル（中心 X=1, Y=1）
+        assert!((grid.value(4, 0).unwrap().unwrap() - 3.0).abs() < 1e-9);
+        // 北東のセルは三角網の外側
+        assert_eq!(grid.value(0, 4).unwrap(), None);
+        assert_eq!(grid.valid_count(), 15);
+
+        let grid = surface
+            .rasterize(
+                &RasterOptions::new(1.0)
+                    .with_resolution(2.5, 1.0)
+                    .with_extent(Point2D { x: -5.0, y: 0.0 }, Point2D { x: 5.0, y: 5.0 })
+                    .with_zone(JapanPlaneCoordinateSystem::Zone9),
+            )
+            .unwrap();
+        assert_eq!((grid.rows, grid.cols), (10, 2));
+        assert_eq!(grid.zone, Some(JapanPlaneCoordinateSystem::Zone9));
+        assert_eq!(grid.value(9, 0).unwrap(), None);
+        assert!((grid.value(4, 1).unwrap().unwrap() - 8.0).abs() < 1e-9);
+
+        assert!(matches!(
+            surface.rasterize(&RasterOptions::new(-1.0)),
+            Err(LandXMLError::InvalidResolution { .. })
+        ));
+        assert!(matches!(
+            Surface::default().rasterize(&RasterOptions::new(1.0)),
+            Err(LandXMLError::MissingSurfaceDefinition)
+        ));
+        // 範囲に対して細かすぎる解像度は確保の前にエラー
+        assert!(matches!(
+            surface.rasterize(&RasterOptions::new(1e-6)),
+            Err(LandXMLError::InvalidGridSize { .. })
+        ));
+    }
+}
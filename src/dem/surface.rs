@@ -0,0 +1,305 @@
+//! DEM to TIN conversion
+//!
+//! 標高格子から三角網（TIN）を生成します：
+//! - 緯度経度の格子、他の系の平面直角座標の格子を指定した系へ投影
+//! - 座標系が判定できない格子・測地原子が異なる格子は変換しない（明示した場合のみ変換先の測地原子の座標として扱う）
+//! - 一定の行・列ごとのセル中心による間引き（外周の行・列は保持）
+//! - 格子の各セルを対角線（北西－南東）で2つの三角形に分割
+//! - 欠測を含むセルは有効な3点の三角形のみ（欠測の領域をまたぐ三角形は作らない）
+
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::DemGrid;
+use crate::coordinate_system::{HorizontalDatum, JapanPlaneCoordinateSystem};
+use crate::error::LandXMLError;
+use crate::models::Point3D;
+use crate::surfaces::{Surface, Triangle};
+
+/// 三角網への変換条件
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DemSurfaceOptions {
+    /// 面の名称
+    pub name: Option<String>,
+    /// 間引きの間隔（行・列数、0と1は間引きなし）
+    pub step: usize,
+    /// 変換先の水平測地原子（Noneは日本測地系2011）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizontal_datum: Option<HorizontalDatum>,
+    /// 座標系が判定できない格子・測地原子が異なる格子も変換先の測地原子の座標として扱う
+    #[serde(default)]
+    pub assume_crs: bool,
+}
+
+impl DemSurfaceOptions {
+    /// 間引きなしの条件で作成
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            step: 1,
+            horizontal_datum: None,
+            assume_crs: false,
+        }
+    }
+
+    /// 面の名称を設定
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 間引きの間隔を設定
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// 変換先の水平測地原子を設定
+    pub fn with_horizontal_datum(mut self, datum: HorizontalDatum) -> Self {
+        self.horizontal_datum = Some(datum);
+        self
+    }
+
+    /// 座標系が判定できない格子・測地原子が異なる格子を変換先の測地原子の座標として扱うかを設定
+    pub fn with_assume_crs(mut self, assume: bool) -> Self {
+        self.assume_crs = assume;
+        self
+    }
+}
+
+/// 間引き後に使用する行・列の番号（最後の行・列を含む）
+fn sampled(count: usize, step: usize) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..count).step_by(step.max(1)).collect();
+    if count > 0 && indices.last() != Some(&(count - 1)) {
+        indices.push(count - 1);
+    }
+    indices
+}
+
+impl DemGrid {
+    /// 指定した平面直角座標系の三角網に変換
+    ///
+    /// 緯度経度の格子は投影し、平面直角座標系が異なる格子は緯度経度を経由して
+    /// 変換します。座標系の情報が無い平面の格子は指定した系の座標として扱います。
+    ///
+    /// 読み込んだEPSGコードが日本の座標系でない格子（UTM等）、測地原子が変換先と
+    /// 異なる格子（旧日本測地系等）はエラーとします。
+    /// [`DemSurfaceOptions::assume_crs`]を指定した場合は変換先の測地原子の座標として扱います。
+    pub fn to_surface(
+        &self,
+        zone: JapanPlaneCoordinateSystem,
+        options: &DemSurfaceOptions,
+    ) -> Result<Surface, LandXMLError> {
+        if !options.assume_crs {
+            self.check_crs(options.horizontal_datum.unwrap_or(HorizontalDatum::JGD2011))?;
+        }
+        let rows = sampled(self.rows, options.step);
+        let cols = sampled(self.cols, options.step);
+
+        let samples: Vec<Option<Point3D>> = (0..rows.len() * cols.len())
+            .into_par_iter()
+            .map(|i| {
+                let (row, col) = (rows[i / cols.len()], cols[i % cols.len()]);
+                let z = self.values[row * self.cols + col]?;
+                let center = self.cell_center(row, col);
+                let (x, y) = if self.geographic {
+                    zone.from_geographic(center.x, center.y)
+                } else {
+                    match self.zone {
+                        Some(source) if source != zone => {
+                            let (lat, lon) = source.to_geographic(center.x, center.y);
+                            zone.from_geographic(lat, lon)
+                        }
+                        _ => (center.x, center.y),
+                    }
+                };
+                Some(Point3D { x, y, z })
+            })
+            .collect();
+
+        let mut surface = Surface {
+            name: options.name.clone(),
+            ..Default::default()
+        };
+        let indices: Vec<Option<usize>> = samples
+            .into_iter()
+            .map(|sample| {
+                sample.map(|p| {
+                    surface.points.push(p);
+                    surface.points.len() - 1
+                })
+            })
+            .collect();
+        if surface.points.is_empty() {
+            return Err(LandXMLError::EmptyPointCloud);
+        }
+
+        let at = |i: usize, j: usize| indices[i * cols.len() + j];
+        for i in 0..rows.len().saturating_sub(1) {
+            for j in 0..cols.len().saturating_sub(1) {
+                // 北西・北東・南東・南西の順は測量座標（X北・Y東）で正の向き
+                let corners = [at(i, j), at(i, j + 1), at(i + 1, j + 1), at(i + 1, j)];
+                match corners {
+                    [Some(nw), Some(ne), Some(se), Some(sw)] => {
+                        surface.triangles.push(Triangle::new([nw, ne, se]));
+                        surface.triangles.push(Triangle::new([nw, se, sw]));
+                    }
+                    _ => {
+                        let valid: Vec<usize> = corners.iter().flatten().copied().collect();
+                        if let [a, b, c] = valid[..] {
+                            surface.triangles.push(Triangle::new([a, b, c]));
+                        }
+                    }
+                }
+            }
+        }
+        if surface.triangles.is_empty() {
+            return Err(LandXMLError::MissingSurfaceDefinition);
+        }
+        Ok(surface)
+    }
+
+    /// 変換先の測地原子で扱える座標系かを確認
+    fn check_crs(&self, datum: HorizontalDatum) -> Result<(), LandXMLError> {
+        match (self.horizontal_datum, self.epsg_code) {
+            (Some(source), _) if source != datum => {
+                Err(LandXMLError::InvalidCoordinateSystem(format!(
+                    "DEM horizontal datum {} differs from {}",
+                    source.as_str(),
+                    datum.as_str()
+                )))
+            }
+            (None, Some(code)) => Err(LandXMLError::InvalidCoordinateSystem(format!(
+                "Unsupported DEM coordinate system: EPSG:{}",
+                code
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Surface {
+    /// DEMファイルを読み込み、指定した平面直角座標系の三角網に変換
+    ///
+    /// ファイル形式は内容から判定します（[`DemGrid::from_file`]）。
+    pub fn from_dem_file<P: AsRef<Path>>(
+        path: P,
+        zone: JapanPlaneCoordinateSystem,
+        options: &DemSurfaceOptions,
+    ) -> Result<Self, LandXMLError> {
+        DemGrid::from_file(path)?.to_surface(zone, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate_system::PlaneCrs;
+
+    #[test]
+    fn test_gsi_to_surface() {
+        let grid = DemGrid::from_gsi_xml_file(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/gsi_dem5a_sample.xml"),
+        )
+        .unwrap();
+        let zone = JapanPlaneCoordinateSystem::Zone9;
+        let surface = grid
+            .to_surface(zone, &DemSurfaceOptions::new().with_name("53394611"))
+            .unwrap();
+        assert_eq!(surface.name.as_deref(), Some("53394611"));
+        assert_eq!(surface.points.len(), 6);
+        // 欠測を含むセルは3点の三角形のみ、2点以下のセルは三角形なし
+        assert_eq!(surface.triangles.len(), 4);
+
+        // 北東隅のセル中心（緯度36.00025°、経度139.00025°）を9系に投影
+        let (x, y) = zone.from_geographic(36.00025, 139.00025);
+        let p = surface.points[1];
+        assert!((p.x - x).abs() < 1e-6 && (p.y - y).abs() < 1e-6);
+        assert_eq!(p.z, 33.25);
+        assert!(surface.area_2d() > 0.0);
+
+        // 2行・列ごとに間引くと四隅のみ（北西・南東が欠測）
+        assert!(matches!(
+            grid.to_surface(zone, &DemSurfaceOptions::new().with_step(2)),
+            Err(LandXMLError::MissingSurfaceDefinition)
+        ));
+    }
+
+    #[test]
+    fn test_plane_grid_to_surface() {
+        let values = (0..25).map(|i| Some(i as f64)).collect();
+        let grid = DemGrid::from_values(5, 5, 1000.0, -2000.0, 10.0, 10.0, values)
+            .unwrap()
+            .with_zone(JapanPlaneCoordinateSystem::Zone9);
+
+        let surface = grid
+            .to_surface(JapanPlaneCoordinateSystem::Zone9, &DemSurfaceOptions::new())
+            .unwrap();
+        assert_eq!(surface.triangles.len(), 32);
+        assert!((surface.area_2d() - 1600.0).abs() < 1e-6);
+        // セル中心の標高を再現
+        let z = surface.elevation_at(1000.0 - 15.0, -2000.0 + 25.0).unwrap();
+        assert!((z - 7.0).abs() < 1e-9);
+
+        // 3行・列ごとに間引いても外周は保持（0, 3, 4行・列）
+        let decimated = grid
+            .to_surface(
+                JapanPlaneCoordinateSystem::Zone9,
+                &DemSurfaceOptions::new().with_step(3),
+            )
+            .unwrap();
+        assert_eq!(decimated.points.len(), 9);
+        assert!((decimated.area_2d() - 1600.0).abs() < 1e-6);
+
+        // 隣接する系へは緯度経度を経由して変換
+        let other = grid
+            .to_surface(
+                JapanPlaneCoordinateSystem::Zone10,
+                &DemSurfaceOptions::new(),
+            )
+            .unwrap();
+        let (lat, lon) = JapanPlaneCoordinateSystem::Zone9.to_geographic(995.0, -1995.0);
+        let (x, y) = JapanPlaneCoordinateSystem::Zone10.from_geographic(lat, lon);
+        assert!((other.points[0].x - x).abs() < 1e-6 && (other.points[0].y - y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_crs_check() {
+        let zone = JapanPlaneCoordinateSystem::Zone9;
+        let values = (0..4).map(|i| Some(i as f64)).collect();
+        let grid = DemGrid::from_values(2, 2, 1000.0, -2000.0, 10.0, 10.0, values).unwrap();
+
+        // 座標系が判定できない格子（UTM 54N）は明示した場合のみ変換
+        let mut utm = grid.clone();
+        utm.epsg_code = Some(32654);
+        assert!(matches!(
+            utm.to_surface(zone, &DemSurfaceOptions::new()),
+            Err(LandXMLError::InvalidCoordinateSystem(_))
+        ));
+        let assumed = utm
+            .to_surface(zone, &DemSurfaceOptions::new().with_assume_crs(true))
+            .unwrap();
+        assert_eq!((assumed.points[0].x, assumed.points[0].y), (995.0, -1995.0));
+
+        // 旧日本測地系の格子は変換先の測地原子が一致する場合のみ変換
+        let tokyo = grid.with_crs(PlaneCrs {
+            zone,
+            horizontal_datum: HorizontalDatum::TD,
+        });
+        assert!(matches!(
+            tokyo.to_surface(zone, &DemSurfaceOptions::new()),
+            Err(LandXMLError::InvalidCoordinateSystem(_))
+        ));
+        assert!(tokyo
+            .to_surface(
+                zone,
+                &DemSurfaceOptions::new().with_horizontal_datum(HorizontalDatum::TD)
+            )
+            .is_ok());
+        assert!(tokyo
+            .to_surface(zone, &DemSurfaceOptions::new().with_assume_crs(true))
+            .is_ok());
+    }
+}
@@ -0,0 +1,523 @@
+//! Geoid module for J-LandXML parser
+//!
+//! This module handles geoid models and height conversion:
+//! - GSI geoid grid files (日本のジオイド2011 / ジオイド2024 ASCII形式)
+//! - Bilinear interpolation of geoid height
+//! - Ellipsoidal height ↔ T.P orthometric height conversion
+//!
+//! ジオイド高 N、楕円体高 h、標高 H の関係は H = h - N です。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cg_points::CgPoints;
+use crate::coordinate_system::{JLandXmlCoordinateSystem, JapanPlaneCoordinateSystem};
+use crate::error::LandXMLError;
+use crate::surfaces::Surface;
+
+/// GSI形式で欠測を表す値
+const GSI_MISSING_VALUE: f64 = 999.0;
+
+/// 格子点数の上限（日本のジオイド2011の1801×1201を十分に含む）
+const MAX_GEOID_CELLS: usize = 16_000_000;
+
+/// 格子間隔を1/n度に丸める際の許容誤差（相対）
+const STEP_SNAP_TOLERANCE: f64 = 1e-3;
+
+/// ヘッダーの格子間隔（例: 0.016667）を最も近い1/n度（例: 1/60度）に丸める
+///
+/// 丸めないと格子点の位置が東端・北端に向かってずれ、補間値が偏ります。
+fn snap_step(step: f64) -> f64 {
+    let n = (1.0 / step).round();
+    if n >= 1.0 && ((1.0 / n) - step).abs() <= step * STEP_SNAP_TOLERANCE {
+        1.0 / n
+    } else {
+        step
+    }
+}
+
+/// Height conversion direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HeightConversion {
+    /// 楕円体高（GNSS観測値）→ 標高（T.P）
+    EllipsoidalToOrthometric,
+    /// 標高（T.P）→ 楕円体高
+    OrthometricToEllipsoidal,
+}
+
+/// Geoid grid model
+///
+/// Reference: 国土地理院 ジオイド・モデル ASCII形式
+/// (ヘッダー: 南端緯度 西端経度 緯度間隔 経度間隔 緯度方向格子数 経度方向格子数 フォーマット識別子 バージョン)
+#[derive(Debug, Clone)]
+pub struct GeoidModel {
+    /// Model name (e.g. "日本のジオイド2011")
+    pub name: String,
+    /// Model version string from the header
+    pub version: Option<String>,
+    /// Southern edge latitude (degrees)
+    pub lat_min: f64,
+    /// Western edge longitude (degrees)
+    pub lon_min: f64,
+    /// Latitude spacing (degrees)
+    pub lat_step: f64,
+    /// Longitude spacing (degrees)
+    pub lon_step: f64,
+    /// Number of grid rows (latitude direction)
+    pub rows: usize,
+    /// Number of grid columns (longitude direction)
+    pub cols: usize,
+    /// Geoid heights in meters, row-major from south-west (None = no data)
+    values: Vec<Option<f64>>,
+}
+
+impl GeoidModel {
+    /// GSI ASCII形式のファイルから読み込み
+    pub fn from_gsi_ascii_file<P: AsRef<Path>>(
+        name: impl Into<String>,
+        path: P,
+    ) -> Result<Self, LandXMLError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_gsi_ascii_str(name, &content)
+    }
+
+    /// GSI ASCII形式の文字列から読み込み
+    pub fn from_gsi_ascii_str(
+        name: impl Into<String>,
+        content: &str,
+    ) -> Result<Self, LandXMLError> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let header = lines.next().ok_or_else(|| LandXMLError::InvalidFormat {
+            message: "Empty geoid grid file".to_string(),
+        })?;
+
+        let fields: Vec<&str> = header.split_whitespace().collect();
+        if fields.len() < 6 {
+            return Err(LandXMLError::InvalidFormat {
+                message: format!("Invalid geoid grid header: {}", header.trim()),
+            });
+        }
+        let parse_f64 = |s: &str| {
+            s.parse::<f64>().map_err(|e| LandXMLError::InvalidFormat {
+                message: format!("Invalid geoid grid header value '{}': {}", s, e),
+            })
+        };
+        let parse_usize = |s: &str| {
+            s.parse::<usize>().map_err(|e| LandXMLError::InvalidFormat {
+                message: format!("Invalid geoid grid header value '{}': {}", s, e),
+            })
+        };
+
+        let lat_min = parse_f64(fields[0])?;
+        let lon_min = parse_f64(fields[1])?;
+        let lat_step = snap_step(parse_f64(fields[2])?);
+        let lon_step = snap_step(parse_f64(fields[3])?);
+        let rows = parse_usize(fields[4])?;
+        let cols = parse_usize(fields[5])?;
+        let version = fields.get(7).map(|s| s.to_string());
+
+        if lat_step <= 0.0 || lon_step <= 0.0 {
+            return Err(LandXMLError::InvalidResolution {
+                x_res: lon_step,
+                y_res: lat_step,
+            });
+        }
+
+        // 格子点数はヘッダーの値を信頼せず、上限とデータの量で確保を制限する
+        let cells = rows
+            .checked_mul(cols)
+            .filter(|&cells| cells <= MAX_GEOID_CELLS)
+            .ok_or_else(|| LandXMLError::InvalidFormat {
+                message: format!("Geoid grid is too large: {} x {}", rows, cols),
+            })?;
+        let mut values = Vec::with_capacity(cells.min(content.len() / 2));
+        for line in lines {
+            for token in line.split_whitespace() {
+                let value = parse_f64(token)?;
+                values.push(if value >= GSI_MISSING_VALUE {
+                    None
+                } else {
+                    Some(value)
+                });
+            }
+        }
+
+        if values.len() != cells {
+            return Err(LandXMLError::InvalidGridSize {
+                expected: cells,
+                actual: values.len(),
+            });
+        }
+
+        Ok(Self {
+            name: name.into(),
+            version,
+            lat_min,
+            lon_min,
+            lat_step,
+            lon_step,
+            rows,
+            cols,
+            values,
+        })
+    }
+
+    /// 格子点のジオイド高を取得
+    pub fn grid_value(&self, row: usize, col: usize) -> Result<Option<f64>, LandXMLError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(LandXMLError::InvalidGridIndex {
+                row,
+                col,
+                max_row: self.rows.saturating_sub(1),
+                max_col: self.cols.saturating_sub(1),
+            });
+        }
+        Ok(self.values[row * self.cols + col])
+    }
+
+    /// 緯度・経度（度）におけるジオイド高を双一次補間で取得
+    ///
+    /// 範囲外、または周囲4格子点のいずれかが欠測の場合はNone
+    pub fn geoid_height(&self, lat: f64, lon: f64) -> Option<f64> {
+        let fy = (lat - self.lat_min) / self.lat_step;
+        let fx = (lon - self.lon_min) / self.lon_step;
+        if fy < 0.0 || fx < 0.0 {
+            return None;
+        }
+
+        let max_row = self.rows.checked_sub(1)? as f64;
+        let max_col = self.cols.checked_sub(1)? as f64;
+        if fy > max_row || fx > max_col {
+            return None;
+        }
+
+        // 北端・東端の格子線上は一つ内側のセルで補間する
+        let row = (fy.floor() as usize).min(self.rows.saturating_sub(2));
+        let col = (fx.floor() as usize).min(self.cols.saturating_sub(2));
+        let ty = fy - row as f64;
+        let tx = fx - col as f64;
+
+        let at = |r: usize, c: usize| self.values.get(r * self.cols + c).copied().flatten();
+        let (r1, c1) = ((row + 1).min(self.rows - 1), (col + 1).min(self.cols - 1));
+        let sw = at(row, col)?;
+        let se = at(row, c1)?;
+        let nw = at(r1, col)?;
+        let ne = at(r1, c1)?;
+
+        Some(
+            sw * (1.0 - tx) * (1.0 - ty)
+                + se * tx * (1.0 - ty)
+                + nw * (1.0 - tx) * ty
+                + ne * tx * ty,
+        )
+    }
+
+    /// 平面直角座標（X北, Y東）におけるジオイド高を取得
+    pub fn geoid_height_at_plane(
+        &self,
+        zone: JapanPlaneCoordinateSystem,
+        x: f64,
+        y: f64,
+    ) -> Option<f64> {
+        let (lat, lon) = zone.to_geographic(x, y);
+        self.geoid_height(lat, lon)
+    }
+
+    /// 楕円体高から標高（T.P）へ変換
+    pub fn to_orthometric_height(&self, lat: f64, lon: f64, ellipsoidal: f64) -> Option<f64> {
+        self.geoid_height(lat, lon).map(|n| ellipsoidal - n)
+    }
+
+    /// 標高（T.P）から楕円体高へ変換
+    pub fn to_ellipsoidal_height(&self, lat: f64, lon: f64, orthometric: f64) -> Option<f64> {
+        self.geoid_height(lat, lon).map(|n| orthometric + n)
+    }
+
+    /// 平面直角座標上の点の高さを変換
+    pub fn convert_height_at_plane(
+        &self,
+        zone: JapanPlaneCoordinateSystem,
+        x: f64,
+        y: f64,
+        z: f64,
+        conversion: HeightConversion,
+    ) -> Result<f64, LandXMLError> {
+        let n =
+            self.geoid_height_at_plane(zone, x, y)
+                .ok_or_else(|| LandXMLError::GeometryError {
+                    message: format!(
+                        "Point ({:.3}, {:.3}) is outside the geoid model '{}'",
+                        x, y, self.name
+                    ),
+                })?;
+        Ok(match conversion {
+            HeightConversion::EllipsoidalToOrthometric => z - n,
+            HeightConversion::OrthometricToEllipsoidal => z + n,
+        })
+    }
+
+    /// CgPointsの全点の高さを変換
+    ///
+    /// 標高を持たない点は対象外です。
+    /// 1点でもモデル範囲外の場合はエラーとなり、点群は変更されません。
+    pub fn convert_cg_points(
+        &self,
+        cg_points: &mut CgPoints,
+        zone: JapanPlaneCoordinateSystem,
+        conversion: HeightConversion,
+    ) -> Result<(), LandXMLError> {
+        let heights = cg_points
+            .points
+            .iter()
+            .map(|p| {
+                let pos = &p.position;
+                if p.has_elevation {
+                    self.convert_height_at_plane(zone, pos.x, pos.y, pos.z, conversion)
+                } else {
+                    Ok(pos.z)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (point, z) in cg_points.points.iter_mut().zip(heights) {
+            point.position.z = z;
+        }
+        Ok(())
+    }
+
+    /// Surfaceの全点の高さを変換
+    ///
+    /// 1点でもモデル範囲外の場合はエラーとなり、サーフェスは変更されません。
+    pub fn convert_surface(
+        &self,
+        surface: &mut Surface,
+        zone: JapanPlaneCoordinateSystem,
+        conversion: HeightConversion,
+    ) -> Result<(), LandXMLError> {
+        let heights = surface
+            .points
+            .iter()
+            .map(|p| self.convert_height_at_plane(zone, p.x, p.y, p.z, conversion))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (point, z) in surface.points.iter_mut().zip(heights) {
+            point.z = z;
+        }
+        Ok(())
+    }
+}
+
+/// Registry of geoid models selected by `geoidName`
+#[derive(Debug, Clone, Default)]
+pub struct GeoidRegistry {
+    models: HashMap<String, GeoidModel>,
+}
+
+impl GeoidRegistry {
+    /// 空のレジストリを作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// モデルを登録（同名のモデルは置き換え）
+    pub fn register(&mut self, model: GeoidModel) {
+        self.models.insert(Self::normalize_name(&model.name), model);
+    }
+
+    /// モデル名から取得
+    pub fn get(&self, name: &str) -> Option<&GeoidModel> {
+        self.models.get(&Self::normalize_name(name))
+    }
+
+    /// 座標系のgeoidNameに対応するモデルを取得
+    pub fn for_coordinate_system(
+        &self,
+        cs: &JLandXmlCoordinateSystem,
+    ) -> Result<&GeoidModel, LandXMLError> {
+        let name = cs
+            .geoid_name
+            .as_deref()
+            .ok_or_else(|| LandXMLError::MissingElement {
+                element: "CoordinateSystem@geoidName".to_string(),
+            })?;
+        self.get(name).ok_or_else(|| {
+            LandXMLError::InvalidCoordinateSystem(format!(
+                "No geoid model registered for geoidName: {}",
+                name
+            ))
+        })
+    }
+
+    /// 登録済みモデル数
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// レジストリが空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    /// モデル名を照合用に正規化（既知の別名は代表名に寄せる）
+    fn normalize_name(name: &str) -> String {
+        let key: String = name
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect();
+        match key.as_str() {
+            "日本のジオイド2011" | "ジオイド2011" | "gsigeo2011" | "geoid2011" => {
+                "gsigeo2011".to_string()
+            }
+            "日本のジオイド2000" | "ジオイド2000" | "gsigeo2000" | "geoid2000" => {
+                "gsigeo2000".to_string()
+            }
+            "ジオイド2024" | "日本のジオイド2024" | "jpgeo2024" | "geoid2024" => {
+                "jpgeo2024".to_string()
+            }
+            _ => key,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cg_points::CgPoint;
+    use crate::models::Point3D;
+
+    // 36°～36°02'、139°48'～139°51'の3×3格子
+    const SAMPLE_GRID: &str = "36.00000 139.80000 0.016667 0.025000 3 3 1 ver2.1
+  40.0000  40.1000  40.2000
+  40.3000  40.4000  40.5000
+  40.6000  40.7000 999.0000
+";
+
+    fn sample_model() -> GeoidModel {
+        GeoidModel::from_gsi_ascii_str("日本のジオイド2011", SAMPLE_GRID).unwrap()
+    }
+
+    #[test]
+    fn test_parse_gsi_ascii() {
+        let model = sample_model();
+        assert_eq!(model.rows, 3);
+        assert_eq!(model.cols, 3);
+        assert_eq!(model.version, Some("ver2.1".to_string()));
+        // ヘッダーの0.016667は1/60度に丸める
+        assert_eq!(model.lat_step, 1.0 / 60.0);
+        assert_eq!(model.lon_step, 0.025);
+        assert_eq!(model.grid_value(0, 0).unwrap(), Some(40.0));
+        assert_eq!(model.grid_value(2, 2).unwrap(), None);
+        assert!(model.grid_value(3, 0).is_err());
+
+        // 過大な格子点数のヘッダーは確保の前にエラー
+        for header in [
+            "20 120 0.0166667 0.025 300000 400000",
+            "20 120 0.0166667 0.025 18446744073709551615 2",
+        ] {
+            assert!(matches!(
+                GeoidModel::from_gsi_ascii_str("forged", header),
+                Err(LandXMLError::InvalidFormat { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_bilinear_interpolation() {
+        let model = sample_model();
+        let n = model
+            .geoid_height(36.0 + 1.0 / 120.0, 139.8 + 0.025 / 2.0)
+            .unwrap();
+        assert!((n - 40.2).abs() < 1e-9);
+        // 北端の格子線上（36°02'）は格子点の値と一致
+        let n = model.geoid_height(36.0 + 2.0 / 60.0, 139.8).unwrap();
+        assert!((n - 40.6).abs() < 1e-9);
+
+        // 欠測格子点を含むセル・範囲外
+        assert!(model.geoid_height(36.03, 139.84).is_none());
+        assert!(model.geoid_height(35.9, 139.81).is_none());
+    }
+
+    #[test]
+    fn test_height_conversion_round_trip() {
+        let model = sample_model();
+        let zone = JapanPlaneCoordinateSystem::Zone9;
+        let (x, y) = zone.from_geographic(36.005, 139.81);
+
+        let mut cg_points = CgPoints {
+            name: None,
+            points: vec![CgPoint {
+                name: Some("GNSS1".to_string()),
+                desc: None,
+                code: None,
+                position: Point3D { x, y, z: 100.0 },
+                has_elevation: true,
+                point_type: None,
+                extensions: Default::default(),
+            }],
+            ..Default::default()
+        };
+
+        model
+            .convert_cg_points(
+                &mut cg_points,
+                zone,
+                HeightConversion::EllipsoidalToOrthometric,
+            )
+            .unwrap();
+        let orthometric = cg_points.points[0].position.z;
+        assert!(orthometric < 60.0 && orthometric > 59.0);
+
+        model
+            .convert_cg_points(
+                &mut cg_points,
+                zone,
+                HeightConversion::OrthometricToEllipsoidal,
+            )
+            .unwrap();
+        assert!((cg_points.points[0].position.z - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_surface_outside_model_is_unchanged() {
+        let model = sample_model();
+        let mut surface = Surface {
+            points: vec![Point3D {
+                x: 0.0,
+                y: 0.0,
+                z: 10.0,
+            }],
+            ..Default::default()
+        };
+        let result = model.convert_surface(
+            &mut surface,
+            JapanPlaneCoordinateSystem::Zone1,
+            HeightConversion::EllipsoidalToOrthometric,
+        );
+        assert!(result.is_err());
+        assert_eq!(surface.points[0].z, 10.0);
+    }
+
+    #[test]
+    fn test_registry_selects_by_geoid_name() {
+        let mut registry = GeoidRegistry::new();
+        registry.register(sample_model());
+
+        let cs = JLandXmlCoordinateSystem::new(
+            "CRS1".to_string(),
+            crate::coordinate_system::HorizontalDatum::JGD2011,
+            crate::coordinate_system::VerticalDatum::TP,
+            "9(X,Y)".to_string(),
+        )
+        .unwrap()
+        .with_geoid_name("gsigeo2011".to_string());
+
+        assert_eq!(
+            registry.for_coordinate_system(&cs).unwrap().name,
+            "日本のジオイド2011"
+        );
+        assert!(registry.get("ジオイド2024").is_none());
+    }
+}
@@ -5,8 +5,12 @@
 
 // Core modules
 pub mod coordinate_system;
+pub mod dem;
 pub mod error;
+pub mod geoid;
 pub mod models;
+pub mod validation;
+pub mod writer;
 
 // LandXML element modules
 pub mod alignments;
@@ -17,6 +21,9 @@ pub mod roadways;
 pub mod surfaces;
 pub mod units;
 
+// Internal XML helpers
+mod xml;
+
 // 後方互換性のためのエイリアス
 pub use alignments as alignment;
 pub use coordinate_system as jlandxml;
@@ -31,8 +38,9 @@ pub use crate::models::*;
 pub use crate::coordinate_system::{
     CoordinateSystem, CoordinateSystemInfo, CoordinateSystemMapper, CoordinateSystemNameParser,
     CoordinateSystemValidator, HorizontalDatum, JLandXml, JLandXmlCoordinateSystem,
-    JLandXmlDocument, JLandXmlParser, JLandXmlProperty, JapanPlaneCoordinateSystem,
-    ParsingStats, ValidationWarning, VerticalDatum,
+    JLandXmlDocument, JLandXmlParser, JLandXmlProperty, JLandXmlVersion,
+    JapanPlaneCoordinateSystem, MigrationReport, ParsingStats, ValidationWarning, VersionMigrator,
+    VerticalDatum, VerticalDatumRegistry,
 };
 
 // Re-exports from alignments
@@ -40,8 +48,23 @@ pub use crate::alignments::{
     CoordGeom, Curve, GeomElement, Line, RotationDirection, Spiral, SpiralType,
 };
 
+// Re-exports from geoid
+pub use crate::geoid::{GeoidModel, GeoidRegistry, HeightConversion};
+
 // Re-exports from units
-pub use crate::units::{AngularUnit, LinearUnit, Units};
+pub use crate::units::{Angle, AngularUnit, AreaUnit, LinearUnit, Units, VolumeUnit};
 
 // Re-exports from surfaces
-pub use crate::surfaces::{Surface, Surfaces, Triangle};
+pub use crate::surfaces::{Surface, Surfaces, TinBuilder, TinIndex, Triangle};
+
+// Re-exports from dem
+pub use crate::dem::{DemGrid, DemSurfaceOptions, RasterOptions};
+
+// Re-exports from validation
+pub use crate::validation::{
+    DeliveryReport, DeliveryRuleChecker, RuleSeverity, SchemaViolation, SchemaViolationKind,
+    XsdValidator,
+};
+
+// Re-exports from writer
+pub use crate::writer::LandXmlWriter;
@@ -5,16 +5,55 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::alignments::{CoordGeom, CrossSects, Profile};
+use crate::application::Application;
+use crate::cg_points::CgPoints;
 use crate::coordinate_system::CoordinateSystem;
+use crate::error::LandXMLError;
+use crate::project::Project;
+use crate::roadways::Roadways;
+use crate::surfaces::Surfaces;
+use crate::units::Units;
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
 
 /// LandXMLドキュメントのルート構造体
-/// 今後Alignment中心に拡張予定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct LandXML {
     pub version: String,
+    /// 作成日（date属性、YYYY-MM-DD）
+    #[serde(default)]
+    pub date: Option<String>,
+    /// 作成時刻（time属性、hh:mm:ss）
+    #[serde(default)]
+    pub time: Option<String>,
+    /// Project要素
+    #[serde(default)]
+    pub project: Option<Project>,
+    /// Application要素
+    #[serde(default)]
+    pub application: Option<Application>,
+    /// Units要素
+    #[serde(default)]
+    pub units: Option<Units>,
     pub coordinate_system: Option<CoordinateSystem>,
     pub alignments: Vec<Alignment>,
     pub features: Vec<Feature>,
+    /// CgPoints要素（複数可）
+    #[serde(default)]
+    pub cg_points: Vec<CgPoints>,
+    /// Roadways要素（複数可）
+    #[serde(default)]
+    pub roadways: Vec<Roadways>,
+    /// Surfaces要素（複数可）
+    #[serde(default)]
+    pub surfaces: Vec<Surfaces>,
+    /// LandXML要素の未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+    /// Alignments要素の未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub alignments_extensions: Extensions,
 }
 
 /// 2次元座標点
@@ -34,11 +73,167 @@ pub struct Point3D {
 
 /// 線形（Alignment）
 /// CoordGeom, Profile, CrossSectionsの詳細はalignmentsモジュールで定義
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Alignment {
     pub name: String,
     pub desc: Option<String>,
     pub sta_start: Option<f64>,
+    /// 線形延長
+    #[serde(default)]
+    pub length: Option<f64>,
+    /// 平面線形
+    #[serde(default)]
+    pub coord_geom: Option<CoordGeom>,
+    /// 縦断線形
+    #[serde(default)]
+    pub profile: Option<Profile>,
+    /// 横断
+    #[serde(default)]
+    pub cross_sects: Option<CrossSects>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl Alignment {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        Ok(Self {
+            name: element.attr_string("name").unwrap_or_default(),
+            desc: element.attr_string("desc"),
+            sta_start: element.attr_f64("staStart"),
+            length: element.attr_f64("length"),
+            coord_geom: element
+                .child("CoordGeom")
+                .map(CoordGeom::from_xml)
+                .transpose()?,
+            profile: element
+                .child("Profile")
+                .map(Profile::from_xml)
+                .transpose()?,
+            cross_sects: element
+                .child("CrossSects")
+                .map(CrossSects::from_xml)
+                .transpose()?,
+            // 2つ目以降のCoordGeom・Profile・CrossSectsは未知要素として保持する
+            extensions: element.extensions_by(
+                &["name", "desc", "staStart", "length"],
+                |position, child| {
+                    matches!(child.name.as_str(), "CoordGeom" | "Profile" | "CrossSects")
+                        && element.is_first_named(position)
+                },
+            ),
+        })
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("Alignment")
+            .with_attr("name", self.name.as_str())
+            .with_opt_attr("length", writer.opt_number(self.length))
+            .with_opt_attr("staStart", writer.opt_number(self.sta_start))
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_children(self.coord_geom.as_ref().map(|c| c.to_xml(writer)))
+            .with_children(self.profile.as_ref().map(|p| p.to_xml(writer)))
+            .with_children(self.cross_sects.as_ref().map(|c| c.to_xml(writer)))
+            .with_extensions(&self.extensions)
+    }
+}
+
+/// モデルが解釈しないXML要素
+///
+/// ラウンドトリップ時にそのまま再出力するために保持します。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RawElement {
+    /// 要素名
+    pub name: String,
+    /// 属性（出現順）
+    #[serde(default)]
+    pub attributes: Vec<(String, String)>,
+    /// 子要素
+    #[serde(default)]
+    pub children: Vec<RawElement>,
+    /// テキスト内容
+    #[serde(default)]
+    pub text: String,
+}
+
+/// 親要素内の位置付きの未知要素
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionElement {
+    /// 親要素の子要素列における元の位置（0始まり）
+    pub position: usize,
+    /// 要素内容
+    pub element: RawElement,
+}
+
+/// モデルが解釈しない属性・子要素
+///
+/// 解析時に親のモデルへ保持し、出力時に元の位置へ再挿入します。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Extensions {
+    /// 未知の属性（出現順）
+    #[serde(default)]
+    pub attributes: Vec<(String, String)>,
+    /// 未知の子要素
+    #[serde(default)]
+    pub elements: Vec<ExtensionElement>,
+}
+
+impl Extensions {
+    /// 保持している内容がないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.elements.is_empty()
+    }
+
+    /// 未知の属性値を取得
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 指定名の未知要素を列挙
+    pub fn elements_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a RawElement> {
+        self.elements
+            .iter()
+            .map(|e| &e.element)
+            .filter(move |e| e.name == name)
+    }
+
+    /// 同じ種類の別の要素から取り出した内容を後ろに追加
+    ///
+    /// `offset`は追加する要素の子要素の前に並ぶ子要素の数です。
+    /// 既に保持している属性と同名の属性は追加しません。
+    pub fn append(&mut self, other: Extensions, offset: usize) {
+        for (key, value) in other.attributes {
+            if self.attribute(&key).is_none() {
+                self.attributes.push((key, value));
+            }
+        }
+        self.elements
+            .extend(other.elements.into_iter().map(|e| ExtensionElement {
+                position: e.position + offset,
+                element: e.element,
+            }));
+    }
+
+    /// 未知要素のFeature/Propertyに指定ラベルがあるかどうか
+    pub fn has_feature_property(&self, label: &str) -> bool {
+        self.feature_property(label).is_some()
+    }
+
+    /// 未知要素のFeature/Propertyから指定ラベルの値を取得
+    pub fn feature_property(&self, label: &str) -> Option<&str> {
+        self.elements_named("Feature")
+            .flat_map(|f| f.children.iter().filter(|c| c.name == "Property"))
+            .find(|p| p.attributes.iter().any(|(k, v)| k == "label" && v == label))
+            .and_then(|p| {
+                p.attributes
+                    .iter()
+                    .find(|(k, _)| k == "value")
+                    .map(|(_, v)| v.as_str())
+            })
+    }
 }
 
 /// 汎用Feature要素（プレースホルダー）
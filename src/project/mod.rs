@@ -4,9 +4,14 @@
 //! - Project name and description
 //! - Project classification (road, river, etc.)
 //! - Phase information
+//! - J-LandXML Feature properties (applicationCriterion, projectPhase, stratumMainData)
 
 use serde::{Deserialize, Serialize};
 
+use crate::coordinate_system::JLandXmlProperty;
+use crate::models::Extensions;
+use crate::xml::XmlElement;
+
 /// Project information
 ///
 /// Reference: LandXML 1.2 Project element
@@ -18,6 +23,186 @@ pub struct Project {
     pub desc: Option<String>,
     /// Project state (existing, proposed, etc.)
     pub state: Option<String>,
+    /// 適用基準（applicationCriterion、例: "MlitLandXmlVer.1.7"）
+    #[serde(default)]
+    pub application_criterion: Option<String>,
+    /// 設計段階（projectPhase、例: "詳細"）
+    #[serde(default)]
+    pub project_phase: Option<String>,
+    /// 地層データの主データ（stratumMainData）
+    #[serde(default)]
+    pub stratum_main_data: Option<String>,
+    /// その他のFeatureプロパティ（出現順）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<JLandXmlProperty>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl Project {
+    /// 設計段階を解析
+    pub fn phase(&self) -> Option<ProjectPhase> {
+        self.project_phase
+            .as_deref()
+            .and_then(ProjectPhase::from_label)
+    }
+
+    /// applicationCriterionからJ-LandXMLのバージョン（例: "1.7"）を取得
+    pub fn j_landxml_version(&self) -> Option<String> {
+        self.application_criterion
+            .as_deref()
+            .and_then(criterion_version)
+    }
+
+    /// ラベルを指定してFeatureプロパティの値を取得
+    pub fn property(&self, label: &str) -> Option<&str> {
+        match label {
+            "applicationCriterion" => self.application_criterion.as_deref(),
+            "projectPhase" => self.project_phase.as_deref(),
+            "stratumMainData" => self.stratum_main_data.as_deref(),
+            _ => self
+                .properties
+                .iter()
+                .find(|p| p.label == label)
+                .map(|p| p.value.as_str()),
+        }
+    }
+
+    pub(crate) fn from_xml(element: &XmlElement) -> Self {
+        let mut project = Self {
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            state: element.attr_string("state"),
+            // 旧形式: applicationCriterionをProjectの属性として記述
+            application_criterion: element.attr_string("applicationCriterion"),
+            extensions: element.extensions_by(
+                &["name", "desc", "state", "applicationCriterion"],
+                |_, child| child.is_property_feature(),
+            ),
+            ..Default::default()
+        };
+
+        let properties = element
+            .children
+            .iter()
+            .filter(|child| child.is_property_feature())
+            .flat_map(|feature| feature.children.iter());
+        for property in properties {
+            let label = property.attr("label").unwrap_or_default();
+            let value = property.attr_string("value").unwrap_or_default();
+            match label {
+                "applicationCriterion" => project.application_criterion = Some(value),
+                "projectPhase" => project.project_phase = Some(value),
+                "stratumMainData" => project.stratum_main_data = Some(value),
+                _ => project.properties.push(JLandXmlProperty::new(label, value)),
+            }
+        }
+        project
+    }
+
+    /// Project要素を生成（nameは必須属性のため未設定時は空文字）
+    pub(crate) fn to_xml(&self) -> XmlElement {
+        let known = [
+            ("projectPhase", &self.project_phase),
+            ("applicationCriterion", &self.application_criterion),
+            ("stratumMainData", &self.stratum_main_data),
+        ];
+        let properties: Vec<XmlElement> = known
+            .into_iter()
+            .filter_map(|(label, value)| value.as_deref().map(|v| (label, v)))
+            .chain(
+                self.properties
+                    .iter()
+                    .map(|p| (p.label.as_str(), p.value.as_str())),
+            )
+            .map(|(label, value)| {
+                XmlElement::new("Property")
+                    .with_attr("label", label)
+                    .with_attr("value", value)
+            })
+            .collect();
+        let feature =
+            (!properties.is_empty()).then(|| XmlElement::new("Feature").with_children(properties));
+
+        XmlElement::new("Project")
+            .with_attr("name", self.name.clone().unwrap_or_default())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_opt_attr("state", self.state.as_deref())
+            .with_children(feature)
+            .with_extensions(&self.extensions)
+    }
+}
+
+/// applicationCriterionの値からバージョン番号を取り出す
+///
+/// "MlitLandXmlVer.1.7"、"MlitLandXmlVer1.6"、"J-LandXML Ver.1.5" 等の表記に対応し、
+/// 将来のバージョン（"Ver.1.8"等）もそのまま返します。
+pub fn criterion_version(criterion: &str) -> Option<String> {
+    let start = criterion.find("Ver")? + 3;
+    let version = criterion[start..].trim_start_matches(['.', ' ']);
+    let end = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    Some(version[..end].trim_end_matches('.').to_string()).filter(|v| !v.is_empty())
+}
+
+/// 設計段階（J-LandXML projectPhase）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectPhase {
+    /// 計画
+    Planning,
+    /// 調査
+    Survey,
+    /// 概略設計
+    Outline,
+    /// 予備設計
+    Preliminary,
+    /// 詳細設計
+    Detailed,
+    /// 施工（工事）
+    Construction,
+    /// 維持管理
+    Maintenance,
+}
+
+impl ProjectPhase {
+    /// projectPhaseの値から解析（「詳細」「詳細設計」のどちらも可）
+    pub fn from_label(value: &str) -> Option<Self> {
+        match value.trim().trim_end_matches("設計") {
+            "計画" => Some(ProjectPhase::Planning),
+            "調査" => Some(ProjectPhase::Survey),
+            "概略" => Some(ProjectPhase::Outline),
+            "予備" => Some(ProjectPhase::Preliminary),
+            "詳細" => Some(ProjectPhase::Detailed),
+            "施工" | "工事" => Some(ProjectPhase::Construction),
+            "維持管理" => Some(ProjectPhase::Maintenance),
+            _ => None,
+        }
+    }
+
+    /// projectPhaseの表記
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProjectPhase::Planning => "計画",
+            ProjectPhase::Survey => "調査",
+            ProjectPhase::Outline => "概略",
+            ProjectPhase::Preliminary => "予備",
+            ProjectPhase::Detailed => "詳細",
+            ProjectPhase::Construction => "施工",
+            ProjectPhase::Maintenance => "維持管理",
+        }
+    }
+
+    /// 設計段階を「設計」付きで表した表記（例: 「詳細設計」）
+    pub fn as_designation(&self) -> &'static str {
+        match self {
+            ProjectPhase::Outline => "概略設計",
+            ProjectPhase::Preliminary => "予備設計",
+            ProjectPhase::Detailed => "詳細設計",
+            other => other.as_str(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -30,7 +215,67 @@ mod tests {
             name: Some("Test Project".to_string()),
             desc: Some("A test project".to_string()),
             state: Some("proposed".to_string()),
+            ..Default::default()
         };
         assert_eq!(project.name, Some("Test Project".to_string()));
     }
+
+    #[test]
+    fn test_project_phase_labels() {
+        assert_eq!(
+            ProjectPhase::from_label("詳細"),
+            Some(ProjectPhase::Detailed)
+        );
+        assert_eq!(
+            ProjectPhase::from_label("予備設計"),
+            Some(ProjectPhase::Preliminary)
+        );
+        assert_eq!(
+            ProjectPhase::from_label("工事"),
+            Some(ProjectPhase::Construction)
+        );
+        assert_eq!(ProjectPhase::from_label("詳細設計2"), None);
+    }
+
+    #[test]
+    fn test_criterion_version() {
+        assert_eq!(
+            criterion_version("MlitLandXmlVer.1.7").as_deref(),
+            Some("1.7")
+        );
+        assert_eq!(
+            criterion_version("MlitLandXmlVer1.6").as_deref(),
+            Some("1.6")
+        );
+        assert_eq!(
+            criterion_version("J-LandXML Ver.1.5").as_deref(),
+            Some("1.5")
+        );
+        assert_eq!(
+            criterion_version("MlitLandXmlVer.1.10").as_deref(),
+            Some("1.10")
+        );
+        assert_eq!(criterion_version("LandXML-1.2"), None);
+    }
+
+    #[test]
+    fn test_project_feature_properties() {
+        let element = XmlElement::parse_str(
+            r#"<Project name="P"><Feature>
+                <Property label="projectPhase" value="詳細"/>
+                <Property label="applicationCriterion" value="MlitLandXmlVer.1.7"/>
+                <Property label="stratumMainData" value="Surface"/>
+                <Property label="classification" value="第3種第2級"/>
+            </Feature><Feature name="other"><Property label="x" value="1"/></Feature></Project>"#,
+        )
+        .unwrap();
+        let project = Project::from_xml(&element);
+        assert_eq!(project.phase(), Some(ProjectPhase::Detailed));
+        assert_eq!(project.j_landxml_version().as_deref(), Some("1.7"));
+        assert_eq!(project.stratum_main_data.as_deref(), Some("Surface"));
+        assert_eq!(project.property("classification"), Some("第3種第2級"));
+        // 名前付きFeatureは未知要素として保持
+        assert_eq!(project.extensions.elements.len(), 1);
+        assert_eq!(project.to_xml(), element);
+    }
 }
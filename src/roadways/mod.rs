@@ -7,6 +7,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::Extensions;
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
+
 /// Roadway definition
 ///
 /// Reference: LandXML 1.2 Roadway element
@@ -20,6 +24,9 @@ pub struct Roadway {
     pub sta_start: Option<f64>,
     /// End station
     pub sta_end: Option<f64>,
+    /// 未知の属性・子要素（Speeds等）
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
 }
 
 /// Collection of Roadways
@@ -29,6 +36,51 @@ pub struct Roadway {
 pub struct Roadways {
     /// List of roadways
     pub roadways: Vec<Roadway>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl Roadway {
+    pub(crate) fn from_xml(element: &XmlElement) -> Self {
+        Self {
+            name: element.attr_string("name"),
+            alignment_refs: element
+                .attr("alignmentRefs")
+                .map(|refs| refs.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            sta_start: element.attr_f64("staStart"),
+            sta_end: element.attr_f64("staEnd"),
+            extensions: element.extensions(&["name", "alignmentRefs", "staStart", "staEnd"], &[]),
+        }
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("Roadway")
+            .with_attr("name", self.name.clone().unwrap_or_default())
+            .with_attr("alignmentRefs", self.alignment_refs.join(" "))
+            .with_opt_attr("staStart", self.sta_start.map(|v| writer.number(v)))
+            .with_opt_attr("staEnd", self.sta_end.map(|v| writer.number(v)))
+            .with_extensions(&self.extensions)
+    }
+}
+
+impl Roadways {
+    pub(crate) fn from_xml(element: &XmlElement) -> Self {
+        Self {
+            roadways: element
+                .children_named("Roadway")
+                .map(Roadway::from_xml)
+                .collect(),
+            extensions: element.extensions(&[], &["Roadway"]),
+        }
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("Roadways")
+            .with_children(self.roadways.iter().map(|roadway| roadway.to_xml(writer)))
+            .with_extensions(&self.extensions)
+    }
 }
 
 #[cfg(test)]
@@ -42,6 +94,7 @@ mod tests {
             alignment_refs: vec!["Alignment1".to_string()],
             sta_start: Some(0.0),
             sta_end: Some(1000.0),
+            ..Default::default()
         };
         assert_eq!(roadway.name, Some("MainRoad".to_string()));
     }
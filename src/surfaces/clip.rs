@@ -0,0 +1,288 @@
+//! Planar clipping helpers for surface computations
+//!
+//! 土量・面積計算で使用する平面上の多角形演算：
+//! - 半平面・凸多角形による多角形の切り取り（Sutherland–Hodgman）
+//! - 多角形の面積・一次モーメント（線形関数の面積分に使用）
+//! - 境界多角形（穴あき・非凸）と凸多角形の共通部分のモーメント
+//!
+//! 座標は測量座標（X北・Y東）で扱います。「正の向き」は符号付き面積が正となる頂点順です。
+//! 平面直角座標の値は大きいため、平面・モーメントは三角形ごとの局所原点からの座標で計算します。
+
+use crate::models::{Point2D, Point3D};
+use crate::units::gis_to_survey;
+
+/// 多角形の面積と一次モーメント（∫x dA, ∫y dA）
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Moments {
+    pub area: f64,
+    pub sx: f64,
+    pub sy: f64,
+}
+
+impl Moments {
+    /// 多角形（閉じていない頂点列）の符号付きモーメント
+    pub fn of(polygon: &[Point2D]) -> Self {
+        let mut moments = Self::default();
+        for (i, p) in polygon.iter().enumerate() {
+            let q = polygon[(i + 1) % polygon.len()];
+            let cross = p.x * q.y - q.x * p.y;
+            moments.area += cross;
+            moments.sx += (p.x + q.x) * cross;
+            moments.sy += (p.y + q.y) * cross;
+        }
+        moments.area /= 2.0;
+        moments.sx /= 6.0;
+        moments.sy /= 6.0;
+        moments
+    }
+
+    /// 線形関数 f = a·x + b·y + c の面積分
+    pub fn integrate(&self, [a, b, c]: [f64; 3]) -> f64 {
+        a * self.sx + b * self.sy + c * self.area
+    }
+}
+
+impl std::ops::Add for Moments {
+    type Output = Moments;
+
+    fn add(self, rhs: Moments) -> Moments {
+        Moments {
+            area: self.area + rhs.area,
+            sx: self.sx + rhs.sx,
+            sy: self.sy + rhs.sy,
+        }
+    }
+}
+
+/// 三角形の平面 z = a·x + b·y + c の係数（平面上で退化している場合はNone）
+pub(crate) fn plane_of([p, q, r]: [Point3D; 3]) -> Option<[f64; 3]> {
+    let (ux, uy, uz) = (q.x - p.x, q.y - p.y, q.z - p.z);
+    let (vx, vy, vz) = (r.x - p.x, r.y - p.y, r.z - p.z);
+    let det = ux * vy - uy * vx;
+    if det.abs() <= f64::EPSILON * (ux.abs() + uy.abs()) * (vx.abs() + vy.abs()) {
+        return None;
+    }
+    let a = (uz * vy - uy * vz) / det;
+    let b = (ux * vz - uz * vx) / det;
+    Some([a, b, p.z - a * p.x - b * p.y])
+}
+
+/// 局所原点からの座標に平行移動（標高はそのまま）
+pub(crate) fn translated(vertices: [Point3D; 3], origin: Point2D) -> [Point3D; 3] {
+    vertices.map(|p| Point3D {
+        x: p.x - origin.x,
+        y: p.y - origin.y,
+        z: p.z,
+    })
+}
+
+/// 三角形の平面投影を正の向きの頂点列で取得
+pub(crate) fn oriented_triangle(vertices: [Point3D; 3]) -> [Point2D; 3] {
+    let [a, b, c] = vertices.map(|p| Point2D { x: p.x, y: p.y });
+    if Moments::of(&[a, b, c]).area < 0.0 {
+        [a, c, b]
+    } else {
+        [a, b, c]
+    }
+}
+
+/// 半平面 a·x + b·y + c ≥ 0 で多角形を切り取る
+pub(crate) fn clip_half_plane(polygon: &[Point2D], [a, b, c]: [f64; 3]) -> Vec<Point2D> {
+    let side = |p: &Point2D| a * p.x + b * p.y + c;
+    let mut output = Vec::with_capacity(polygon.len() + 1);
+    for (i, p) in polygon.iter().enumerate() {
+        let q = &polygon[(i + 1) % polygon.len()];
+        let (sp, sq) = (side(p), side(q));
+        if sp >= 0.0 {
+            output.push(*p);
+        }
+        if (sp >= 0.0) != (sq >= 0.0) {
+            let t = sp / (sp - sq);
+            output.push(Point2D {
+                x: p.x + (q.x - p.x) * t,
+                y: p.y + (q.y - p.y) * t,
+            });
+        }
+    }
+    output
+}
+
+/// 正の向きの凸多角形で多角形を切り取る
+///
+/// 切り取られる多角形は非凸でもよく、結果に含まれる退化した辺は
+/// モーメントの計算に影響しません。
+pub(crate) fn clip_convex(polygon: &[Point2D], convex: &[Point2D]) -> Vec<Point2D> {
+    if Moments::of(convex).area <= 0.0 {
+        return Vec::new();
+    }
+    let mut output = polygon.to_vec();
+    for (i, p) in convex.iter().enumerate() {
+        if output.len() < 3 {
+            return Vec::new();
+        }
+        let q = convex[(i + 1) % convex.len()];
+        // 辺pqの左側（正の向きの多角形の内側）
+        let (a, b) = (p.y - q.y, q.x - p.x);
+        if a == 0.0 && b == 0.0 {
+            // 重複した頂点は切り取りに寄与しない
+            continue;
+        }
+        output = clip_half_plane(&output, [a, b, -(a * p.x + b * p.y)]);
+    }
+    if output.len() < 3 {
+        Vec::new()
+    } else {
+        output
+    }
+}
+
+/// 計算範囲を限定する境界多角形（穴を含む）
+#[derive(Debug, Clone)]
+pub(crate) struct Region {
+    /// 外周は正の向き、穴は負の向きの頂点列
+    rings: Vec<Vec<Point2D>>,
+    min: Point2D,
+    max: Point2D,
+}
+
+impl Region {
+    /// GIS座標（x東・y北）の多角形から作成
+    pub fn from_polygon(polygon: &geo::Polygon<f64>) -> Self {
+        let ring = |line: &geo::LineString<f64>, positive: bool| {
+            let mut points: Vec<Point2D> = line.0.iter().map(|c| gis_to_survey(*c)).collect();
+            if points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+            if (Moments::of(&points).area > 0.0) != positive {
+                points.reverse();
+            }
+            points
+        };
+        let mut rings = vec![ring(polygon.exterior(), true)];
+        rings.extend(polygon.interiors().iter().map(|r| ring(r, false)));
+
+        let mut min = Point2D {
+            x: f64::INFINITY,
+            y: f64::INFINITY,
+        };
+        let mut max = Point2D {
+            x: f64::NEG_INFINITY,
+            y: f64::NEG_INFINITY,
+        };
+        for p in &rings[0] {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+        Self { rings, min, max }
+    }
+
+    /// 外周の頂点列（正の向き）
+    pub fn exterior(&self) -> &[Point2D] {
+        &self.rings[0]
+    }
+
+    /// 凸多角形と境界の共通部分のモーメント
+    ///
+    /// 凸多角形とモーメントは`origin`を原点とする局所座標です。
+    pub fn moments_within(&self, convex: &[Point2D], origin: Point2D) -> Moments {
+        let disjoint = convex.iter().all(|p| p.x + origin.x < self.min.x)
+            || convex.iter().all(|p| p.x + origin.x > self.max.x)
+            || convex.iter().all(|p| p.y + origin.y < self.min.y)
+            || convex.iter().all(|p| p.y + origin.y > self.max.y);
+        if disjoint {
+            return Moments::default();
+        }
+        self.rings
+            .iter()
+            .map(|ring| {
+                let local: Vec<Point2D> = ring
+                    .iter()
+                    .map(|p| Point2D {
+                        x: p.x - origin.x,
+                        y: p.y - origin.y,
+                    })
+                    .collect();
+                Moments::of(&clip_convex(&local, convex))
+            })
+            .fold(Moments::default(), |sum, m| sum + m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(x: f64, y: f64) -> Point2D {
+        Point2D { x, y }
+    }
+
+    #[test]
+    fn test_clip_moments() {
+        let square = [p(0.0, 0.0), p(10.0, 0.0), p(10.0, 10.0), p(0.0, 10.0)];
+        let moments = Moments::of(&square);
+        assert!((moments.area - 100.0).abs() < 1e-9);
+        // ∫x dA = 面積 × 重心
+        assert!((moments.sx - 500.0).abs() < 1e-9);
+
+        let half = clip_half_plane(&square, [-1.0, 0.0, 4.0]);
+        assert!((Moments::of(&half).area - 40.0).abs() < 1e-9);
+
+        // 凹多角形（コの字形）を凸多角形で切り取る
+        let u_shape = [
+            p(0.0, 0.0),
+            p(3.0, 0.0),
+            p(3.0, 8.0),
+            p(7.0, 8.0),
+            p(7.0, 0.0),
+            p(10.0, 0.0),
+            p(10.0, 10.0),
+            p(0.0, 10.0),
+        ];
+        let window = [p(0.0, 0.0), p(10.0, 0.0), p(10.0, 5.0), p(0.0, 5.0)];
+        let clipped = Moments::of(&clip_convex(&u_shape, &window));
+        assert!((clipped.area - 30.0).abs() < 1e-9);
+
+        // 穴あきの境界（GIS座標）
+        let boundary = geo::Polygon::new(
+            geo::LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+            vec![geo::LineString::from(vec![
+                (2.0, 2.0),
+                (4.0, 2.0),
+                (4.0, 4.0),
+                (2.0, 4.0),
+            ])],
+        );
+        let region = Region::from_polygon(&boundary);
+        let origin = p(0.0, 0.0);
+        assert!((region.moments_within(&square, origin).area - 96.0).abs() < 1e-9);
+        assert!((region.moments_within(&window, origin).area - 46.0).abs() < 1e-9);
+        // 局所原点(5, 5)からの座標で指定した南東の四半分
+        let quarter = [p(0.0, 0.0), p(5.0, 0.0), p(5.0, 5.0), p(0.0, 5.0)];
+        assert!((region.moments_within(&quarter, p(5.0, 5.0)).area - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_plane_of() {
+        let plane = plane_of([
+            Point3D {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            },
+            Point3D {
+                x: 1.0,
+                y: 0.0,
+                z: 3.0,
+            },
+            Point3D {
+                x: 0.0,
+                y: 1.0,
+                z: 4.0,
+            },
+        ])
+        .unwrap();
+        assert_eq!(plane, [2.0, 3.0, 1.0]);
+    }
+}
@@ -0,0 +1,342 @@
+//! Contour line generation
+//!
+//! 三角網（TIN）から等高線を生成します：
+//! - 指定した間隔・基準標高の各標高で三角形ごとの線分を求め、共有辺で連結
+//! - 高い側を右手に見る向き（GIS座標）に統一した折れ線・閉曲線
+//! - 主曲線・計曲線の区別
+//! - Chaikin法による任意の平滑化
+//!
+//! 出力はGIS座標（x東・y北）の`geo::LineString`で、閉曲線は始点と終点が一致します。
+
+use std::collections::{HashMap, HashSet};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::Surface;
+use crate::error::LandXMLError;
+use crate::models::{Point2D, Point3D};
+use crate::units::survey_to_gis;
+
+/// 等高線の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContourKind {
+    /// 計曲線（主曲線の一定本数ごとの太い線）
+    Major,
+    /// 主曲線
+    Minor,
+}
+
+/// 等高線
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contour {
+    /// 標高
+    pub elevation: f64,
+    /// 種類
+    pub kind: ContourKind,
+    /// 線形状（GIS座標、高い側を右手に見る向き）
+    pub line: geo::LineString<f64>,
+}
+
+impl Contour {
+    /// 閉曲線かどうか
+    pub fn is_closed(&self) -> bool {
+        self.line.is_closed()
+    }
+}
+
+/// 等高線の生成条件
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContourOptions {
+    /// 等高線の間隔
+    pub interval: f64,
+    /// 基準標高（この標高から間隔ごとに等高線を生成）
+    pub base: f64,
+    /// 計曲線とする本数の間隔（0の場合は全て主曲線）
+    pub major_every: usize,
+    /// 平滑化の反復回数（0の場合は平滑化しない）
+    pub smoothing: usize,
+}
+
+impl ContourOptions {
+    /// 間隔を指定して作成（基準標高0、5本ごとに計曲線）
+    pub fn new(interval: f64) -> Self {
+        Self {
+            interval,
+            base: 0.0,
+            major_every: 5,
+            smoothing: 0,
+        }
+    }
+
+    /// 基準標高を設定
+    pub fn with_base(mut self, base: f64) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// 計曲線とする本数の間隔を設定
+    pub fn with_major_every(mut self, count: usize) -> Self {
+        self.major_every = count;
+        self
+    }
+
+    /// 平滑化の反復回数を設定
+    pub fn with_smoothing(mut self, iterations: usize) -> Self {
+        self.smoothing = iterations;
+        self
+    }
+}
+
+/// 三角形の辺（頂点番号の昇順）
+type EdgeKey = (usize, usize);
+
+impl Surface {
+    /// 等高線を生成
+    ///
+    /// 標高の低い順に、同じ標高の中では生成順に返します。
+    pub fn contours(&self, options: &ContourOptions) -> Result<Vec<Contour>, LandXMLError> {
+        if !(options.interval.is_finite() && options.interval > 0.0) {
+            return Err(LandXMLError::GeometryError {
+                message: format!("Invalid contour interval: {}", options.interval),
+            });
+        }
+
+        let triangles: Vec<([usize; 3], [Point3D; 3])> = self
+            .triangles
+            .iter()
+            .filter_map(|t| Some((t.vertices, self.triangle_vertices(t)?)))
+            .collect();
+        let (min, max) = triangles
+            .iter()
+            .flat_map(|(_, v)| v.iter().map(|p| p.z))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), z| {
+                (lo.min(z), hi.max(z))
+            });
+        if triangles.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let first = ((min - options.base) / options.interval).ceil() as i64;
+        let last = ((max - options.base) / options.interval).floor() as i64;
+        let contours = (first..=last)
+            .into_par_iter()
+            .flat_map_iter(|step| {
+                let elevation = options.base + step as f64 * options.interval;
+                let kind = if options.major_every > 0
+                    && step.rem_euclid(options.major_every as i64) == 0
+                {
+                    ContourKind::Major
+                } else {
+                    ContourKind::Minor
+                };
+                trace_level(&triangles, elevation)
+                    .into_iter()
+                    .map(move |mut points| {
+                        for _ in 0..options.smoothing {
+                            points = chaikin(&points);
+                        }
+                        Contour {
+                            elevation,
+                            kind,
+                            line: points.into_iter().map(survey_to_gis).collect(),
+                        }
+                    })
+            })
+            .collect();
+        Ok(contours)
+    }
+}
+
+/// 1つの標高の等高線を追跡
+///
+/// 標高がちょうど等しい頂点は上側として扱い、線分が頂点を通過しないようにします。
+fn trace_level(triangles: &[([usize; 3], [Point3D; 3])], level: f64) -> Vec<Vec<Point2D>> {
+    let mut crossings: HashMap<EdgeKey, Point2D> = HashMap::new();
+    // 線分の始点の辺 → 終点の辺
+    let mut next: HashMap<EdgeKey, EdgeKey> = HashMap::new();
+
+    for (indices, vertices) in triangles {
+        let above = vertices.map(|p| p.z >= level);
+        if above.iter().all(|a| *a) || above.iter().all(|a| !*a) {
+            continue;
+        }
+        // 測量座標（X北・Y東）で正の向きはGIS座標では時計回りとなるため、逆順にたどる
+        let positive = {
+            let [a, b, c] = vertices;
+            (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x) > 0.0
+        };
+        let order: [usize; 3] = if positive { [0, 2, 1] } else { [0, 1, 2] };
+
+        // GIS座標の反時計回りに辺をたどり、下→上の辺から上→下の辺へ線分を引くと
+        // 高い側が右手になる
+        let (mut start, mut end) = (None, None);
+        for k in 0..3 {
+            let (i, j) = (order[k], order[(k + 1) % 3]);
+            if above[i] == above[j] {
+                continue;
+            }
+            let key = edge_key(indices[i], indices[j]);
+            crossings
+                .entry(key)
+                .or_insert_with(|| crossing(indices, vertices, i, j, level));
+            if above[j] {
+                start = Some(key);
+            } else {
+                end = Some(key);
+            }
+        }
+        if let (Some(start), Some(end)) = (start, end) {
+            next.insert(start, end);
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut visited: HashSet<EdgeKey> = HashSet::new();
+    let ends: HashSet<EdgeKey> = next.values().copied().collect();
+    // 開いた線（三角網の外周で途切れる線）を先に、残りを閉曲線として追跡
+    let mut starts: Vec<EdgeKey> = next.keys().filter(|k| !ends.contains(k)).copied().collect();
+    starts.sort_unstable();
+    let mut loops: Vec<EdgeKey> = next.keys().copied().collect();
+    loops.sort_unstable();
+
+    for start in starts.into_iter().chain(loops) {
+        if visited.contains(&start) {
+            continue;
+        }
+        let mut points = vec![crossings[&start]];
+        let mut key = start;
+        visited.insert(key);
+        while let Some(&following) = next.get(&key) {
+            points.push(crossings[&following]);
+            if following == start || !visited.insert(following) {
+                break;
+            }
+            key = following;
+        }
+        // 標高と等しい頂点を通る場合に生じる重複点を除く
+        points.dedup();
+        if points.len() >= 2 {
+            lines.push(points);
+        }
+    }
+    lines
+}
+
+fn edge_key(a: usize, b: usize) -> EdgeKey {
+    (a.min(b), a.max(b))
+}
+
+/// 辺と標高の交点（辺を共有する三角形で同じ座標となるよう頂点番号順に補間）
+fn crossing(
+    indices: &[usize; 3],
+    vertices: &[Point3D; 3],
+    i: usize,
+    j: usize,
+    level: f64,
+) -> Point2D {
+    let (p, q) = if indices[i] < indices[j] {
+        (vertices[i], vertices[j])
+    } else {
+        (vertices[j], vertices[i])
+    };
+    let t = (level - p.z) / (q.z - p.z);
+    Point2D {
+        x: p.x + (q.x - p.x) * t,
+        y: p.y + (q.y - p.y) * t,
+    }
+}
+
+/// Chaikin法による平滑化（開いた線は端点を保持）
+fn chaikin(points: &[Point2D]) -> Vec<Point2D> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let closed = points.first() == points.last();
+    let lerp = |p: Point2D, q: Point2D, t: f64| Point2D {
+        x: p.x + (q.x - p.x) * t,
+        y: p.y + (q.y - p.y) * t,
+    };
+    let mut smoothed = Vec::with_capacity(points.len() * 2);
+    if !closed {
+        smoothed.push(points[0]);
+    }
+    for pair in points.windows(2) {
+        smoothed.push(lerp(pair[0], pair[1], 0.25));
+        smoothed.push(lerp(pair[0], pair[1], 0.75));
+    }
+    if closed {
+        smoothed.push(smoothed[0]);
+    } else {
+        smoothed.push(points[points.len() - 1]);
+    }
+    smoothed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10m四方の格子上の四角錐（中央の標高10m、外周0m）
+    fn pyramid() -> Surface {
+        crate::surfaces::grid_surface(10.0, 10, |x, y| {
+            10.0 - 2.0 * (x - 5.0).abs().max((y - 5.0).abs())
+        })
+    }
+
+    #[test]
+    fn test_closed_contours() {
+        let surface = pyramid();
+        let contours = surface
+            .contours(&ContourOptions::new(1.0).with_major_every(5))
+            .unwrap();
+
+        // 外周（0m）と頂点（10m）は線にならない
+        let elevations: Vec<f64> = contours.iter().map(|c| c.elevation).collect();
+        assert_eq!(elevations, (1..=9).map(f64::from).collect::<Vec<_>>());
+        assert!(contours.iter().all(|c| c.is_closed()));
+        assert_eq!(contours[4].kind, ContourKind::Major);
+        assert_eq!(contours[3].kind, ContourKind::Minor);
+
+        // 標高4mの等高線は一辺6mの正方形（格子点を通る）
+        let square = &contours[3];
+        let length: f64 = square.line.lines().map(|l| (l.dx()).hypot(l.dy())).sum();
+        assert!((length - 24.0).abs() < 1e-9);
+
+        // 高い側を右手に見る向き：GIS座標で時計回り（符号付き面積が負）
+        let coords = &square.line.0;
+        let signed_area: f64 = coords
+            .windows(2)
+            .map(|w| w[0].x * w[1].y - w[1].x * w[0].y)
+            .sum::<f64>()
+            / 2.0;
+        assert!((signed_area + 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_open_contours_and_smoothing() {
+        // 平面 z = X（北ほど高い）の等高線は東西方向の開いた線
+        let mut surface = pyramid();
+        for p in &mut surface.points {
+            p.z = p.x;
+        }
+        let contours = surface
+            .contours(&ContourOptions::new(2.5).with_base(0.5))
+            .unwrap();
+        assert_eq!(contours.len(), 4);
+        let line = &contours[0].line;
+        assert!(!contours[0].is_closed());
+        assert_eq!(line.0.len(), 21);
+        // 北（GIS座標のy正方向）が高いので、右手に北を見る西向き
+        assert!(line.0[0].x > line.0[line.0.len() - 1].x);
+        assert!(line.0.iter().all(|c| (c.y - 0.5).abs() < 1e-9));
+
+        let smoothed = surface
+            .contours(&ContourOptions::new(2.5).with_base(0.5).with_smoothing(2))
+            .unwrap();
+        assert_eq!(smoothed[0].line.0.first(), line.0.first());
+        assert!(smoothed[0].line.0.len() > line.0.len());
+
+        assert!(surface.contours(&ContourOptions::new(0.0)).is_err());
+    }
+}
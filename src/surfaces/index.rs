@@ -0,0 +1,306 @@
+//! TIN elevation query
+//!
+//! 三角網（TIN）上の任意点の標高を求めます：
+//! - 重心座標（barycentric）による三角形内の線形補間
+//! - 均等格子による三角形の空間索引（面ごとに一度だけ構築）
+//! - rayonによる複数点の並列問い合わせ
+//!
+//! 座標は測量座標（X北・Y東）で扱い、三角網の外側ではNoneを返します。
+
+use rayon::prelude::*;
+
+use super::Surface;
+use crate::models::{Point2D, Point3D};
+
+/// 三角形の内外判定の許容誤差（重心座標）
+const BARYCENTRIC_TOLERANCE: f64 = 1e-9;
+
+/// 格子の最大セル数（三角形数に対する倍率）
+const MAX_CELLS_PER_TRIANGLE: usize = 4;
+
+/// 点の重心座標を計算
+///
+/// 三角形の内側（辺上を含む）の場合のみ値を返します。
+pub(crate) fn barycentric(p: Point2D, a: Point3D, b: Point3D, c: Point3D) -> Option<[f64; 3]> {
+    let denom = (b.y - c.y) * (a.x - c.x) + (c.x - b.x) * (a.y - c.y);
+    if denom.abs() <= f64::EPSILON * ((a.x - c.x).abs() + (a.y - c.y).abs()).powi(2) {
+        return None;
+    }
+    let l1 = ((b.y - c.y) * (p.x - c.x) + (c.x - b.x) * (p.y - c.y)) / denom;
+    let l2 = ((c.y - a.y) * (p.x - c.x) + (a.x - c.x) * (p.y - c.y)) / denom;
+    let l3 = 1.0 - l1 - l2;
+    [l1, l2, l3]
+        .iter()
+        .all(|l| *l >= -BARYCENTRIC_TOLERANCE)
+        .then_some([l1, l2, l3])
+}
+
+/// 三角形内の点の標高を線形補間
+pub(crate) fn interpolate(p: Point2D, [a, b, c]: [Point3D; 3]) -> Option<f64> {
+    barycentric(p, a, b, c).map(|[l1, l2, l3]| l1 * a.z + l2 * b.z + l3 * c.z)
+}
+
+impl Surface {
+    /// 三角形の頂点座標を取得
+    ///
+    /// 非表示の三角形、存在しない点を参照する三角形の場合はNoneを返します。
+    pub fn triangle_vertices(&self, triangle: &super::Triangle) -> Option<[Point3D; 3]> {
+        if triangle.invisible {
+            return None;
+        }
+        let [a, b, c] = triangle.vertices;
+        Some([
+            *self.points.get(a)?,
+            *self.points.get(b)?,
+            *self.points.get(c)?,
+        ])
+    }
+
+    /// 三角形の空間索引を構築
+    pub fn index(&self) -> TinIndex<'_> {
+        TinIndex::new(self)
+    }
+
+    /// 指定位置（X北, Y東）の標高を取得
+    ///
+    /// 全ての三角形を走査します。多数の点を問い合わせる場合は
+    /// [`Surface::index`]で構築した索引を使用してください。
+    pub fn elevation_at(&self, x: f64, y: f64) -> Option<f64> {
+        let p = Point2D { x, y };
+        self.triangles
+            .iter()
+            .filter_map(|t| self.triangle_vertices(t))
+            .find_map(|vertices| interpolate(p, vertices))
+    }
+
+    /// 複数位置（X北, Y東）の標高を並列に取得
+    pub fn elevations_at(&self, points: &[Point2D]) -> Vec<Option<f64>> {
+        self.index().elevations_at(points)
+    }
+}
+
+/// 三角形の空間索引（均等格子）
+///
+/// 各セルに外接矩形が重なる三角形の番号を保持します。
+#[derive(Debug, Clone)]
+pub struct TinIndex<'a> {
+    surface: &'a Surface,
+    /// 格子の原点（X・Yの最小値）
+    origin: Point2D,
+    /// 格子の最大点（X・Yの最大値）
+    max: Point2D,
+    cell_size: f64,
+    rows: usize,
+    cols: usize,
+    /// セルごとの三角形番号（行優先）
+    cells: Vec<Vec<usize>>,
+}
+
+impl<'a> TinIndex<'a> {
+    /// 面の三角形から索引を構築
+    pub fn new(surface: &'a Surface) -> Self {
+        let triangles: Vec<(usize, [Point3D; 3])> = surface
+            .triangles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| surface.triangle_vertices(t).map(|v| (i, v)))
+            .collect();
+
+        let mut origin = Point2D {
+            x: f64::INFINITY,
+            y: f64::INFINITY,
+        };
+        let mut max = Point2D {
+            x: f64::NEG_INFINITY,
+            y: f64::NEG_INFINITY,
+        };
+        for p in triangles.iter().flat_map(|(_, v)| v.iter()) {
+            origin.x = origin.x.min(p.x);
+            origin.y = origin.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+
+        let mut index = Self {
+            surface,
+            origin,
+            max,
+            cell_size: 1.0,
+            rows: 0,
+            cols: 0,
+            cells: Vec::new(),
+        };
+        if triangles.is_empty() {
+            return index;
+        }
+
+        // 1セルあたりおおむね1つの三角形となる大きさ
+        let (width, height) = (max.x - origin.x, max.y - origin.y);
+        let count = triangles.len() as f64;
+        let cell_size = (width * height / count)
+            .sqrt()
+            .max(width.max(height) / (count * MAX_CELLS_PER_TRIANGLE as f64))
+            .max(f64::MIN_POSITIVE);
+        index.cell_size = cell_size;
+        index.rows = ((width / cell_size).floor() as usize + 1).max(1);
+        index.cols = ((height / cell_size).floor() as usize + 1).max(1);
+        index.cells = vec![Vec::new(); index.rows * index.cols];
+
+        for (i, vertices) in &triangles {
+            let min = Point2D {
+                x: vertices.iter().map(|p| p.x).fold(f64::INFINITY, f64::min),
+                y: vertices.iter().map(|p| p.y).fold(f64::INFINITY, f64::min),
+            };
+            let max = Point2D {
+                x: vertices
+                    .iter()
+                    .map(|p| p.x)
+                    .fold(f64::NEG_INFINITY, f64::max),
+                y: vertices
+                    .iter()
+                    .map(|p| p.y)
+                    .fold(f64::NEG_INFINITY, f64::max),
+            };
+            let (r0, c0) = index.cell_of(min);
+            let (r1, c1) = index.cell_of(max);
+            for row in r0..=r1 {
+                for col in c0..=c1 {
+                    index.cells[row * index.cols + col].push(*i);
+                }
+            }
+        }
+        index
+    }
+
+    /// 索引の対象の面
+    pub fn surface(&self) -> &'a Surface {
+        self.surface
+    }
+
+    /// 三角網の外接矩形（最小点, 最大点）
+    pub fn bounds(&self) -> Option<(Point2D, Point2D)> {
+        (!self.cells.is_empty()).then_some((self.origin, self.max))
+    }
+
+    /// 点を含むセルの行・列（範囲外は端のセルに丸める）
+    fn cell_of(&self, p: Point2D) -> (usize, usize) {
+        let clamp = |value: f64, count: usize| {
+            (value.floor().max(0.0) as usize).min(count.saturating_sub(1))
+        };
+        (
+            clamp((p.x - self.origin.x) / self.cell_size, self.rows),
+            clamp((p.y - self.origin.y) / self.cell_size, self.cols),
+        )
+    }
+
+    /// 矩形に外接矩形が重なる可能性のある三角形の番号（昇順・重複なし）
+    pub fn triangles_in(&self, min: Point2D, max: Point2D) -> Vec<usize> {
+        if self.cells.is_empty()
+            || max.x < self.origin.x
+            || max.y < self.origin.y
+            || min.x > self.max.x
+            || min.y > self.max.y
+        {
+            return Vec::new();
+        }
+        let (r0, c0) = self.cell_of(min);
+        let (r1, c1) = self.cell_of(max);
+        let mut found: Vec<usize> = (r0..=r1)
+            .flat_map(|row| (c0..=c1).map(move |col| (row, col)))
+            .flat_map(|(row, col)| self.cells[row * self.cols + col].iter().copied())
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found
+    }
+
+    /// 指定位置（X北, Y東）を含む三角形の番号を取得
+    pub fn locate(&self, x: f64, y: f64) -> Option<usize> {
+        self.locate_with_elevation(Point2D { x, y }).map(|(i, _)| i)
+    }
+
+    /// 指定位置（X北, Y東）の標高を取得
+    pub fn elevation_at(&self, x: f64, y: f64) -> Option<f64> {
+        self.locate_with_elevation(Point2D { x, y }).map(|(_, z)| z)
+    }
+
+    /// 複数位置（X北, Y東）の標高を並列に取得
+    pub fn elevations_at(&self, points: &[Point2D]) -> Vec<Option<f64>> {
+        points
+            .par_iter()
+            .map(|p| self.elevation_at(p.x, p.y))
+            .collect()
+    }
+
+    fn locate_with_elevation(&self, p: Point2D) -> Option<(usize, f64)> {
+        if self.cells.is_empty()
+            || p.x < self.origin.x
+            || p.y < self.origin.y
+            || p.x > self.max.x
+            || p.y > self.max.y
+        {
+            return None;
+        }
+        let (row, col) = self.cell_of(p);
+        self.cells[row * self.cols + col].iter().find_map(|&i| {
+            let vertices = self.surface.triangle_vertices(&self.surface.triangles[i])?;
+            interpolate(p, vertices).map(|z| (i, z))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10m格子の4×4セル（標高 z = x + 2y）
+    fn sloped_grid() -> Surface {
+        crate::surfaces::grid_surface(40.0, 4, |x, y| x + 2.0 * y)
+    }
+
+    #[test]
+    fn test_elevation_query() {
+        let surface = sloped_grid();
+        let index = surface.index();
+        assert_eq!(
+            index.bounds().map(|(min, max)| (min.x, max.y)),
+            Some((0.0, 40.0))
+        );
+
+        for (x, y) in [(12.5, 7.5), (0.0, 0.0), (40.0, 40.0), (20.0, 35.0)] {
+            let z = index.elevation_at(x, y).unwrap();
+            assert!((z - (x + 2.0 * y)).abs() < 1e-9, "({}, {}): {}", x, y, z);
+            assert_eq!(surface.elevation_at(x, y), Some(z));
+        }
+        assert!(index.locate(12.5, 7.5).is_some());
+        assert_eq!(index.elevation_at(-0.1, 5.0), None);
+        assert_eq!(surface.elevation_at(41.0, 5.0), None);
+
+        let points: Vec<Point2D> = (0..100)
+            .map(|i| Point2D {
+                x: i as f64 * 0.5 - 5.0,
+                y: 17.0,
+            })
+            .collect();
+        let elevations = surface.elevations_at(&points);
+        for (p, z) in points.iter().zip(&elevations) {
+            if p.x < 0.0 || p.x > 40.0 {
+                assert_eq!(*z, None);
+            } else {
+                assert!((z.unwrap() - (p.x + 34.0)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_concave_hull() {
+        // L字形の面では、外接矩形内でも三角網の外側はNone
+        let mut surface = sloped_grid();
+        surface.triangles.retain(|t| {
+            let v = t.vertices[0];
+            !(v / 5 >= 2 && v % 5 >= 2)
+        });
+        assert!(surface.index().elevation_at(5.0, 35.0).is_some());
+        assert_eq!(surface.index().elevation_at(35.0, 35.0), None);
+    }
+}
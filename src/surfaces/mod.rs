@@ -4,22 +4,55 @@
 //! - Surface definitions
 //! - Triangle meshes
 //! - Point clouds
+//! - Elevation queries (see [`index`])
+//! - Volume computation (see [`volume`])
+//! - Contour generation (see [`contour`])
+//! - Constrained Delaunay triangulation (see [`triangulation`])
+
+mod clip;
+pub mod contour;
+pub mod index;
+pub mod triangulation;
+pub mod volume;
+
+use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use crate::models::Point3D;
+use crate::error::LandXMLError;
+use crate::models::{Extensions, Point3D, RawElement};
+use crate::writer::LandXmlWriter;
+use crate::xml::XmlElement;
+
+pub use self::contour::{Contour, ContourKind, ContourOptions};
+pub use self::index::TinIndex;
+pub use self::triangulation::TinBuilder;
+pub use self::volume::{PlaneVolumeResult, ReferencePlane, VolumeResult};
 
 /// A triangular face in a TIN surface
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Triangle {
     /// Indices into the points array (3 vertices)
     pub vertices: [usize; 3],
+    /// Invisible face (F i="1", e.g. holes); excluded from geometry computations
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub invisible: bool,
+}
+
+impl Triangle {
+    /// 表示される三角形を作成
+    pub fn new(vertices: [usize; 3]) -> Self {
+        Self {
+            vertices,
+            invisible: false,
+        }
+    }
 }
 
 /// TIN Surface definition
 ///
 /// Reference: LandXML 1.2 Surface element
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Surface {
     /// Surface name
     pub name: Option<String>,
@@ -29,17 +62,23 @@ pub struct Surface {
     pub points: Vec<Point3D>,
     /// Triangle faces
     pub triangles: Vec<Triangle>,
-}
-
-impl Default for Surface {
-    fn default() -> Self {
-        Self {
-            name: None,
-            desc: None,
-            points: Vec::new(),
-            triangles: Vec::new(),
-        }
-    }
+    /// Original P ids (same order as `points`; empty = 1-based indices on output)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub point_ids: Vec<String>,
+    /// Definition surfType ("TIN" or "grid"; None = "TIN" on output)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surf_type: Option<String>,
+    /// 三角形として解釈できないF要素（未定義のPのidを参照する等）
+    ///
+    /// 計算には使用せず、出力時にFacesの末尾へそのまま再出力します。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_faces: Vec<RawElement>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+    /// Definition要素の未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub definition_extensions: Extensions,
 }
 
 /// Collection of Surfaces
@@ -47,8 +86,158 @@ impl Default for Surface {
 /// Reference: LandXML 1.2 Surfaces element
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Surfaces {
+    /// Collection name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// List of surfaces
     pub surfaces: Vec<Surface>,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl Surface {
+    /// Surface要素を解析
+    ///
+    /// Pのid属性をpoints配列の添字に変換し、元のidはpoint_idsに保持します。
+    /// 未定義のidを参照する面、頂点が3つでない面はunresolved_facesに保持します。
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let mut surface = Self {
+            name: element.attr_string("name"),
+            desc: element.attr_string("desc"),
+            extensions: element.extensions_by(&["name", "desc"], |position, child| {
+                child.name == "Definition" && element.is_first_named(position)
+            }),
+            ..Default::default()
+        };
+
+        let Some(definition) = element.child("Definition") else {
+            return Ok(surface);
+        };
+        surface.surf_type = definition.attr_string("surfType");
+        surface.definition_extensions =
+            definition.extensions_by(&["surfType"], |position, child| {
+                child.name == "Faces"
+                    || (child.name == "Pnts" && definition.is_first_named(position))
+            });
+
+        let mut index_by_id = HashMap::new();
+        if let Some(pnts) = definition.child("Pnts") {
+            for p in pnts.children_named("P") {
+                let (position, elevation) = p.point()?;
+                let id = match p.attr("id") {
+                    Some(id) => id.trim().to_string(),
+                    None => (surface.points.len() + 1).to_string(),
+                };
+                index_by_id.insert(id.clone(), surface.points.len());
+                surface.point_ids.push(id);
+                surface.points.push(Point3D {
+                    x: position.x,
+                    y: position.y,
+                    z: elevation.unwrap_or(0.0),
+                });
+            }
+        }
+
+        for faces in definition.children_named("Faces") {
+            for face in faces.children_named("F") {
+                let indices: Option<Vec<usize>> = face
+                    .text
+                    .split_whitespace()
+                    .map(|id| index_by_id.get(id).copied())
+                    .collect();
+                match indices.as_deref() {
+                    Some(&[a, b, c]) => surface.triangles.push(Triangle {
+                        vertices: [a, b, c],
+                        invisible: face.attr("i").is_some_and(|i| i.trim() == "1"),
+                    }),
+                    _ => surface.unresolved_faces.push(face.to_raw()),
+                }
+            }
+        }
+
+        Ok(surface)
+    }
+
+    /// Surface要素を生成
+    ///
+    /// Pのid属性は読み込み時のid（ない場合はpoints配列の添字+1）で出力し、
+    /// 非表示の面はi="1"で出力します。
+    /// PntsとFacesは既知要素として扱うため、P・F要素のその他の属性（F@n等）は保持しません。
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        let id = |index: usize| match self.point_ids.get(index) {
+            Some(id) if self.point_ids.len() == self.points.len() => id.clone(),
+            _ => (index + 1).to_string(),
+        };
+        let pnts = XmlElement::new("Pnts").with_children(self.points.iter().enumerate().map(
+            |(index, p)| {
+                XmlElement::new("P")
+                    .with_attr("id", id(index))
+                    .with_text(writer.numbers(&[p.x, p.y, p.z]))
+            },
+        ));
+        let faces = XmlElement::new("Faces").with_children(self.triangles.iter().map(|t| {
+            let [a, b, c] = t.vertices;
+            XmlElement::new("F")
+                .with_opt_attr("i", t.invisible.then_some("1"))
+                .with_text(format!("{} {} {}", id(a), id(b), id(c)))
+        }));
+        let faces = faces.with_children(self.unresolved_faces.iter().map(XmlElement::from_raw));
+
+        XmlElement::new("Surface")
+            .with_opt_attr("name", self.name.as_deref())
+            .with_opt_attr("desc", self.desc.as_deref())
+            .with_child(
+                XmlElement::new("Definition")
+                    .with_attr("surfType", self.surf_type.as_deref().unwrap_or("TIN"))
+                    .with_child(pnts)
+                    .with_child(faces)
+                    .with_extensions(&self.definition_extensions),
+            )
+            .with_extensions(&self.extensions)
+    }
+}
+
+impl Surfaces {
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        Ok(Self {
+            name: element.attr_string("name"),
+            surfaces: element
+                .children_named("Surface")
+                .map(Surface::from_xml)
+                .collect::<Result<Vec<_>, _>>()?,
+            extensions: element.extensions(&["name"], &["Surface"]),
+        })
+    }
+
+    pub(crate) fn to_xml(&self, writer: &LandXmlWriter) -> XmlElement {
+        XmlElement::new("Surfaces")
+            .with_opt_attr("name", self.name.as_deref())
+            .with_children(self.surfaces.iter().map(|surface| surface.to_xml(writer)))
+            .with_extensions(&self.extensions)
+    }
+}
+
+/// 一辺`size`の正方形を`n`×`n`に分割した格子状の三角網（標高は関数で指定）
+#[cfg(test)]
+pub(crate) fn grid_surface(size: f64, n: usize, z: impl Fn(f64, f64) -> f64) -> Surface {
+    let step = size / n as f64;
+    let mut surface = Surface::default();
+    for i in 0..=n {
+        for j in 0..=n {
+            let (x, y) = (i as f64 * step, j as f64 * step);
+            surface.points.push(Point3D { x, y, z: z(x, y) });
+        }
+    }
+    for i in 0..n {
+        for j in 0..n {
+            let v = i * (n + 1) + j;
+            let w = v + n + 1;
+            surface.triangles.push(Triangle::new([v, w, w + 1]));
+            surface.triangles.push(Triangle::new([v, w + 1, v + 1]));
+        }
+    }
+    surface
 }
 
 #[cfg(test)]
@@ -77,9 +266,64 @@ mod tests {
                     z: 0.0,
                 },
             ],
-            triangles: vec![Triangle { vertices: [0, 1, 2] }],
+            triangles: vec![Triangle::new([0, 1, 2])],
+            ..Default::default()
         };
         assert_eq!(surface.name, Some("DesignSurface".to_string()));
         assert_eq!(surface.triangles.len(), 1);
     }
+
+    #[test]
+    fn test_surfaces_from_xml() {
+        let xml = r#"<Surfaces name="地形">
+            <Surface name="地形 1" desc="ExistingGround">
+              <Definition surfType="TIN">
+                <Pnts>
+                  <P id="1">0.0 0.0 10.0</P>
+                  <P id="2">10.0 0.0 11.0</P>
+                  <P id="5">0.0 10.0 12.0</P>
+                </Pnts>
+                <Faces>
+                  <F>1 2 5</F>
+                  <F i="1">2 1 5</F>
+                </Faces>
+              </Definition>
+            </Surface>
+        </Surfaces>"#;
+        let element = XmlElement::parse_str(xml).unwrap();
+        let surfaces = Surfaces::from_xml(&element).unwrap();
+
+        let surface = &surfaces.surfaces[0];
+        assert_eq!(surface.points.len(), 3);
+        assert_eq!(surface.point_ids, ["1", "2", "5"]);
+        assert_eq!(surface.surf_type.as_deref(), Some("TIN"));
+        assert_eq!(surface.triangles.len(), 2);
+        assert_eq!(surface.triangles[0].vertices, [0, 1, 2]);
+        assert!(surface.triangles[1].invisible);
+        // 非表示の面は面積・標高の計算から除く
+        assert!((surface.area_2d() - 50.0).abs() < 1e-9);
+
+        // 元のid・非表示の面を出力
+        let written = surfaces.to_xml(&LandXmlWriter::new().with_precision(1));
+        let definition = written.children[0].child("Definition").unwrap();
+        let p = &definition.child("Pnts").unwrap().children[2];
+        assert_eq!(p.attr("id"), Some("5"));
+        let f = &definition.child("Faces").unwrap().children[1];
+        assert_eq!((f.attr("i"), f.text.as_str()), (Some("1"), "2 1 5"));
+
+        // 未定義のidを参照する面は計算から除き、そのまま再出力する
+        let dangling = xml.replace("<F>1 2 5</F>", "<F>1 2 99</F>");
+        let surfaces = Surfaces::from_xml(&XmlElement::parse_str(&dangling).unwrap()).unwrap();
+        let surface = &surfaces.surfaces[0];
+        assert_eq!(surface.triangles.len(), 1);
+        assert_eq!(surface.unresolved_faces[0].text, "1 2 99");
+        assert_eq!(surface.area_2d(), 0.0);
+        let written = surfaces.to_xml(&LandXmlWriter::new());
+        let faces = written.children[0]
+            .child("Definition")
+            .unwrap()
+            .child("Faces")
+            .unwrap();
+        assert_eq!(faces.children[1].text, "1 2 99");
+    }
 }
@@ -0,0 +1,1074 @@
+//! Constrained Delaunay triangulation
+//!
+//! 点群から三角網（TIN）を生成します：
+//! - 逐次挿入法によるDelaunay三角形分割（Lawsonの辺交換）
+//! - ブレークライン（設計横断の稜線等）を三角形の辺として保持する制約付き分割
+//! - 外周境界・穴による三角形の除去
+//! - 許容距離内の重複点の統合（ブレークライン・境界の標高を優先）
+//!
+//! 座標は測量座標（X北・Y東）で扱い、生成した三角形は正の向きの頂点順です。
+
+use std::collections::VecDeque;
+
+use super::{Surface, Triangle};
+use crate::cg_points::CgPoints;
+use crate::error::LandXMLError;
+use crate::models::{Point2D, Point3D};
+
+/// 同一点とみなす距離の既定値（m）
+const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// 隣接三角形がないことを表す番号
+const NONE: usize = usize::MAX;
+
+/// 外側の仮想三角形の頂点数（分割中の頂点番号の先頭を占める）
+const SUPER: usize = 3;
+
+/// 外接円判定の相対許容誤差（共円の4点で辺交換を繰り返さないため）
+const IN_CIRCLE_TOLERANCE: f64 = 1e-10;
+
+/// 三角網の生成条件
+///
+/// ブレークライン・境界の頂点は点群より先に挿入され、
+/// 重複する点群の点よりも標高が優先されます。
+#[derive(Debug, Clone)]
+pub struct TinBuilder {
+    name: Option<String>,
+    desc: Option<String>,
+    points: Vec<Point3D>,
+    breaklines: Vec<Vec<Point3D>>,
+    boundary: Option<Vec<Point3D>>,
+    holes: Vec<Vec<Point3D>>,
+    tolerance: f64,
+}
+
+impl Default for TinBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TinBuilder {
+    /// 空の条件で作成
+    pub fn new() -> Self {
+        Self {
+            name: None,
+            desc: None,
+            points: Vec::new(),
+            breaklines: Vec::new(),
+            boundary: None,
+            holes: Vec::new(),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// CgPointsから作成（標高のない点は除外）
+    pub fn from_cg_points(points: &CgPoints) -> Self {
+        let mut builder = Self::new().with_points(
+            points
+                .points
+                .iter()
+                .filter(|p| p.has_elevation)
+                .map(|p| p.position),
+        );
+        builder.name = points.name.clone();
+        builder
+    }
+
+    /// 面の名称を設定
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 面の説明を設定
+    pub fn with_desc(mut self, desc: impl Into<String>) -> Self {
+        self.desc = Some(desc.into());
+        self
+    }
+
+    /// 点を追加
+    pub fn with_points(mut self, points: impl IntoIterator<Item = Point3D>) -> Self {
+        self.points.extend(points);
+        self
+    }
+
+    /// ブレークライン（折れ線）を追加
+    pub fn with_breakline(mut self, line: impl IntoIterator<Item = Point3D>) -> Self {
+        self.breaklines.push(line.into_iter().collect());
+        self
+    }
+
+    /// 外周境界（閉じた頂点列、始点の繰り返しは任意）を設定
+    pub fn with_boundary(mut self, ring: impl IntoIterator<Item = Point3D>) -> Self {
+        self.boundary = Some(ring.into_iter().collect());
+        self
+    }
+
+    /// 穴（閉じた頂点列、始点の繰り返しは任意）を追加
+    pub fn with_hole(mut self, ring: impl IntoIterator<Item = Point3D>) -> Self {
+        self.holes.push(ring.into_iter().collect());
+        self
+    }
+
+    /// 同一点とみなす距離を設定
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// 三角網を生成
+    ///
+    /// 重複を除いた点が3点未満の場合は`EmptyPointCloud`、
+    /// 三角形が1つもできない場合（全点が一直線上にある等）は
+    /// `MissingSurfaceDefinition`を返します。
+    pub fn build(&self) -> Result<Surface, LandXMLError> {
+        let mut constraints: Vec<(&[Point3D], EdgeKind, bool)> = Vec::new();
+        if let Some(boundary) = &self.boundary {
+            constraints.push((boundary, EdgeKind::Boundary, true));
+        }
+        for hole in &self.holes {
+            constraints.push((hole, EdgeKind::Boundary, true));
+        }
+        for line in &self.breaklines {
+            constraints.push((line, EdgeKind::Breakline, false));
+        }
+        for (line, _, closed) in &constraints {
+            let required = if *closed { 3 } else { 2 };
+            if line.len() < required || line.iter().any(|p| !is_finite(p)) {
+                return Err(LandXMLError::GeometryError {
+                    message: format!(
+                        "Invalid {} with {} vertices",
+                        if *closed { "boundary" } else { "breakline" },
+                        line.len()
+                    ),
+                });
+            }
+        }
+
+        let mut points: Vec<Point3D> = self.points.iter().copied().filter(is_finite).collect();
+        let all = || {
+            constraints
+                .iter()
+                .flat_map(|(line, _, _)| line.iter())
+                .chain(points.iter())
+        };
+        if all().next().is_none() {
+            return Err(LandXMLError::EmptyPointCloud);
+        }
+        let (mut min, mut max) = (
+            Point2D {
+                x: f64::INFINITY,
+                y: f64::INFINITY,
+            },
+            Point2D {
+                x: f64::NEG_INFINITY,
+                y: f64::NEG_INFINITY,
+            },
+        );
+        for p in all() {
+            min.x = min.x.min(p.x);
+            min.y = min.y.min(p.y);
+            max.x = max.x.max(p.x);
+            max.y = max.y.max(p.y);
+        }
+        // 桁落ちを避けるため外接矩形の中心を原点とした座標で分割する
+        let center = Point2D {
+            x: (min.x + max.x) / 2.0,
+            y: (min.y + max.y) / 2.0,
+        };
+        let extent = (max.x - min.x).max(max.y - min.y) / 2.0;
+        let local = |p: &Point3D| Point2D {
+            x: p.x - center.x,
+            y: p.y - center.y,
+        };
+
+        let mut mesh = Mesh::new(extent, self.tolerance);
+        let mut vertices: Vec<Point3D> = Vec::new();
+        let mut insert = |mesh: &mut Mesh, p: &Point3D| {
+            let (id, added) = mesh.insert(local(p));
+            if added {
+                vertices.push(*p);
+            }
+            id
+        };
+
+        let constraint_ids: Vec<Vec<usize>> = constraints
+            .iter()
+            .map(|(line, _, _)| line.iter().map(|p| insert(&mut mesh, p)).collect())
+            .collect();
+
+        // 走査距離を短くするため、点群は格子を蛇行する順に挿入する
+        let cells = (points.len() as f64).sqrt().ceil().max(1.0);
+        let size = (2.0 * extent / cells).max(f64::MIN_POSITIVE);
+        points.sort_by_cached_key(|p| {
+            let row = ((p.x - min.x) / size) as i64;
+            let col = ((p.y - min.y) / size) as i64;
+            (row, if row % 2 == 0 { col } else { -col })
+        });
+        for p in &points {
+            insert(&mut mesh, p);
+        }
+        if vertices.len() < 3 {
+            return Err(LandXMLError::EmptyPointCloud);
+        }
+
+        for ((_, kind, closed), ids) in constraints.iter().zip(&constraint_ids) {
+            for pair in ids.windows(2) {
+                mesh.insert_constraint(pair[0], pair[1], *kind)?;
+            }
+            if *closed {
+                mesh.insert_constraint(ids[ids.len() - 1], ids[0], *kind)?;
+            }
+        }
+        mesh.restore_delaunay();
+
+        let keep = mesh.interior(self.boundary.is_some());
+        let mut remap = vec![NONE; vertices.len()];
+        let mut surface = Surface {
+            name: self.name.clone(),
+            desc: self.desc.clone(),
+            ..Default::default()
+        };
+        for (t, vertex_ids) in mesh.triangles.iter().enumerate() {
+            if !keep[t] {
+                continue;
+            }
+            let indices = vertex_ids.map(|v| {
+                let v = v - SUPER;
+                if remap[v] == NONE {
+                    remap[v] = surface.points.len();
+                    surface.points.push(vertices[v]);
+                }
+                remap[v]
+            });
+            surface.triangles.push(Triangle::new(indices));
+        }
+        if surface.triangles.is_empty() {
+            return Err(LandXMLError::MissingSurfaceDefinition);
+        }
+        Ok(surface)
+    }
+}
+
+impl Surface {
+    /// 点群からDelaunay三角網を生成
+    ///
+    /// ブレークライン・境界を指定する場合は[`TinBuilder`]を使用してください。
+    pub fn from_points(points: impl IntoIterator<Item = Point3D>) -> Result<Self, LandXMLError> {
+        TinBuilder::new().with_points(points).build()
+    }
+}
+
+fn is_finite(p: &Point3D) -> bool {
+    p.x.is_finite() && p.y.is_finite() && p.z.is_finite()
+}
+
+// ============================================================================
+// 三角形分割
+// ============================================================================
+
+/// 辺の種類（制約の強い順に大きい）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EdgeKind {
+    Free,
+    Breakline,
+    Boundary,
+}
+
+/// 点の位置
+enum Location {
+    /// 既存の頂点と一致
+    Vertex(usize),
+    /// 三角形の辺上（三角形番号, 対頂点の位置）
+    Edge(usize, usize),
+    /// 三角形の内部
+    Inside(usize),
+}
+
+/// 制約辺が横切る要素
+enum Crossing {
+    /// 線分上の頂点（ここで制約辺を分割する）
+    Vertex(usize),
+    /// 横切る辺の列
+    Edges(Vec<(usize, usize)>),
+}
+
+/// 分割中の三角網
+///
+/// 三角形は正の向きの頂点列で、k番目の隣接三角形・辺の種類は
+/// k番目の頂点の対辺に対応します。
+struct Mesh {
+    points: Vec<Point2D>,
+    triangles: Vec<[usize; 3]>,
+    neighbors: Vec<[usize; 3]>,
+    kinds: Vec<[EdgeKind; 3]>,
+    /// 頂点を含む三角形の1つ
+    vertex_triangle: Vec<usize>,
+    /// 直前に挿入した三角形（点の探索の起点）
+    last: usize,
+    tolerance: f64,
+}
+
+fn orient(a: Point2D, b: Point2D, c: Point2D) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn distance(a: Point2D, b: Point2D) -> f64 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+/// 点dが正の向きの三角形abcの外接円の内側にあるか
+fn in_circle(a: Point2D, b: Point2D, c: Point2D, d: Point2D) -> bool {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+    let (al, bl, cl) = (
+        adx * adx + ady * ady,
+        bdx * bdx + bdy * bdy,
+        cdx * cdx + cdy * cdy,
+    );
+    let det =
+        al * (bdx * cdy - cdx * bdy) + bl * (cdx * ady - adx * cdy) + cl * (adx * bdy - bdx * ady);
+    let magnitude = al * ((bdx * cdy).abs() + (cdx * bdy).abs())
+        + bl * ((cdx * ady).abs() + (adx * cdy).abs())
+        + cl * ((adx * bdy).abs() + (bdx * ady).abs());
+    det > IN_CIRCLE_TOLERANCE * magnitude
+}
+
+/// 線分abと線分cdが端点以外で交差するか
+fn segments_cross(a: Point2D, b: Point2D, c: Point2D, d: Point2D) -> bool {
+    orient(a, b, c) * orient(a, b, d) < 0.0 && orient(c, d, a) * orient(c, d, b) < 0.0
+}
+
+fn intersecting_constraints() -> LandXMLError {
+    LandXMLError::GeometryError {
+        message: "Breaklines or boundaries intersect".to_string(),
+    }
+}
+
+impl Mesh {
+    /// 半径extentの範囲を十分に含む仮想三角形で初期化
+    fn new(extent: f64, tolerance: f64) -> Self {
+        let m = extent.max(1.0) * 1000.0;
+        Self {
+            points: vec![
+                Point2D { x: -m, y: -m },
+                Point2D { x: m, y: -m },
+                Point2D { x: 0.0, y: m },
+            ],
+            triangles: vec![[0, 1, 2]],
+            neighbors: vec![[NONE; 3]],
+            kinds: vec![[EdgeKind::Free; 3]],
+            vertex_triangle: vec![0; SUPER],
+            last: 0,
+            tolerance,
+        }
+    }
+
+    fn set(&mut self, t: usize, vertices: [usize; 3], neighbors: [usize; 3], kinds: [EdgeKind; 3]) {
+        self.triangles[t] = vertices;
+        self.neighbors[t] = neighbors;
+        self.kinds[t] = kinds;
+        for v in vertices {
+            self.vertex_triangle[v] = t;
+        }
+    }
+
+    fn add_triangle(&mut self) -> usize {
+        self.triangles.push([0; 3]);
+        self.neighbors.push([NONE; 3]);
+        self.kinds.push([EdgeKind::Free; 3]);
+        self.triangles.len() - 1
+    }
+
+    /// 三角形nの隣接をoldからnewに付け替える
+    fn relink(&mut self, n: usize, old: usize, new: usize) {
+        if n == NONE {
+            return;
+        }
+        for k in 0..3 {
+            if self.neighbors[n][k] == old {
+                self.neighbors[n][k] = new;
+            }
+        }
+    }
+
+    fn position(&self, t: usize, v: usize) -> usize {
+        self.triangles[t].iter().position(|&x| x == v).unwrap_or(0)
+    }
+
+    /// 三角形tのk番目の対辺の向こう側（三角形, 対頂点の位置, 対頂点）
+    fn opposite(&self, t: usize, k: usize) -> Option<(usize, usize, usize)> {
+        let u = self.neighbors[t][k];
+        if u == NONE {
+            return None;
+        }
+        let m = self.neighbors[u].iter().position(|&n| n == t)?;
+        Some((u, m, self.triangles[u][m]))
+    }
+
+    // ------------------------------------------------------------------------
+    // 点の挿入
+    // ------------------------------------------------------------------------
+
+    /// 点を挿入して頂点番号と新規かどうかを返す
+    fn insert(&mut self, p: Point2D) -> (usize, bool) {
+        let location = self.locate(p);
+        let id = self.points.len();
+        match location {
+            Location::Vertex(v) => return (v, false),
+            Location::Edge(t, k) => {
+                // 辺上に投影し、許容誤差内のずれで三角形が反転しないようにする
+                let v = self.triangles[t];
+                let (a, b) = (self.points[v[(k + 1) % 3]], self.points[v[(k + 2) % 3]]);
+                let s = ((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y))
+                    / ((b.x - a.x).powi(2) + (b.y - a.y).powi(2));
+                self.points.push(Point2D {
+                    x: a.x + (b.x - a.x) * s,
+                    y: a.y + (b.y - a.y) * s,
+                });
+                self.vertex_triangle.push(t);
+                self.split_edge(t, k, id);
+            }
+            Location::Inside(t) => {
+                self.points.push(p);
+                self.vertex_triangle.push(t);
+                self.split(t, id);
+            }
+        }
+        (id, true)
+    }
+
+    /// 点を含む三角形を隣接をたどって探索
+    fn locate(&self, p: Point2D) -> Location {
+        let mut t = self.last;
+        for step in 0..self.triangles.len() + 16 {
+            let v = self.triangles[t];
+            let next = (0..3).map(|i| (i + step) % 3).find(|&k| {
+                self.neighbors[t][k] != NONE
+                    && orient(self.points[v[(k + 1) % 3]], self.points[v[(k + 2) % 3]], p) < 0.0
+            });
+            match next {
+                Some(k) => t = self.neighbors[t][k],
+                None => return self.classify(t, p),
+            }
+        }
+        // 数値誤差で走査が循環した場合は全探索
+        let best = (0..self.triangles.len())
+            .max_by(|&s, &t| {
+                let margin = |t: usize| {
+                    let v = self.triangles[t].map(|i| self.points[i]);
+                    (0..3)
+                        .map(|k| orient(v[(k + 1) % 3], v[(k + 2) % 3], p))
+                        .fold(f64::INFINITY, f64::min)
+                };
+                margin(s).total_cmp(&margin(t))
+            })
+            .unwrap_or(0);
+        self.classify(best, p)
+    }
+
+    fn classify(&self, t: usize, p: Point2D) -> Location {
+        let v = self.triangles[t];
+        if let Some(&i) = v
+            .iter()
+            .find(|&&i| distance(self.points[i], p) <= self.tolerance)
+        {
+            return Location::Vertex(i);
+        }
+        for k in 0..3 {
+            let (a, b) = (self.points[v[(k + 1) % 3]], self.points[v[(k + 2) % 3]]);
+            if orient(a, b, p).abs() <= self.tolerance * distance(a, b) {
+                return Location::Edge(t, k);
+            }
+        }
+        Location::Inside(t)
+    }
+
+    /// 三角形tの内部の点pで3分割
+    fn split(&mut self, t: usize, p: usize) {
+        let [a, b, c] = self.triangles[t];
+        let [na, nb, nc] = self.neighbors[t];
+        let [ka, kb, kc] = self.kinds[t];
+        let free = EdgeKind::Free;
+        let t1 = self.add_triangle();
+        let t2 = self.add_triangle();
+        self.set(t, [a, b, p], [t1, t2, nc], [free, free, kc]);
+        self.set(t1, [b, c, p], [t2, t, na], [free, free, ka]);
+        self.set(t2, [c, a, p], [t, t1, nb], [free, free, kb]);
+        self.relink(na, t, t1);
+        self.relink(nb, t, t2);
+        self.last = t;
+        self.legalize(vec![(t, 2), (t1, 2), (t2, 2)]);
+    }
+
+    /// 三角形tのk番目の対辺上の点pで辺を分割
+    fn split_edge(&mut self, t: usize, k: usize, p: usize) {
+        let Some((u, m, d)) = self.opposite(t, k) else {
+            self.split(t, p);
+            return;
+        };
+        let at = |values: [usize; 3], i: usize| values[(k + i) % 3];
+        let (v0, v1, v2) = (
+            at(self.triangles[t], 0),
+            at(self.triangles[t], 1),
+            at(self.triangles[t], 2),
+        );
+        let (n1, n2) = (at(self.neighbors[t], 1), at(self.neighbors[t], 2));
+        let [k0, k1, k2] = [0, 1, 2].map(|i| self.kinds[t][(k + i) % 3]);
+        let (un1, un2) = (
+            self.neighbors[u][(m + 1) % 3],
+            self.neighbors[u][(m + 2) % 3],
+        );
+        let (uk1, uk2) = (self.kinds[u][(m + 1) % 3], self.kinds[u][(m + 2) % 3]);
+        let free = EdgeKind::Free;
+
+        let t1 = self.add_triangle();
+        let u1 = self.add_triangle();
+        // 分割した辺は元の辺の種類を引き継ぐ
+        self.set(t, [v0, v1, p], [u1, t1, n2], [k0, free, k2]);
+        self.set(t1, [v0, p, v2], [u, n1, t], [k0, k1, free]);
+        self.set(u, [d, v2, p], [t1, u1, un2], [k0, free, uk2]);
+        self.set(u1, [d, p, v1], [t, un1, u], [k0, uk1, free]);
+        self.relink(n1, t, t1);
+        self.relink(un1, u, u1);
+        self.last = t;
+        self.legalize(vec![(t, 2), (t1, 1), (u, 2), (u1, 1)]);
+    }
+
+    /// 三角形tのk番目の対辺を交換
+    ///
+    /// 交換後の2つの三角形は、いずれも元のk番目の頂点を先頭に持ちます。
+    fn flip(&mut self, t: usize, k: usize) -> Option<(usize, usize)> {
+        let (u, m, d) = self.opposite(t, k)?;
+        let v = self.triangles[t];
+        let (p, a, b) = (v[k], v[(k + 1) % 3], v[(k + 2) % 3]);
+        let (a1, a2) = (
+            self.neighbors[t][(k + 2) % 3],
+            self.neighbors[t][(k + 1) % 3],
+        );
+        let (ka1, ka2) = (self.kinds[t][(k + 2) % 3], self.kinds[t][(k + 1) % 3]);
+        let (b1, b2) = (
+            self.neighbors[u][(m + 2) % 3],
+            self.neighbors[u][(m + 1) % 3],
+        );
+        let (kb1, kb2) = (self.kinds[u][(m + 2) % 3], self.kinds[u][(m + 1) % 3]);
+        let free = EdgeKind::Free;
+        self.set(t, [p, a, d], [b2, u, a1], [kb2, free, ka1]);
+        self.set(u, [p, d, b], [b1, a2, t], [kb1, ka2, free]);
+        self.relink(b2, u, t);
+        self.relink(a2, t, u);
+        Some((t, u))
+    }
+
+    /// 辺を交換できるか（2つの三角形でできる四角形が凸）
+    fn flippable(&self, t: usize, k: usize, d: usize) -> bool {
+        let v = self.triangles[t].map(|i| self.points[i]);
+        let (p, a, b) = (v[k], v[(k + 1) % 3], v[(k + 2) % 3]);
+        let d = self.points[d];
+        orient(p, a, d) > 0.0 && orient(p, d, b) > 0.0
+    }
+
+    /// 辺がDelaunay条件を満たさない場合に交換
+    fn flip_if_illegal(&mut self, t: usize, k: usize) -> Option<(usize, usize)> {
+        if self.kinds[t][k] != EdgeKind::Free {
+            return None;
+        }
+        let (_, _, d) = self.opposite(t, k)?;
+        let v = self.triangles[t].map(|i| self.points[i]);
+        if !in_circle(v[0], v[1], v[2], self.points[d]) || !self.flippable(t, k, d) {
+            return None;
+        }
+        self.flip(t, k)
+    }
+
+    /// 挿入した点の対辺を再帰的に交換（点は各三角形のk番目の頂点）
+    fn legalize(&mut self, mut stack: Vec<(usize, usize)>) {
+        while let Some((t, k)) = stack.pop() {
+            if let Some((t, u)) = self.flip_if_illegal(t, k) {
+                stack.push((t, 0));
+                stack.push((u, 0));
+            }
+        }
+    }
+
+    /// 制約辺の挿入で崩れたDelaunay条件を制約辺以外で回復
+    fn restore_delaunay(&mut self) {
+        let mut stack: Vec<(usize, usize)> = (0..self.triangles.len())
+            .flat_map(|t| (0..3).map(move |k| (t, k)))
+            .collect();
+        // 数値誤差による交換の循環を防ぐ上限
+        let mut budget = stack.len() * 16;
+        while let Some((t, k)) = stack.pop() {
+            if budget == 0 {
+                break;
+            }
+            budget -= 1;
+            if let Some((t, u)) = self.flip_if_illegal(t, k) {
+                stack.extend([(t, 0), (t, 2), (u, 0), (u, 1)]);
+            }
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // 制約辺
+    // ------------------------------------------------------------------------
+
+    /// 頂点vを含む三角形
+    fn around(&self, v: usize) -> Vec<usize> {
+        let start = self.vertex_triangle[v];
+        let mut result = vec![start];
+        let mut t = start;
+        loop {
+            let n = self.neighbors[t][(self.position(t, v) + 2) % 3];
+            if n == start || result.len() > self.triangles.len() {
+                return result;
+            }
+            if n == NONE {
+                break;
+            }
+            result.push(n);
+            t = n;
+        }
+        // 外周の頂点は逆回りにもたどる
+        t = start;
+        loop {
+            let n = self.neighbors[t][(self.position(t, v) + 1) % 3];
+            if n == NONE || result.len() > self.triangles.len() {
+                return result;
+            }
+            result.push(n);
+            t = n;
+        }
+    }
+
+    /// 辺abを持つ三角形と対頂点の位置
+    fn find_edge(&self, a: usize, b: usize) -> Option<(usize, usize)> {
+        self.around(a).into_iter().find_map(|t| {
+            let i = self.position(t, a);
+            let v = self.triangles[t];
+            if v[(i + 1) % 3] == b {
+                Some((t, (i + 2) % 3))
+            } else if v[(i + 2) % 3] == b {
+                Some((t, (i + 1) % 3))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// 既存の辺abに種類を設定（辺がない場合はfalse）
+    fn mark(&mut self, a: usize, b: usize, kind: EdgeKind) -> bool {
+        let Some((t, k)) = self.find_edge(a, b) else {
+            return false;
+        };
+        let kind = kind.max(self.kinds[t][k]);
+        self.kinds[t][k] = kind;
+        if let Some((u, m, _)) = self.opposite(t, k) {
+            self.kinds[u][m] = kind;
+        }
+        true
+    }
+
+    /// 頂点aからbへの制約辺を挿入
+    fn insert_constraint(
+        &mut self,
+        a: usize,
+        b: usize,
+        kind: EdgeKind,
+    ) -> Result<(), LandXMLError> {
+        let mut pending = vec![(a, b)];
+        while let Some((a, b)) = pending.pop() {
+            if a == b || self.mark(a, b, kind) {
+                continue;
+            }
+            match self.crossings(a, b)? {
+                Crossing::Vertex(v) => {
+                    pending.push((v, b));
+                    pending.push((a, v));
+                }
+                Crossing::Edges(edges) => {
+                    self.recover(a, b, edges)?;
+                    if !self.mark(a, b, kind) {
+                        return Err(LandXMLError::GeometryError {
+                            message: "Failed to insert constraint edge".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 線分abが横切る辺、または線分上の最初の頂点を求める
+    fn crossings(&self, a: usize, b: usize) -> Result<Crossing, LandXMLError> {
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let on_line = |p: Point2D| orient(pa, pb, p).abs() <= self.tolerance * distance(pa, pb);
+        let ahead = |p: Point2D| (p.x - pa.x) * (pb.x - pa.x) + (p.y - pa.y) * (pb.y - pa.y) > 0.0;
+        let fan = self.around(a);
+
+        for &t in &fan {
+            let i = self.position(t, a);
+            for w in [
+                self.triangles[t][(i + 1) % 3],
+                self.triangles[t][(i + 2) % 3],
+            ] {
+                if w >= SUPER && on_line(self.points[w]) && ahead(self.points[w]) {
+                    return Ok(Crossing::Vertex(w));
+                }
+            }
+        }
+        let (mut t, mut k) = fan
+            .iter()
+            .find_map(|&t| {
+                let i = self.position(t, a);
+                let v = self.triangles[t];
+                let (v1, v2) = (self.points[v[(i + 1) % 3]], self.points[v[(i + 2) % 3]]);
+                (orient(pa, pb, v1) < 0.0 && orient(pa, pb, v2) > 0.0).then_some((t, i))
+            })
+            .ok_or_else(intersecting_constraints)?;
+
+        let mut edges = Vec::new();
+        for _ in 0..self.triangles.len() {
+            if self.kinds[t][k] != EdgeKind::Free {
+                return Err(intersecting_constraints());
+            }
+            let v = self.triangles[t];
+            edges.push((v[(k + 1) % 3], v[(k + 2) % 3]));
+            let (u, m, w) = self.opposite(t, k).ok_or_else(intersecting_constraints)?;
+            if w == b {
+                return Ok(Crossing::Edges(edges));
+            }
+            if on_line(self.points[w]) {
+                return Ok(Crossing::Vertex(w));
+            }
+            // wと同じ側の頂点の対辺から抜ける
+            let left = orient(pa, pb, self.points[w]) > 0.0;
+            let x1 = self.points[self.triangles[u][(m + 1) % 3]];
+            k = if (orient(pa, pb, x1) > 0.0) == left {
+                (m + 1) % 3
+            } else {
+                (m + 2) % 3
+            };
+            t = u;
+        }
+        Err(intersecting_constraints())
+    }
+
+    /// 線分abを横切る辺を交換して辺abを作る（Sloanの方法）
+    fn recover(
+        &mut self,
+        a: usize,
+        b: usize,
+        edges: Vec<(usize, usize)>,
+    ) -> Result<(), LandXMLError> {
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let mut queue: VecDeque<(usize, usize)> = edges.into();
+        let mut stalled = 0;
+        while let Some((x, y)) = queue.pop_front() {
+            let Some((t, k)) = self.find_edge(x, y) else {
+                continue;
+            };
+            let Some((_, _, d)) = self.opposite(t, k) else {
+                continue;
+            };
+            if !self.flippable(t, k, d) {
+                // 凹四角形の辺は他の辺を交換した後に再試行する
+                stalled += 1;
+                if stalled > queue.len() + 1 {
+                    return Err(LandXMLError::GeometryError {
+                        message: "Failed to insert constraint edge".to_string(),
+                    });
+                }
+                queue.push_back((x, y));
+                continue;
+            }
+            stalled = 0;
+            let p = self.triangles[t][k];
+            self.flip(t, k);
+            if ![a, b].contains(&p)
+                && ![a, b].contains(&d)
+                && segments_cross(pa, pb, self.points[p], self.points[d])
+            {
+                queue.push_back((p, d));
+            }
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------------
+    // 範囲外の除去
+    // ------------------------------------------------------------------------
+
+    /// 残す三角形
+    ///
+    /// 外側（仮想三角形の頂点を含む三角形）から境界の辺を越えた回数を数え、
+    /// 外周境界がある場合は奇数回、ない場合は偶数回（穴の外）の三角形を残します。
+    fn interior(&self, has_boundary: bool) -> Vec<bool> {
+        let count = self.triangles.len();
+        let mut depth = vec![usize::MAX; count];
+        let mut queue = VecDeque::new();
+        for (t, vertices) in self.triangles.iter().enumerate() {
+            if vertices.iter().any(|&v| v < SUPER) {
+                depth[t] = 0;
+                queue.push_back(t);
+            }
+        }
+        while let Some(t) = queue.pop_front() {
+            for k in 0..3 {
+                let u = self.neighbors[t][k];
+                if u == NONE {
+                    continue;
+                }
+                let crossing = self.kinds[t][k] == EdgeKind::Boundary;
+                let d = depth[t] + usize::from(crossing);
+                if d < depth[u] {
+                    depth[u] = d;
+                    if crossing {
+                        queue.push_back(u);
+                    } else {
+                        queue.push_front(u);
+                    }
+                }
+            }
+        }
+        (0..count)
+            .map(|t| {
+                self.triangles[t].iter().all(|&v| v >= SUPER)
+                    && depth[t] != usize::MAX
+                    && (depth[t] % 2 == 1) == has_boundary
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn p(x: f64, y: f64, z: f64) -> Point3D {
+        Point3D { x, y, z }
+    }
+
+    /// 10m四方の1m格子
+    fn grid() -> Vec<Point3D> {
+        (0..=10)
+            .flat_map(|i| (0..=10).map(move |j| p(i as f64, j as f64, 0.0)))
+            .collect()
+    }
+
+    fn edges(surface: &Surface) -> HashSet<(usize, usize)> {
+        surface
+            .triangles
+            .iter()
+            .flat_map(|t| {
+                let [a, b, c] = t.vertices;
+                [(a, b), (b, c), (c, a)]
+            })
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect()
+    }
+
+    fn has_edge(surface: &Surface, a: (f64, f64), b: (f64, f64)) -> bool {
+        let find = |(x, y): (f64, f64)| {
+            surface
+                .points
+                .iter()
+                .position(|q| (q.x - x).abs() < 1e-9 && (q.y - y).abs() < 1e-9)
+                .unwrap()
+        };
+        let (i, j) = (find(a), find(b));
+        edges(surface).contains(&(i.min(j), i.max(j)))
+    }
+
+    #[test]
+    fn test_delaunay_points() {
+        // 疑似乱数による点群
+        let mut seed: u64 = 12345;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1u64 << 53) as f64
+        };
+        let points: Vec<Point3D> = (0..300)
+            .map(|_| {
+                p(
+                    1000.0 + random() * 100.0,
+                    2000.0 + random() * 50.0,
+                    random(),
+                )
+            })
+            .collect();
+        let surface = Surface::from_points(points.clone()).unwrap();
+        assert_eq!(surface.points.len(), 300);
+
+        // 全ての三角形が正の向きで、外接円の内側に他の点を含まない
+        for t in &surface.triangles {
+            let v = surface.triangle_vertices(t).unwrap().map(|q| Point2D {
+                x: q.x - 1000.0,
+                y: q.y - 2000.0,
+            });
+            assert!(orient(v[0], v[1], v[2]) > 0.0);
+            for q in &surface.points {
+                let q = Point2D {
+                    x: q.x - 1000.0,
+                    y: q.y - 2000.0,
+                };
+                assert!(!in_circle(v[0], v[1], v[2], q) || v.contains(&q));
+            }
+        }
+
+        // 面積は凸包の面積に一致
+        use geo::{Area, ConvexHull};
+        let hull = geo::MultiPoint::from(
+            points
+                .iter()
+                .map(|q| geo::Point::new(q.y, q.x))
+                .collect::<Vec<_>>(),
+        )
+        .convex_hull()
+        .unsigned_area();
+        assert!((surface.area_2d() - hull).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_breaklines() {
+        // 細長い菱形：Delaunay分割では短い対角線CDが選ばれる
+        let rhombus = vec![
+            p(0.0, -10.0, 0.0),
+            p(0.0, 10.0, 0.0),
+            p(-1.0, 0.0, 5.0),
+            p(1.0, 0.0, 5.0),
+        ];
+        let surface = Surface::from_points(rhombus.clone()).unwrap();
+        assert!(has_edge(&surface, (-1.0, 0.0), (1.0, 0.0)));
+        let surface = TinBuilder::new()
+            .with_points(rhombus.clone())
+            .with_breakline(vec![rhombus[0], rhombus[1]])
+            .build()
+            .unwrap();
+        assert_eq!(surface.triangles.len(), 2);
+        assert!(has_edge(&surface, (0.0, -10.0), (0.0, 10.0)));
+
+        // 格子点を通る2本の対角線（交点は格子点）
+        let surface = TinBuilder::new()
+            .with_points(grid())
+            .with_breakline(vec![p(0.0, 0.0, 0.0), p(10.0, 10.0, 0.0)])
+            .with_breakline(vec![p(1.0, 9.0, 0.0), p(9.0, 1.0, 0.0)])
+            .build()
+            .unwrap();
+        assert_eq!(surface.points.len(), 121);
+        for i in 0..10 {
+            let (a, b) = (i as f64, (i + 1) as f64);
+            assert!(has_edge(&surface, (a, a), (b, b)));
+        }
+        for i in 1..9 {
+            let (a, b) = (i as f64, (i + 1) as f64);
+            assert!(has_edge(&surface, (a, 10.0 - a), (b, 10.0 - b)));
+        }
+
+        // 格子に沿わない稜線
+        let surface = TinBuilder::new()
+            .with_points(grid())
+            .with_breakline(vec![p(0.5, 0.2, 3.0), p(4.5, 9.7, 3.0), p(9.8, 4.1, 3.0)])
+            .build()
+            .unwrap();
+        assert_eq!(surface.points.len(), 124);
+        assert!(has_edge(&surface, (0.5, 0.2), (4.5, 9.7)));
+        assert!(has_edge(&surface, (4.5, 9.7), (9.8, 4.1)));
+        assert!((surface.area_2d() - 100.0).abs() < 1e-9);
+        // ブレークラインの標高で補間される
+        let z = surface.elevation_at(2.5, 4.95).unwrap();
+        assert!((z - 3.0).abs() < 1e-9);
+
+        // 格子点を通らずに交差するブレークラインはエラー
+        let result = TinBuilder::new()
+            .with_points(grid())
+            .with_breakline(vec![p(0.5, 0.5, 0.0), p(9.5, 9.7, 0.0)])
+            .with_breakline(vec![p(0.5, 9.5, 0.0), p(9.5, 0.3, 0.0)])
+            .build();
+        assert!(matches!(result, Err(LandXMLError::GeometryError { .. })));
+    }
+
+    #[test]
+    fn test_boundary_and_holes() {
+        let square = |min: f64, max: f64| {
+            vec![
+                p(min, min, 0.0),
+                p(max, min, 0.0),
+                p(max, max, 0.0),
+                p(min, max, 0.0),
+            ]
+        };
+        let surface = TinBuilder::new()
+            .with_name("Design")
+            .with_points(grid())
+            .with_boundary(square(1.0, 9.0))
+            .with_hole(square(4.0, 6.0))
+            .build()
+            .unwrap();
+        assert_eq!(surface.name.as_deref(), Some("Design"));
+        assert!((surface.area_2d() - 60.0).abs() < 1e-9);
+        assert!(surface.elevation_at(5.0, 5.0).is_none());
+        assert!(surface.elevation_at(0.5, 5.0).is_none());
+        // 境界外の点は出力しない
+        assert_eq!(surface.points.len(), 81 - 1);
+
+        // 格子に沿わない三角形の境界・境界のみの穴
+        let surface = TinBuilder::new()
+            .with_points(grid())
+            .with_boundary(vec![p(0.0, 0.0, 0.0), p(10.0, 0.0, 0.0), p(0.0, 10.0, 0.0)])
+            .build()
+            .unwrap();
+        assert!((surface.area_2d() - 50.0).abs() < 1e-9);
+        let surface = TinBuilder::new()
+            .with_points(grid())
+            .with_hole(vec![p(2.5, 2.5, 0.0), p(7.5, 2.5, 0.0), p(5.0, 7.5, 0.0)])
+            .build()
+            .unwrap();
+        assert!((surface.area_2d() - (100.0 - 12.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cg_points_and_errors() {
+        let mut cg_points = CgPoints {
+            name: Some("Survey".to_string()),
+            ..Default::default()
+        };
+        for (i, (x, y)) in [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0), (5.0, 5.0)]
+            .iter()
+            .enumerate()
+        {
+            cg_points.points.push(crate::cg_points::CgPoint {
+                name: Some(i.to_string()),
+                desc: None,
+                code: None,
+                position: p(*x, *y, 1.0),
+                has_elevation: i < 3,
+                point_type: None,
+                extensions: Default::default(),
+            });
+        }
+        let surface = TinBuilder::from_cg_points(&cg_points).build().unwrap();
+        assert_eq!(surface.name.as_deref(), Some("Survey"));
+        assert_eq!(surface.points.len(), 3);
+        assert_eq!(surface.triangles.len(), 1);
+
+        assert!(matches!(
+            Surface::from_points(Vec::new()),
+            Err(LandXMLError::EmptyPointCloud)
+        ));
+        // 許容距離内の重複点は統合される
+        assert!(matches!(
+            Surface::from_points(vec![p(0.0, 0.0, 0.0), p(1.0, 0.0, 0.0), p(0.0, 0.0, 1e-7)]),
+            Err(LandXMLError::EmptyPointCloud)
+        ));
+        assert!(matches!(
+            Surface::from_points((0..5).map(|i| p(i as f64, i as f64 * 2.0, 0.0))),
+            Err(LandXMLError::MissingSurfaceDefinition)
+        ));
+    }
+}
@@ -0,0 +1,435 @@
+//! Surface volume computation
+//!
+//! 面と面の間の土量（切土・盛土）を計算します：
+//! - 重なり合う三角形の角柱の厳密な交差による体積
+//! - 任意の境界多角形（穴あき・非凸可）による計算範囲の限定
+//! - 切土・盛土・差引土量と計算対象面積
+//! - 一定標高・任意平面・境界に当てはめた平面を基準とする体積（ストックパイル・ピット）
+//! - 平面投影面積と表面積（斜面積）
+//!
+//! 体積は立方メートル、面積は平方メートル（モデルの基準単位）で、
+//! SurfVolumeのvolCut/volFillやcrossSectSurfaceVolumeの値と比較できます。
+
+use nalgebra::{Matrix3, Vector3};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::clip::{
+    clip_convex, clip_half_plane, oriented_triangle, plane_of, translated, Moments, Region,
+};
+use super::Surface;
+use crate::error::LandXMLError;
+use crate::models::Point2D;
+
+/// 土量計算の結果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct VolumeResult {
+    /// 切土量（基準面が比較面より高い部分）
+    pub cut: f64,
+    /// 盛土量（比較面が基準面より高い部分）
+    pub fill: f64,
+    /// 差引土量（盛土量 − 切土量）
+    pub net: f64,
+    /// 計算対象の面積（平面投影）
+    pub area: f64,
+}
+
+/// 体積計算の基準面
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReferencePlane {
+    /// 一定の標高
+    Elevation(f64),
+    /// 平面 z = a·x + b·y + c（X北・Y東）
+    Plane { a: f64, b: f64, c: f64 },
+    /// 境界多角形の頂点における面の標高に最小二乗法で当てはめた平面
+    BestFit,
+}
+
+/// 基準面との体積計算の結果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlaneVolumeResult {
+    /// 基準面より上の体積（ストックパイル等）
+    pub above: f64,
+    /// 基準面より下の体積（ピット等）
+    pub below: f64,
+    /// 差引体積（上 − 下）
+    pub net: f64,
+    /// 平面投影面積
+    pub area_2d: f64,
+    /// 表面積（斜面積）
+    pub area_3d: f64,
+    /// 使用した基準面の係数 [a, b, c]（z = a·x + b·y + c）
+    pub plane: [f64; 3],
+}
+
+impl VolumeResult {
+    fn combine(self, other: Self) -> Self {
+        Self {
+            cut: self.cut + other.cut,
+            fill: self.fill + other.fill,
+            net: self.net + other.net,
+            area: self.area + other.area,
+        }
+    }
+
+    /// 凸多角形上で高低差 d = a·x + b·y + c（正は切土）を積分して加算
+    ///
+    /// 多角形と高低差は`origin`を原点とする局所座標です。加算した計算対象面積を返します。
+    pub(crate) fn accumulate(
+        &mut self,
+        polygon: &[Point2D],
+        d: [f64; 3],
+        region: Option<&Region>,
+        origin: Point2D,
+    ) -> f64 {
+        let moments = |part: &[Point2D]| match region {
+            _ if part.len() < 3 => Moments::default(),
+            Some(region) => region.moments_within(part, origin),
+            None => Moments::of(part),
+        };
+        let [a, b, c] = d;
+        let above = moments(&clip_half_plane(polygon, d));
+        let below = moments(&clip_half_plane(polygon, [-a, -b, -c]));
+        let cut = above.integrate(d).max(0.0);
+        let fill = (-below.integrate(d)).max(0.0);
+        self.cut += cut;
+        self.fill += fill;
+        self.net += fill - cut;
+        // 高低差が0の部分は上下の両方に含まれるため、面積は全体から求める
+        let area = moments(polygon).area;
+        self.area += area;
+        area
+    }
+}
+
+impl Surface {
+    /// この面（基準面、現況地盤等）と比較面（計画面等）の間の土量を計算
+    ///
+    /// 両面が重なる範囲の三角形の組ごとに平面投影の共通部分を求め、
+    /// 高低差を基準面の三角形の頂点を原点とする局所座標で厳密に積分します。
+    /// `boundary`はGIS座標（x東・y北）の多角形で、指定した場合はその内側のみを計算対象とします。
+    pub fn volume_between(
+        &self,
+        comparison: &Surface,
+        boundary: Option<&geo::Polygon<f64>>,
+    ) -> VolumeResult {
+        let region = boundary.map(Region::from_polygon);
+        let index = comparison.index();
+
+        self.triangles
+            .par_iter()
+            .filter_map(|t| self.triangle_vertices(t))
+            .map(|base| {
+                let mut result = VolumeResult::default();
+                let origin = Point2D {
+                    x: base[0].x,
+                    y: base[0].y,
+                };
+                let local = translated(base, origin);
+                let Some(base_plane) = plane_of(local) else {
+                    return result;
+                };
+                let base_polygon = oriented_triangle(local);
+                let min = Point2D {
+                    x: base.iter().map(|p| p.x).fold(f64::INFINITY, f64::min),
+                    y: base.iter().map(|p| p.y).fold(f64::INFINITY, f64::min),
+                };
+                let max = Point2D {
+                    x: base.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max),
+                    y: base.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max),
+                };
+
+                for j in index.triangles_in(min, max) {
+                    let Some(other) = comparison.triangle_vertices(&comparison.triangles[j]) else {
+                        continue;
+                    };
+                    let other = translated(other, origin);
+                    let Some(other_plane) = plane_of(other) else {
+                        continue;
+                    };
+                    let overlap = clip_convex(&base_polygon, &oriented_triangle(other));
+                    if overlap.is_empty() {
+                        continue;
+                    }
+                    let d = [0, 1, 2].map(|k| base_plane[k] - other_plane[k]);
+                    result.accumulate(&overlap, d, region.as_ref(), origin);
+                }
+                result
+            })
+            .reduce(VolumeResult::default, VolumeResult::combine)
+    }
+
+    /// 基準面との間の体積と面積を計算
+    ///
+    /// `boundary`はGIS座標（x東・y北）の多角形で、指定した場合はその内側のみを
+    /// 計算対象とします。[`ReferencePlane::BestFit`]には境界の指定が必要です。
+    pub fn volume_to_plane(
+        &self,
+        reference: ReferencePlane,
+        boundary: Option<&geo::Polygon<f64>>,
+    ) -> Result<PlaneVolumeResult, LandXMLError> {
+        let region = boundary.map(Region::from_polygon);
+        let plane = match reference {
+            ReferencePlane::Elevation(z) => [0.0, 0.0, z],
+            ReferencePlane::Plane { a, b, c } => [a, b, c],
+            ReferencePlane::BestFit => {
+                let region = region.as_ref().ok_or_else(|| LandXMLError::GeometryError {
+                    message: "Best-fit plane requires a boundary polygon".to_string(),
+                })?;
+                self.fit_plane(region.exterior())?
+            }
+        };
+
+        let (volume, area_3d) = self
+            .triangles
+            .par_iter()
+            .filter_map(|t| self.triangle_vertices(t))
+            .filter_map(|vertices| {
+                // 三角形の頂点を局所原点とし、基準面も同じ原点で表す
+                let origin = Point2D {
+                    x: vertices[0].x,
+                    y: vertices[0].y,
+                };
+                let local = translated(vertices, origin);
+                Some((origin, oriented_triangle(local), plane_of(local)?))
+            })
+            .map(|(origin, polygon, [a, b, c])| {
+                let [pa, pb, pc] = plane;
+                let reference = pc + pa * origin.x + pb * origin.y;
+                let mut volume = VolumeResult::default();
+                let area_2d = volume.accumulate(
+                    &polygon,
+                    [a - pa, b - pb, c - reference],
+                    region.as_ref(),
+                    origin,
+                );
+                // 三角形内の表面積は投影面積に勾配の係数を乗じたもの
+                (volume, area_2d * (1.0 + a * a + b * b).sqrt())
+            })
+            .reduce(
+                || (VolumeResult::default(), 0.0),
+                |(v1, a1), (v2, a2)| (v1.combine(v2), a1 + a2),
+            );
+
+        Ok(PlaneVolumeResult {
+            above: volume.cut,
+            below: volume.fill,
+            net: volume.cut - volume.fill,
+            area_2d: volume.area,
+            area_3d,
+            plane,
+        })
+    }
+
+    /// 平面投影面積
+    pub fn area_2d(&self) -> f64 {
+        self.triangles
+            .iter()
+            .filter_map(|t| self.triangle_vertices(t))
+            .map(|vertices| Moments::of(&oriented_triangle(vertices)).area)
+            .sum()
+    }
+
+    /// 表面積（斜面積）
+    pub fn area_3d(&self) -> f64 {
+        self.triangles
+            .iter()
+            .filter_map(|t| self.triangle_vertices(t))
+            .map(|[p, q, r]| {
+                let u = Vector3::new(q.x - p.x, q.y - p.y, q.z - p.z);
+                let v = Vector3::new(r.x - p.x, r.y - p.y, r.z - p.z);
+                u.cross(&v).norm() / 2.0
+            })
+            .sum()
+    }
+
+    /// 多角形の頂点における面の標高に平面を当てはめる
+    fn fit_plane(&self, vertices: &[Point2D]) -> Result<[f64; 3], LandXMLError> {
+        let index = self.index();
+        let samples: Vec<(f64, f64, f64)> = vertices
+            .iter()
+            .filter_map(|p| index.elevation_at(p.x, p.y).map(|z| (p.x, p.y, z)))
+            .collect();
+        if samples.len() < 3 {
+            return Err(LandXMLError::GeometryError {
+                message: format!(
+                    "Best-fit plane requires at least 3 boundary vertices on the surface, found {}",
+                    samples.len()
+                ),
+            });
+        }
+
+        // 重心を原点として正規方程式を解く（座標値が大きい場合の桁落ちを避ける）
+        let n = samples.len() as f64;
+        let (mx, my, mz) = samples.iter().fold((0.0, 0.0, 0.0), |(x, y, z), p| {
+            (x + p.0 / n, y + p.1 / n, z + p.2 / n)
+        });
+        let mut normal = Matrix3::zeros();
+        let mut rhs = Vector3::zeros();
+        for (x, y, z) in &samples {
+            let row = Vector3::new(x - mx, y - my, 1.0);
+            normal += row * row.transpose();
+            rhs += row * (z - mz);
+        }
+        let solution = normal
+            .lu()
+            .solve(&rhs)
+            .ok_or_else(|| LandXMLError::GeometryError {
+                message: "Boundary vertices are collinear; cannot fit a plane".to_string(),
+            })?;
+        let (a, b) = (solution[0], solution[1]);
+        Ok([a, b, mz + solution[2] - a * mx - b * my])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::grid_surface;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_flat_fill() {
+        let existing = grid_surface(10.0, 4, |_, _| 10.0);
+        let design = grid_surface(10.0, 3, |_, _| 12.0);
+        let result = existing.volume_between(&design, None);
+        assert_close(result.fill, 200.0);
+        assert_close(result.cut, 0.0);
+        assert_close(result.net, 200.0);
+        assert_close(result.area, 100.0);
+    }
+
+    #[test]
+    fn test_crossing_surfaces() {
+        // 比較面が x = 5 で基準面と交差する
+        let existing = grid_surface(10.0, 2, |_, _| 0.0);
+        let design = grid_surface(10.0, 3, |x, _| x - 5.0);
+        let result = existing.volume_between(&design, None);
+        assert_close(result.cut, 125.0);
+        assert_close(result.fill, 125.0);
+        assert_close(result.net, 0.0);
+
+        // 境界はGIS座標（x東・y北）：北側半分（X ≤ 5）に限定
+        let boundary = geo::Polygon::new(
+            geo::LineString::from(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 5.0), (0.0, 5.0)]),
+            vec![],
+        );
+        let result = existing.volume_between(&design, Some(&boundary));
+        assert_close(result.cut, 125.0);
+        assert_close(result.fill, 0.0);
+        assert_close(result.area, 50.0);
+    }
+
+    #[test]
+    fn test_stockpile_volume() {
+        // 底面の標高10m、X = 5を稜線とする高さ3m・幅6mの盛土
+        let pile = grid_surface(10.0, 10, |x, _| 10.0 + (3.0 - (x - 5.0).abs()).max(0.0));
+        let result = pile
+            .volume_to_plane(ReferencePlane::Elevation(10.0), None)
+            .unwrap();
+        assert_close(result.above, 90.0);
+        assert_close(result.below, 0.0);
+        assert_close(result.area_2d, 100.0);
+        assert_close(result.area_3d, pile.area_3d());
+        assert!(result.area_3d > pile.area_2d());
+
+        // 傾いた地盤上の盛土は、境界に当てはめた基準面で計測する
+        let tilted = grid_surface(10.0, 10, |x, y| {
+            0.1 * x + 0.2 * y + (3.0 - (x - 5.0).abs()).max(0.0)
+        });
+        let boundary = geo::Polygon::new(
+            geo::LineString::from(vec![(1.0, 1.0), (9.0, 1.0), (9.0, 9.0), (1.0, 9.0)]),
+            vec![],
+        );
+        let result = tilted
+            .volume_to_plane(ReferencePlane::BestFit, Some(&boundary))
+            .unwrap();
+        assert_close(result.above, 72.0);
+        assert_close(result.area_2d, 64.0);
+        assert!((result.plane[0] - 0.1).abs() < 1e-9);
+        assert!((result.plane[1] - 0.2).abs() < 1e-9);
+
+        assert!(matches!(
+            tilted.volume_to_plane(ReferencePlane::BestFit, None),
+            Err(LandXMLError::GeometryError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_volume_at_plane_coordinates() {
+        // 平面直角座標の実際の値（X = −35km、Y = −200km）へ移動しても結果は変わらない
+        let (dx, dy) = (-35_000.0, -200_000.0);
+        let shift = |mut surface: Surface| {
+            for p in &mut surface.points {
+                p.x += dx;
+                p.y += dy;
+            }
+            surface
+        };
+        let boundary = |offset: (f64, f64)| {
+            let (x, y) = (offset.0, offset.1);
+            geo::Polygon::new(
+                geo::LineString::from(vec![
+                    (y + 1.0, x + 1.0),
+                    (y + 9.0, x + 1.0),
+                    (y + 9.0, x + 8.0),
+                    (y + 1.0, x + 8.0),
+                ]),
+                vec![],
+            )
+        };
+        let existing = grid_surface(10.0, 10, |x, y| 3.0 * (0.7 * x).sin() + 0.1 * y);
+        let design = grid_surface(10.0, 7, |x, y| 1.0 + (0.5 * y).cos() + 0.05 * x * y);
+
+        let local = existing.volume_between(&design, Some(&boundary((0.0, 0.0))));
+        let shifted = shift(existing.clone())
+            .volume_between(&shift(design.clone()), Some(&boundary((dx, dy))));
+        assert!(local.cut > 0.0 && local.fill > 0.0);
+        assert_close(shifted.cut, local.cut);
+        assert_close(shifted.fill, local.fill);
+        assert_close(shifted.area, local.area);
+
+        let (a, b, c) = (0.02, -0.03, 1.5);
+        for (reference, moved) in [
+            (
+                ReferencePlane::Elevation(0.5),
+                ReferencePlane::Elevation(0.5),
+            ),
+            (
+                ReferencePlane::Plane { a, b, c },
+                ReferencePlane::Plane {
+                    a,
+                    b,
+                    c: c - a * dx - b * dy,
+                },
+            ),
+            (ReferencePlane::BestFit, ReferencePlane::BestFit),
+        ] {
+            let local = existing
+                .volume_to_plane(reference, Some(&boundary((0.0, 0.0))))
+                .unwrap();
+            let shifted = shift(existing.clone())
+                .volume_to_plane(moved, Some(&boundary((dx, dy))))
+                .unwrap();
+            assert!(local.above > 0.0 && local.below > 0.0);
+            assert_close(shifted.above, local.above);
+            assert_close(shifted.below, local.below);
+            assert_close(shifted.area_2d, local.area_2d);
+        }
+    }
+
+    #[test]
+    fn test_partial_overlap() {
+        let existing = grid_surface(10.0, 2, |_, _| 0.0);
+        let mut design = grid_surface(10.0, 2, |_, _| 1.0);
+        for p in &mut design.points {
+            p.x += 5.0;
+        }
+        let result = existing.volume_between(&design, None);
+        assert_close(result.area, 50.0);
+        assert_close(result.fill, 50.0);
+    }
+}
@@ -0,0 +1,400 @@
+//! Angle representation and orientation conventions
+//!
+//! 角度の表現と、方向角・座標軸の規約間の変換を扱います：
+//! - 度分秒（decimal dd.mm.ss）・度・グラード・ラジアンの解析と書式化
+//! - 方向角（北を0とし時計回り、LandXMLの規約）と数学的角度（東を0とし反時計回り）の変換
+//! - 測量座標（X北・Y東）とGIS座標（x東・y北）の変換
+//!
+//! 幾何計算のAPIは角度を[`Angle`]、平面座標を測量座標の[`Point2D`]で扱い、
+//! GISライブラリとの受け渡しにのみGIS座標を使用します。
+
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::AngularUnit;
+use crate::error::LandXMLError;
+use crate::models::Point2D;
+
+// ============================================================================
+// 角度
+// ============================================================================
+
+/// 角度（内部表現はラジアン）
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Angle(f64);
+
+/// 度分秒に分解した角度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dms {
+    /// 負の角度かどうか
+    pub negative: bool,
+    pub degrees: u32,
+    pub minutes: u32,
+    pub seconds: f64,
+}
+
+impl Angle {
+    /// 0度
+    pub const ZERO: Angle = Angle(0.0);
+
+    /// ラジアンから作成
+    pub fn from_radians(radians: f64) -> Self {
+        Self(radians)
+    }
+
+    /// 度から作成
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self(degrees.to_radians())
+    }
+
+    /// グラード（直角 = 100grad）から作成
+    pub fn from_gradians(gradians: f64) -> Self {
+        Self(gradians * PI / 200.0)
+    }
+
+    /// 度・分・秒から作成
+    ///
+    /// 符号は度の値から決まります（-0°30'は`-Angle::from_dms(0.0, 30.0, 0.0)`）。
+    pub fn from_dms(degrees: f64, minutes: f64, seconds: f64) -> Self {
+        let magnitude = degrees.abs() + minutes / 60.0 + seconds / 3600.0;
+        Self::from_degrees(magnitude.copysign(degrees))
+    }
+
+    /// 度分秒の小数表記（dd.mmss、30.2045 = 30°20'45"）から作成
+    pub fn from_decimal_dms(value: f64) -> Self {
+        let magnitude = value.abs();
+        let degrees = magnitude.trunc();
+        // 浮動小数点誤差で分・秒の桁が繰り下がらないよう丸めてから分解する
+        let rest = ((magnitude - degrees) * 100.0 * 1e9).round() / 1e9;
+        let minutes = rest.trunc();
+        let seconds = (rest - minutes) * 100.0;
+        Self::from_degrees((degrees + minutes / 60.0 + seconds / 3600.0).copysign(value))
+    }
+
+    /// 指定した単位の値から作成
+    pub fn from_unit(value: f64, unit: AngularUnit) -> Self {
+        match unit {
+            AngularUnit::DecimalDegrees => Self::from_degrees(value),
+            AngularUnit::DecimalDms => Self::from_decimal_dms(value),
+            AngularUnit::Gradian => Self::from_gradians(value),
+            AngularUnit::Radian => Self::from_radians(value),
+        }
+    }
+
+    /// 文字列を解析
+    ///
+    /// 数値のみの場合は`unit`の値として解釈し、`30°20'45"`・`30度20分45秒`の
+    /// 記号付き度分秒表記は単位によらず受け付けます。
+    pub fn parse(text: &str, unit: AngularUnit) -> Result<Self, LandXMLError> {
+        let text = text.trim();
+        if let Ok(value) = text.parse::<f64>() {
+            return Ok(Self::from_unit(value, unit));
+        }
+
+        let invalid = || LandXMLError::ParseError(format!("Invalid angle: {}", text));
+        let re = Regex::new(
+            r#"^([+-])?\s*(\d+(?:\.\d+)?)\s*[°度]\s*(?:(\d+(?:\.\d+)?)\s*['′分]\s*)?(?:(\d+(?:\.\d+)?)\s*(?:"|″|''|秒)\s*)?$"#,
+        )
+        .map_err(|_| invalid())?;
+        let captures = re.captures(text).ok_or_else(invalid)?;
+        let number = |i: usize| {
+            captures.get(i).map_or(Ok(0.0), |m| {
+                m.as_str().parse::<f64>().map_err(|_| invalid())
+            })
+        };
+        let (minutes, seconds) = (number(3)?, number(4)?);
+        if minutes >= 60.0 || seconds >= 60.0 {
+            return Err(invalid());
+        }
+        let angle = Self::from_dms(number(2)?, minutes, seconds);
+        Ok(match captures.get(1) {
+            Some(sign) if sign.as_str() == "-" => -angle,
+            _ => angle,
+        })
+    }
+
+    /// ラジアンで取得
+    pub fn radians(&self) -> f64 {
+        self.0
+    }
+
+    /// 度で取得
+    pub fn degrees(&self) -> f64 {
+        self.0.to_degrees()
+    }
+
+    /// グラードで取得
+    pub fn gradians(&self) -> f64 {
+        self.0 * 200.0 / PI
+    }
+
+    /// 度分秒の小数表記（dd.mmss）で取得
+    pub fn decimal_dms(&self) -> f64 {
+        // 0.1マイクロ秒単位に丸める
+        let dms = self.to_dms(7);
+        let value = dms.degrees as f64 + dms.minutes as f64 / 100.0 + dms.seconds / 10000.0;
+        if dms.negative {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// 指定した単位の値で取得
+    pub fn to_unit(&self, unit: AngularUnit) -> f64 {
+        match unit {
+            AngularUnit::DecimalDegrees => self.degrees(),
+            AngularUnit::DecimalDms => self.decimal_dms(),
+            AngularUnit::Gradian => self.gradians(),
+            AngularUnit::Radian => self.radians(),
+        }
+    }
+
+    /// 度分秒に分解
+    ///
+    /// 秒を小数点以下`precision`桁に丸め、60秒・60分の繰り上がりを処理します。
+    pub fn to_dms(&self, precision: usize) -> Dms {
+        let scale = 10f64.powi(precision.min(15) as i32);
+        let total_seconds = (self.degrees().abs() * 3600.0 * scale).round() / scale;
+        let degrees = (total_seconds / 3600.0).floor();
+        let minutes = ((total_seconds - degrees * 3600.0) / 60.0).floor();
+        let seconds = total_seconds - degrees * 3600.0 - minutes * 60.0;
+        Dms {
+            negative: self.0 < 0.0 && total_seconds > 0.0,
+            degrees: degrees as u32,
+            minutes: minutes as u32,
+            seconds,
+        }
+    }
+
+    /// 指定した単位・小数点以下桁数で書式化
+    ///
+    /// 度分秒の小数表記の場合、桁数は秒の小数点以下の桁数です。
+    pub fn format(&self, unit: AngularUnit, precision: usize) -> String {
+        match unit {
+            AngularUnit::DecimalDms => {
+                let dms = self.to_dms(precision);
+                let sign = if dms.negative { "-" } else { "" };
+                let width = if precision > 0 { precision + 3 } else { 2 };
+                let seconds = format!("{:0width$.precision$}", dms.seconds);
+                // 秒の小数点を除いて分・秒の桁を連結する（30°20'45.5" → 30.20455）
+                format!(
+                    "{}{}.{:02}{}",
+                    sign,
+                    dms.degrees,
+                    dms.minutes,
+                    seconds.replace('.', "")
+                )
+            }
+            _ => format!("{:.*}", precision, self.to_unit(unit)),
+        }
+    }
+
+    /// [0, 2π)の範囲に正規化
+    pub fn normalized(&self) -> Self {
+        let radians = self.0.rem_euclid(TAU);
+        // rem_euclidは丸め誤差でTAUを返すことがある
+        Self(if radians >= TAU { 0.0 } else { radians })
+    }
+
+    /// (-π, π]の範囲に正規化
+    pub fn normalized_signed(&self) -> Self {
+        let radians = self.normalized().0;
+        Self(if radians > PI { radians - TAU } else { radians })
+    }
+
+    // ------------------------------------------------------------------------
+    // 方向角
+    // ------------------------------------------------------------------------
+
+    /// 測量座標の2点間の方向角（北を0とし時計回り、[0, 2π)）
+    pub fn azimuth(from: Point2D, to: Point2D) -> Self {
+        // X北・Y東の右手系では、atan2(ΔY, ΔX)が北からの時計回りの角度になる
+        Self((to.y - from.y).atan2(to.x - from.x)).normalized()
+    }
+
+    /// 方向角（北を0とし時計回り）を数学的角度（東を0とし反時計回り）に変換
+    pub fn azimuth_to_math(&self) -> Self {
+        Self(FRAC_PI_2 - self.0).normalized()
+    }
+
+    /// 数学的角度（東を0とし反時計回り）を方向角（北を0とし時計回り）に変換
+    pub fn math_to_azimuth(&self) -> Self {
+        // 変換式は対称（θ' = π/2 - θ）
+        self.azimuth_to_math()
+    }
+
+    /// 方向角の単位ベクトル（測量座標、X北・Y東）
+    pub fn direction_vector(&self) -> Point2D {
+        Point2D {
+            x: self.0.cos(),
+            y: self.0.sin(),
+        }
+    }
+
+    /// sin
+    pub fn sin(&self) -> f64 {
+        self.0.sin()
+    }
+
+    /// cos
+    pub fn cos(&self) -> f64 {
+        self.0.cos()
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
+
+impl Add for Angle {
+    type Output = Angle;
+
+    fn add(self, rhs: Angle) -> Angle {
+        Angle(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Angle {
+    type Output = Angle;
+
+    fn sub(self, rhs: Angle) -> Angle {
+        Angle(self.0 - rhs.0)
+    }
+}
+
+/// 記号付きの度分秒表記（30°20'45"）で表示
+///
+/// 精度指定（`{:.2}`）は秒の小数点以下の桁数です。
+impl fmt::Display for Angle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        write!(f, "{:.*}", precision, self.to_dms(precision))
+    }
+}
+
+impl fmt::Display for Dms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0);
+        let width = if precision > 0 { precision + 3 } else { 2 };
+        write!(
+            f,
+            "{}{}°{:02}'{:0width$.precision$}\"",
+            if self.negative { "-" } else { "" },
+            self.degrees,
+            self.minutes,
+            self.seconds,
+        )
+    }
+}
+
+/// 度分秒の小数表記（dd.mmss、30.2045 = 30°20'45"）を度に変換
+pub fn dms_to_degrees(value: f64) -> f64 {
+    Angle::from_decimal_dms(value).degrees()
+}
+
+/// 度を度分秒の小数表記（dd.mmss）に変換
+pub fn degrees_to_dms(degrees: f64) -> f64 {
+    Angle::from_degrees(degrees).decimal_dms()
+}
+
+// ============================================================================
+// 座標軸
+// ============================================================================
+
+/// 測量座標（X北・Y東）をGIS座標（x東・y北）に変換
+pub fn survey_to_gis(point: Point2D) -> geo::Coord<f64> {
+    geo::Coord {
+        x: point.y,
+        y: point.x,
+    }
+}
+
+/// GIS座標（x東・y北）を測量座標（X北・Y東）に変換
+pub fn gis_to_survey(coord: geo::Coord<f64>) -> Point2D {
+    Point2D {
+        x: coord.y,
+        y: coord.x,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_parse_and_format() {
+        let angle = Angle::parse("30.2045", AngularUnit::DecimalDms).unwrap();
+        assert_close(angle.degrees(), 30.0 + 20.0 / 60.0 + 45.0 / 3600.0);
+        assert_eq!(angle.format(AngularUnit::DecimalDms, 1), "30.20450");
+        assert_eq!(angle.to_string(), "30°20'45\"");
+
+        for text in ["30°20'45\"", "30度20分45秒", " 30° 20′ 45″ "] {
+            let parsed = Angle::parse(text, AngularUnit::Radian).unwrap();
+            assert_close(parsed.radians(), angle.radians());
+        }
+        let negative = Angle::parse("-0°30'", AngularUnit::Radian).unwrap();
+        assert_close(negative.degrees(), -0.5);
+        assert_eq!(format!("{:.1}", negative), "-0°30'00.0\"");
+        assert!(Angle::parse("30°75'", AngularUnit::Radian).is_err());
+        assert!(Angle::parse("north", AngularUnit::Radian).is_err());
+
+        let right = Angle::parse("100", AngularUnit::Gradian).unwrap();
+        assert_close(right.radians(), FRAC_PI_2);
+        assert_eq!(right.format(AngularUnit::DecimalDegrees, 3), "90.000");
+        // 59.99999999秒は繰り上げる
+        assert_eq!(
+            Angle::from_degrees(10.0 - 1e-12).format(AngularUnit::DecimalDms, 2),
+            "10.000000"
+        );
+    }
+
+    #[test]
+    fn test_direction_conventions() {
+        let origin = Point2D { x: 0.0, y: 0.0 };
+        let east = Point2D { x: 0.0, y: 10.0 };
+        let south = Point2D { x: -10.0, y: 0.0 };
+        assert_close(Angle::azimuth(origin, east).degrees(), 90.0);
+        assert_close(Angle::azimuth(origin, south).degrees(), 180.0);
+
+        // 方向角90°（東）は数学的角度0°
+        assert_close(Angle::from_degrees(90.0).azimuth_to_math().degrees(), 0.0);
+        assert_close(Angle::from_degrees(0.0).azimuth_to_math().degrees(), 90.0);
+        assert_close(
+            Angle::from_degrees(135.0).math_to_azimuth().degrees(),
+            315.0,
+        );
+        assert_close(Angle::from_degrees(-90.0).normalized().degrees(), 270.0);
+        assert_close(
+            Angle::from_degrees(270.0).normalized_signed().degrees(),
+            -90.0,
+        );
+
+        let vector = Angle::from_degrees(90.0).direction_vector();
+        assert_close(vector.x, 0.0);
+        assert_close(vector.y, 1.0);
+    }
+
+    #[test]
+    fn test_axis_conventions() {
+        let point = Point2D {
+            x: -5000.0,
+            y: 1200.0,
+        };
+        let coord = survey_to_gis(point);
+        assert_eq!((coord.x, coord.y), (1200.0, -5000.0));
+        assert_eq!(gis_to_survey(coord), point);
+    }
+}
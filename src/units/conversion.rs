@@ -0,0 +1,418 @@
+//! Unit conversion of document values
+//!
+//! 文書中の長さ・角度・方向角の値を一括で変換します：
+//! - 長さ：座標・標高・測点・延長・半径等
+//! - 角度：交角（delta）・接線角（theta）
+//! - 方向角：dir / dirStart / dirEnd
+//! - 未知要素として保持される測点・標高・面積・体積（StaEquation、CrossSectの面積等）
+//! - J-LandXMLのFeatureの値（CgPointsのsta・tangentDirectionAngle等）
+
+use crate::alignments::{GeomElement, ProfAlignElement};
+use crate::models::{Extensions, LandXML, Point2D, Point3D, RawElement};
+
+/// 未知要素の属性として保持される値の種類
+#[derive(Debug, Clone, Copy)]
+enum RawQuantity {
+    Length,
+    Direction,
+    Area,
+    Volume,
+}
+
+/// 要素名・属性名から値の種類を判定
+fn raw_quantity(element: &str, attribute: &str) -> Option<RawQuantity> {
+    match (element, attribute) {
+        ("Definition", "elevMax" | "elevMin")
+        | ("StaEquation", "staBack" | "staAhead" | "staInternal")
+        | ("CrossSect", "centroidCut" | "centroidFill") => Some(RawQuantity::Length),
+        ("Definition", "area2DSurf" | "area3DSurf")
+        | ("Parcel", "area")
+        | ("CrossSect", "areaCut" | "areaFill") => Some(RawQuantity::Area),
+        ("SurfVolume", "volCut" | "volFill" | "volTotal")
+        | ("CrossSect", "volumeCut" | "volumeFill") => Some(RawQuantity::Volume),
+        _ => None,
+    }
+}
+
+/// Featureを持つ要素名・Propertyのラベルから値の種類を判定
+fn feature_quantity(owner: &str, label: &str) -> Option<RawQuantity> {
+    match (owner, label) {
+        ("CgPoints", "sta") | ("Alignment", "main") | ("Spiral", "A") => Some(RawQuantity::Length),
+        ("CgPoints", "tangentDirectionAngle") => Some(RawQuantity::Direction),
+        _ => None,
+    }
+}
+
+/// 換算した値を未知要素の属性値として表記（有効数字12桁、末尾の0は省略）
+///
+/// 単位の往復変換で生じる丸め誤差が表記に現れないようにします。
+pub(crate) fn format_raw(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return if value == 0.0 {
+            "0".to_string()
+        } else {
+            value.to_string()
+        };
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (11 - magnitude).clamp(0, 15) as usize;
+    let text = format!("{:.*}", decimals, value);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        text.as_str()
+    };
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+/// 測点・面積等を未知要素として保持しうる要素（要素名, 未知の属性・子要素）
+///
+/// Units・Project・Application・CoordinateSystemは換算対象の値を持たないため含めません。
+pub(crate) fn extension_owners(doc: &mut LandXML) -> Vec<(&'static str, &mut Extensions)> {
+    let mut owners: Vec<(&'static str, &mut Extensions)> = vec![
+        ("LandXML", &mut doc.extensions),
+        ("Alignments", &mut doc.alignments_extensions),
+    ];
+    for cg_points in &mut doc.cg_points {
+        owners.push(("CgPoints", &mut cg_points.extensions));
+        for point in &mut cg_points.points {
+            owners.push(("CgPoint", &mut point.extensions));
+        }
+    }
+    for alignment in &mut doc.alignments {
+        owners.push(("Alignment", &mut alignment.extensions));
+        if let Some(coord_geom) = alignment.coord_geom.as_mut() {
+            owners.push(("CoordGeom", &mut coord_geom.extensions));
+            for element in &mut coord_geom.elements {
+                owners.push(match element {
+                    GeomElement::Line(e) => ("Line", &mut e.extensions),
+                    GeomElement::Curve(e) => ("Curve", &mut e.extensions),
+                    GeomElement::Spiral(e) => ("Spiral", &mut e.extensions),
+                });
+            }
+        }
+        if let Some(profile) = alignment.profile.as_mut() {
+            owners.push(("Profile", &mut profile.extensions));
+            for prof_align in &mut profile.prof_aligns {
+                owners.push(("ProfAlign", &mut prof_align.extensions));
+            }
+            for prof_surf in &mut profile.prof_surfs {
+                owners.push(("ProfSurf", &mut prof_surf.extensions));
+            }
+        }
+        if let Some(cross_sects) = alignment.cross_sects.as_mut() {
+            owners.push(("CrossSects", &mut cross_sects.extensions));
+            for section in &mut cross_sects.sections {
+                owners.push(("CrossSect", &mut section.extensions));
+                for surface in &mut section.surfaces {
+                    owners.push(("CrossSectSurf", &mut surface.extensions));
+                }
+                for surface in &mut section.design_surfaces {
+                    owners.push(("DesignCrossSectSurf", &mut surface.extensions));
+                }
+            }
+        }
+    }
+    for roadways in &mut doc.roadways {
+        owners.push(("Roadways", &mut roadways.extensions));
+        for roadway in &mut roadways.roadways {
+            owners.push(("Roadway", &mut roadway.extensions));
+        }
+    }
+    for surfaces in &mut doc.surfaces {
+        owners.push(("Surfaces", &mut surfaces.extensions));
+        for surface in &mut surfaces.surfaces {
+            owners.push(("Surface", &mut surface.extensions));
+            owners.push(("Definition", &mut surface.definition_extensions));
+        }
+    }
+    owners
+}
+
+/// 値の種類ごとの変換関数
+#[derive(Default)]
+pub(crate) struct QuantityMap<'a> {
+    /// 長さの変換（Noneの場合は変換しない）
+    pub length: Option<&'a dyn Fn(f64) -> f64>,
+    /// 角度の変換（Noneの場合は変換しない）
+    pub angle: Option<&'a dyn Fn(f64) -> f64>,
+    /// 方向角の変換（Noneの場合は変換しない）
+    pub direction: Option<&'a dyn Fn(f64) -> f64>,
+    /// 面積の変換（Noneの場合は変換しない）
+    pub area: Option<&'a dyn Fn(f64) -> f64>,
+    /// 体積の変換（Noneの場合は変換しない）
+    pub volume: Option<&'a dyn Fn(f64) -> f64>,
+}
+
+impl QuantityMap<'_> {
+    fn length(&self, value: &mut f64) {
+        if let Some(f) = self.length {
+            *value = f(*value);
+        }
+    }
+
+    fn opt_length(&self, value: &mut Option<f64>) {
+        if let Some(v) = value {
+            self.length(v);
+        }
+    }
+
+    fn point_2d(&self, point: &mut Point2D) {
+        self.length(&mut point.x);
+        self.length(&mut point.y);
+    }
+
+    fn point_3d(&self, point: &mut Point3D) {
+        self.length(&mut point.x);
+        self.length(&mut point.y);
+        self.length(&mut point.z);
+    }
+
+    fn point_lists(&self, lists: &mut [Vec<Point2D>]) {
+        for point in lists.iter_mut().flatten() {
+            self.point_2d(point);
+        }
+    }
+
+    fn angle(&self, value: &mut Option<f64>) {
+        if let (Some(f), Some(v)) = (self.angle, value.as_mut()) {
+            *v = f(*v);
+        }
+    }
+
+    fn direction(&self, value: &mut Option<f64>) {
+        if let (Some(f), Some(v)) = (self.direction, value.as_mut()) {
+            *v = f(*v);
+        }
+    }
+
+    fn geom_element(&self, element: &mut GeomElement) {
+        match element {
+            GeomElement::Line(e) => {
+                self.point_2d(&mut e.start);
+                self.point_2d(&mut e.end);
+                for value in [
+                    &mut e.start_elevation,
+                    &mut e.end_elevation,
+                    &mut e.length,
+                    &mut e.sta_start,
+                ] {
+                    self.opt_length(value);
+                }
+                self.direction(&mut e.dir);
+            }
+            GeomElement::Curve(e) => {
+                self.point_2d(&mut e.start);
+                self.point_2d(&mut e.end);
+                for point in [&mut e.center, &mut e.pi].into_iter().flatten() {
+                    self.point_2d(point);
+                }
+                for value in [
+                    &mut e.start_elevation,
+                    &mut e.end_elevation,
+                    &mut e.radius,
+                    &mut e.length,
+                    &mut e.chord,
+                    &mut e.tangent,
+                    &mut e.external,
+                    &mut e.mid_ord,
+                    &mut e.sta_start,
+                ] {
+                    self.opt_length(value);
+                }
+                self.angle(&mut e.delta);
+                self.direction(&mut e.dir_start);
+                self.direction(&mut e.dir_end);
+            }
+            GeomElement::Spiral(e) => {
+                self.point_2d(&mut e.start);
+                self.point_2d(&mut e.end);
+                if let Some(pi) = e.pi.as_mut() {
+                    self.point_2d(pi);
+                }
+                self.length(&mut e.length);
+                for value in [
+                    &mut e.start_elevation,
+                    &mut e.end_elevation,
+                    &mut e.radius_start,
+                    &mut e.radius_end,
+                    &mut e.constant,
+                    &mut e.total_x,
+                    &mut e.total_y,
+                    &mut e.tan_long,
+                    &mut e.tan_short,
+                    &mut e.chord,
+                    &mut e.sta_start,
+                ] {
+                    self.opt_length(value);
+                }
+                self.angle(&mut e.theta);
+                self.direction(&mut e.dir_start);
+                self.direction(&mut e.dir_end);
+            }
+        }
+    }
+
+    fn prof_align_element(&self, element: &mut ProfAlignElement) {
+        match element {
+            ProfAlignElement::Pvi { sta, elevation } => {
+                self.length(sta);
+                self.length(elevation);
+            }
+            ProfAlignElement::ParaCurve {
+                sta,
+                elevation,
+                length,
+            } => {
+                self.length(sta);
+                self.length(elevation);
+                self.length(length);
+            }
+            ProfAlignElement::UnsymParaCurve {
+                sta,
+                elevation,
+                length_in,
+                length_out,
+            } => {
+                self.length(sta);
+                self.length(elevation);
+                self.length(length_in);
+                self.length(length_out);
+            }
+            ProfAlignElement::CircCurve {
+                sta,
+                elevation,
+                length,
+                radius,
+            } => {
+                self.length(sta);
+                self.length(elevation);
+                self.length(length);
+                self.length(radius);
+            }
+        }
+    }
+
+    fn raw_value(&self, quantity: Option<RawQuantity>, value: &mut String) {
+        let convert = match quantity {
+            Some(RawQuantity::Length) => self.length,
+            Some(RawQuantity::Direction) => self.direction,
+            Some(RawQuantity::Area) => self.area,
+            Some(RawQuantity::Volume) => self.volume,
+            None => None,
+        };
+        if let (Some(f), Ok(v)) = (convert, value.trim().parse::<f64>()) {
+            *value = format_raw(f(v));
+        }
+    }
+
+    fn raw_attributes(&self, element: &str, attributes: &mut [(String, String)]) {
+        for (key, value) in attributes {
+            self.raw_value(raw_quantity(element, key), value);
+        }
+    }
+
+    /// 未知要素を変換（`parent`は親要素名）
+    fn raw_element(&self, parent: &str, element: &mut RawElement) {
+        self.raw_attributes(&element.name, &mut element.attributes);
+        if element.name == "Feature" {
+            for property in element.children.iter_mut().filter(|c| c.name == "Property") {
+                let quantity = property
+                    .attributes
+                    .iter()
+                    .find(|(k, _)| k == "label")
+                    .and_then(|(_, label)| feature_quantity(parent, label));
+                for (key, value) in &mut property.attributes {
+                    if key == "value" {
+                        self.raw_value(quantity, value);
+                    }
+                }
+            }
+            return;
+        }
+        for child in &mut element.children {
+            self.raw_element(&element.name, child);
+        }
+    }
+
+    fn extensions(&self, owner: &str, extensions: &mut Extensions) {
+        self.raw_attributes(owner, &mut extensions.attributes);
+        for element in &mut extensions.elements {
+            self.raw_element(owner, &mut element.element);
+        }
+    }
+
+    /// 未知要素として保持されている値を変換
+    ///
+    /// 測点・標高・方向角は解析時の正規化で基準単位（メートル・ラジアン）に変換されます。
+    /// 面積・体積は文書の単位のまま保持し、単位系の変換時にのみ換算します。
+    pub fn apply_extensions(&self, doc: &mut LandXML) {
+        for (owner, extensions) in extension_owners(doc) {
+            self.extensions(owner, extensions);
+        }
+    }
+
+    /// 文書中の全ての値（未知要素として保持されている値を含む）を変換
+    pub fn apply(&self, doc: &mut LandXML) {
+        for point in doc.cg_points.iter_mut().flat_map(|c| c.points.iter_mut()) {
+            self.point_3d(&mut point.position);
+        }
+
+        for alignment in &mut doc.alignments {
+            self.opt_length(&mut alignment.sta_start);
+            self.opt_length(&mut alignment.length);
+            if let Some(coord_geom) = alignment.coord_geom.as_mut() {
+                for element in &mut coord_geom.elements {
+                    self.geom_element(element);
+                }
+            }
+            if let Some(profile) = alignment.profile.as_mut() {
+                for prof_align in &mut profile.prof_aligns {
+                    for element in &mut prof_align.elements {
+                        self.prof_align_element(element);
+                    }
+                }
+                for prof_surf in &mut profile.prof_surfs {
+                    self.point_lists(&mut prof_surf.point_lists);
+                }
+            }
+            if let Some(cross_sects) = alignment.cross_sects.as_mut() {
+                for section in &mut cross_sects.sections {
+                    self.length(&mut section.sta);
+                    for surface in &mut section.surfaces {
+                        self.point_lists(&mut surface.point_lists);
+                    }
+                    for point in section
+                        .design_surfaces
+                        .iter_mut()
+                        .flat_map(|s| s.points.iter_mut())
+                    {
+                        self.length(&mut point.offset);
+                        self.length(&mut point.elevation);
+                    }
+                }
+            }
+        }
+
+        for roadway in doc.roadways.iter_mut().flat_map(|r| r.roadways.iter_mut()) {
+            self.opt_length(&mut roadway.sta_start);
+            self.opt_length(&mut roadway.sta_end);
+        }
+
+        if self.length.is_some() {
+            for point in doc
+                .surfaces
+                .iter_mut()
+                .flat_map(|s| s.surfaces.iter_mut())
+                .flat_map(|s| s.points.iter_mut())
+            {
+                self.point_3d(point);
+            }
+        }
+
+        self.apply_extensions(doc);
+    }
+}
@@ -2,18 +2,40 @@
 //!
 //! This module handles unit definitions including:
 //! - Linear units (meter, foot, etc.)
-//! - Angular units (degree, radian, etc.)
+//! - Angular units (degree, radian, etc.) for angles and directions
+//! - Angle representation and direction/axis conventions (see [`angle`])
 //! - Area/Volume units
+//! - Temperature/Pressure units
+//!
+//! 解析時に長さはメートル、角度・方向角はラジアンに正規化し、
+//! 出力時に元の単位へ戻します。
+
+pub mod angle;
+pub(crate) mod conversion;
 
 use serde::{Deserialize, Serialize};
 
+pub use self::angle::{degrees_to_dms, dms_to_degrees, gis_to_survey, survey_to_gis, Angle, Dms};
+
+use self::conversion::QuantityMap;
+use crate::error::LandXMLError;
+use crate::models::LandXML;
+
+use crate::models::Extensions;
+use crate::xml::XmlElement;
+
 /// Linear unit types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum LinearUnit {
     #[default]
     Meter,
+    Millimeter,
+    Centimeter,
+    Kilometer,
     Foot,
     UsSurveyFoot,
+    Inch,
+    Mile,
 }
 
 /// Angular unit types
@@ -21,18 +43,44 @@ pub enum LinearUnit {
 pub enum AngularUnit {
     #[default]
     DecimalDegrees,
+    /// 度分秒を小数表記した値（30.2045 = 30°20'45"）
+    DecimalDms,
     Gradian,
     Radian,
 }
 
+/// Temperature unit types
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Kelvin,
+    Fahrenheit,
+}
+
+/// Pressure unit types
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PressureUnit {
+    /// ヘクトパスカル
+    #[default]
+    Hectopascal,
+    Millibar,
+    MillimeterHg,
+    InchHg,
+}
+
 /// Area unit types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum AreaUnit {
     #[default]
     SquareMeter,
-    SquareFoot,
+    SquareMillimeter,
+    SquareCentimeter,
     Hectare,
+    SquareFoot,
+    SquareInch,
     Acre,
+    SquareMile,
 }
 
 /// Volume unit types
@@ -40,8 +88,12 @@ pub enum AreaUnit {
 pub enum VolumeUnit {
     #[default]
     CubicMeter,
+    Liter,
+    HectareMeter,
     CubicFoot,
+    CubicInch,
     CubicYard,
+    AcreFoot,
 }
 
 /// Units definition for LandXML document
@@ -50,14 +102,502 @@ pub enum VolumeUnit {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Units {
     pub linear_unit: LinearUnit,
+    /// 角度（交角・接線角）の単位
     pub angular_unit: AngularUnit,
+    /// 方向角（dir / dirStart / dirEnd）の単位
+    #[serde(default)]
+    pub direction_unit: AngularUnit,
     pub area_unit: AreaUnit,
     pub volume_unit: VolumeUnit,
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+    #[serde(default)]
+    pub pressure_unit: PressureUnit,
+    /// 未知の属性・子要素
+    #[serde(default, skip_serializing_if = "Extensions::is_empty")]
+    pub extensions: Extensions,
+}
+
+impl LinearUnit {
+    /// LandXMLのlinearUnit属性値から解析
+    pub fn from_landxml(value: &str) -> Option<Self> {
+        match value.trim() {
+            "meter" => Some(Self::Meter),
+            "millimeter" => Some(Self::Millimeter),
+            "centimeter" => Some(Self::Centimeter),
+            "kilometer" => Some(Self::Kilometer),
+            "foot" => Some(Self::Foot),
+            "USSurveyFoot" => Some(Self::UsSurveyFoot),
+            "inch" => Some(Self::Inch),
+            "mile" => Some(Self::Mile),
+            _ => None,
+        }
+    }
+
+    /// LandXMLのlinearUnit属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::Meter => "meter",
+            Self::Millimeter => "millimeter",
+            Self::Centimeter => "centimeter",
+            Self::Kilometer => "kilometer",
+            Self::Foot => "foot",
+            Self::UsSurveyFoot => "USSurveyFoot",
+            Self::Inch => "inch",
+            Self::Mile => "mile",
+        }
+    }
+
+    /// メートル法の単位かどうか
+    pub fn is_metric(&self) -> bool {
+        matches!(
+            self,
+            Self::Meter | Self::Millimeter | Self::Centimeter | Self::Kilometer
+        )
+    }
+
+    /// 1単位あたりのメートル数
+    pub fn meters_per_unit(&self) -> f64 {
+        match self {
+            Self::Meter => 1.0,
+            Self::Millimeter => 0.001,
+            Self::Centimeter => 0.01,
+            Self::Kilometer => 1000.0,
+            Self::Foot => 0.3048,
+            Self::UsSurveyFoot => 1200.0 / 3937.0,
+            Self::Inch => 0.0254,
+            Self::Mile => 1609.344,
+        }
+    }
+
+    /// この単位の値をメートルに変換
+    pub fn to_meters(&self, value: f64) -> f64 {
+        value * self.meters_per_unit()
+    }
+
+    /// メートルの値をこの単位に変換
+    pub fn from_meters(&self, meters: f64) -> f64 {
+        meters / self.meters_per_unit()
+    }
+
+    /// この単位の値を別の単位に変換
+    pub fn convert(&self, value: f64, to: LinearUnit) -> f64 {
+        to.from_meters(self.to_meters(value))
+    }
+}
+
+impl AngularUnit {
+    /// LandXMLのangularUnit属性値から解析
+    pub fn from_landxml(value: &str) -> Option<Self> {
+        // 区切りの空白が連続する表記（"decimal  dd.mm.ss"）も受け付ける
+        let normalized = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        match normalized.as_str() {
+            "decimal degrees" => Some(Self::DecimalDegrees),
+            "decimal dd.mm.ss" => Some(Self::DecimalDms),
+            "grads" => Some(Self::Gradian),
+            "radians" => Some(Self::Radian),
+            _ => None,
+        }
+    }
+
+    /// LandXMLのangularUnit属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::DecimalDegrees => "decimal degrees",
+            Self::DecimalDms => "decimal dd.mm.ss",
+            Self::Gradian => "grads",
+            Self::Radian => "radians",
+        }
+    }
+
+    /// この単位の値をラジアンに変換
+    pub fn to_radians(&self, value: f64) -> f64 {
+        Angle::from_unit(value, *self).radians()
+    }
+
+    /// ラジアンの値をこの単位に変換
+    pub fn from_radians(&self, radians: f64) -> f64 {
+        Angle::from_radians(radians).to_unit(*self)
+    }
+}
+
+impl TemperatureUnit {
+    /// LandXMLのtemperatureUnit属性値から解析
+    pub fn from_landxml(value: &str) -> Option<Self> {
+        match value.trim() {
+            "celsius" => Some(Self::Celsius),
+            "kelvin" => Some(Self::Kelvin),
+            "fahrenheit" => Some(Self::Fahrenheit),
+            _ => None,
+        }
+    }
+
+    /// LandXMLのtemperatureUnit属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::Celsius => "celsius",
+            Self::Kelvin => "kelvin",
+            Self::Fahrenheit => "fahrenheit",
+        }
+    }
+}
+
+impl PressureUnit {
+    /// LandXMLのpressureUnit属性値から解析
+    pub fn from_landxml(value: &str) -> Option<Self> {
+        match value.trim() {
+            "HPA" => Some(Self::Hectopascal),
+            "milliBars" => Some(Self::Millibar),
+            "mmHG" | "millimeterHG" => Some(Self::MillimeterHg),
+            "inHG" | "inchHG" => Some(Self::InchHg),
+            _ => None,
+        }
+    }
+
+    /// LandXMLのpressureUnit属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::Hectopascal => "HPA",
+            Self::Millibar => "milliBars",
+            Self::MillimeterHg => "mmHG",
+            Self::InchHg => "inHG",
+        }
+    }
+}
+
+impl AreaUnit {
+    /// LandXMLのareaUnit属性値から解析
+    pub fn from_landxml(value: &str) -> Option<Self> {
+        match value.trim() {
+            "squareMeter" => Some(Self::SquareMeter),
+            "squareMillimeter" => Some(Self::SquareMillimeter),
+            "squareCentimeter" => Some(Self::SquareCentimeter),
+            "hectare" => Some(Self::Hectare),
+            "squareFoot" => Some(Self::SquareFoot),
+            "squareInch" => Some(Self::SquareInch),
+            "acre" => Some(Self::Acre),
+            "squareMiles" => Some(Self::SquareMile),
+            _ => None,
+        }
+    }
+
+    /// LandXMLのareaUnit属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::SquareMeter => "squareMeter",
+            Self::SquareMillimeter => "squareMillimeter",
+            Self::SquareCentimeter => "squareCentimeter",
+            Self::Hectare => "hectare",
+            Self::SquareFoot => "squareFoot",
+            Self::SquareInch => "squareInch",
+            Self::Acre => "acre",
+            Self::SquareMile => "squareMiles",
+        }
+    }
+
+    /// 1単位あたりの平方メートル数
+    pub fn square_meters_per_unit(&self) -> f64 {
+        match self {
+            Self::SquareMeter => 1.0,
+            Self::SquareMillimeter => 1e-6,
+            Self::SquareCentimeter => 1e-4,
+            Self::Hectare => 10_000.0,
+            Self::SquareFoot => 0.092_903_04,
+            Self::SquareInch => 0.000_645_16,
+            // 国際エーカー（4840平方ヤード）
+            Self::Acre => 4_046.856_422_4,
+            Self::SquareMile => 2_589_988.110_336,
+        }
+    }
+
+    /// この単位の値を平方メートルに変換
+    pub fn to_square_meters(&self, value: f64) -> f64 {
+        value * self.square_meters_per_unit()
+    }
+
+    /// 平方メートルの値をこの単位に変換
+    pub fn from_square_meters(&self, square_meters: f64) -> f64 {
+        square_meters / self.square_meters_per_unit()
+    }
+
+    /// この単位の値を別の単位に変換
+    pub fn convert(&self, value: f64, to: AreaUnit) -> f64 {
+        to.from_square_meters(self.to_square_meters(value))
+    }
+}
+
+impl VolumeUnit {
+    /// LandXMLのvolumeUnit属性値から解析
+    pub fn from_landxml(value: &str) -> Option<Self> {
+        match value.trim() {
+            "cubicMeter" => Some(Self::CubicMeter),
+            "liter" => Some(Self::Liter),
+            "hectareMeter" => Some(Self::HectareMeter),
+            "cubicFeet" => Some(Self::CubicFoot),
+            "cubicInch" => Some(Self::CubicInch),
+            "cubicYard" => Some(Self::CubicYard),
+            "acreFeet" => Some(Self::AcreFoot),
+            _ => None,
+        }
+    }
+
+    /// LandXMLのvolumeUnit属性値を取得
+    pub fn as_landxml_str(&self) -> &'static str {
+        match self {
+            Self::CubicMeter => "cubicMeter",
+            Self::Liter => "liter",
+            Self::HectareMeter => "hectareMeter",
+            Self::CubicFoot => "cubicFeet",
+            Self::CubicInch => "cubicInch",
+            Self::CubicYard => "cubicYard",
+            Self::AcreFoot => "acreFeet",
+        }
+    }
+
+    /// 1単位あたりの立方メートル数
+    pub fn cubic_meters_per_unit(&self) -> f64 {
+        match self {
+            Self::CubicMeter => 1.0,
+            Self::Liter => 0.001,
+            Self::HectareMeter => 10_000.0,
+            Self::CubicFoot => 0.028_316_846_592,
+            Self::CubicInch => 0.000_016_387_064,
+            Self::CubicYard => 0.764_554_857_984,
+            // 国際エーカー×フィート
+            Self::AcreFoot => 1_233.481_837_547_52,
+        }
+    }
+
+    /// この単位の値を立方メートルに変換
+    pub fn to_cubic_meters(&self, value: f64) -> f64 {
+        value * self.cubic_meters_per_unit()
+    }
+
+    /// 立方メートルの値をこの単位に変換
+    pub fn from_cubic_meters(&self, cubic_meters: f64) -> f64 {
+        cubic_meters / self.cubic_meters_per_unit()
+    }
+
+    /// この単位の値を別の単位に変換
+    pub fn convert(&self, value: f64, to: VolumeUnit) -> f64 {
+        to.from_cubic_meters(self.to_cubic_meters(value))
+    }
+}
+
+/// 単位系要素の単位属性を解析（省略時は既定値、未知の単位名はエラー）
+fn unit_attr<T>(
+    system: &XmlElement,
+    key: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    default: T,
+) -> Result<T, LandXMLError> {
+    match system.attr(key) {
+        Some(value) => parse(value).ok_or_else(|| LandXMLError::InvalidFormat {
+            message: format!("Unknown {} '{}' in {}", key, value, system.name),
+        }),
+        None => Ok(default),
+    }
+}
+
+impl Units {
+    /// Units要素（Metric/Imperial）を解析
+    ///
+    /// 未知の単位名はエラーとします。角度・方向角の単位が省略された場合は
+    /// LandXMLの既定値（radians）、その他の単位は各単位系の標準値とします。
+    pub(crate) fn from_xml(element: &XmlElement) -> Result<Self, LandXMLError> {
+        let extensions = element.extensions(&[], &["Metric", "Imperial"]);
+        let Some(system) = element
+            .child("Metric")
+            .or_else(|| element.child("Imperial"))
+        else {
+            return Ok(Self {
+                extensions,
+                ..Default::default()
+            });
+        };
+
+        let imperial = system.name == "Imperial";
+        Ok(Self {
+            linear_unit: unit_attr(
+                system,
+                "linearUnit",
+                LinearUnit::from_landxml,
+                if imperial {
+                    LinearUnit::Foot
+                } else {
+                    LinearUnit::Meter
+                },
+            )?,
+            // LandXMLの既定値はradians
+            angular_unit: unit_attr(
+                system,
+                "angularUnit",
+                AngularUnit::from_landxml,
+                AngularUnit::Radian,
+            )?,
+            direction_unit: unit_attr(
+                system,
+                "directionUnit",
+                AngularUnit::from_landxml,
+                AngularUnit::Radian,
+            )?,
+            area_unit: unit_attr(
+                system,
+                "areaUnit",
+                AreaUnit::from_landxml,
+                if imperial {
+                    AreaUnit::SquareFoot
+                } else {
+                    AreaUnit::SquareMeter
+                },
+            )?,
+            volume_unit: unit_attr(
+                system,
+                "volumeUnit",
+                VolumeUnit::from_landxml,
+                if imperial {
+                    VolumeUnit::CubicFoot
+                } else {
+                    VolumeUnit::CubicMeter
+                },
+            )?,
+            temperature_unit: unit_attr(
+                system,
+                "temperatureUnit",
+                TemperatureUnit::from_landxml,
+                if imperial {
+                    TemperatureUnit::Fahrenheit
+                } else {
+                    TemperatureUnit::Celsius
+                },
+            )?,
+            pressure_unit: unit_attr(
+                system,
+                "pressureUnit",
+                PressureUnit::from_landxml,
+                if imperial {
+                    PressureUnit::InchHg
+                } else {
+                    PressureUnit::Hectopascal
+                },
+            )?,
+            extensions,
+        })
+    }
+
+    /// メートル法の単位系かどうか
+    pub fn is_metric(&self) -> bool {
+        self.linear_unit.is_metric()
+    }
+
+    /// モデルの基準単位（メートル・ラジアン）と一致するかどうか
+    pub fn is_canonical(&self) -> bool {
+        self.linear_unit == LinearUnit::Meter
+            && self.angular_unit == AngularUnit::Radian
+            && self.direction_unit == AngularUnit::Radian
+    }
+
+    /// 長さをメートルに変換
+    pub fn length_to_meters(&self, value: f64) -> f64 {
+        self.linear_unit.to_meters(value)
+    }
+
+    /// 面積を平方メートルに変換
+    pub fn area_to_square_meters(&self, value: f64) -> f64 {
+        self.area_unit.to_square_meters(value)
+    }
+
+    /// 体積を立方メートルに変換
+    pub fn volume_to_cubic_meters(&self, value: f64) -> f64 {
+        self.volume_unit.to_cubic_meters(value)
+    }
+
+    /// 角度をラジアンに変換
+    pub fn angle_to_radians(&self, value: f64) -> f64 {
+        self.angular_unit.to_radians(value)
+    }
+
+    /// 方向角をラジアンに変換
+    pub fn direction_to_radians(&self, value: f64) -> f64 {
+        self.direction_unit.to_radians(value)
+    }
+
+    /// 文書の値をこの単位系からモデルの基準単位（メートル・ラジアン）に変換
+    pub fn normalize(&self, doc: &mut LandXML) {
+        let length = |v| self.linear_unit.to_meters(v);
+        let angle = |v| self.angular_unit.to_radians(v);
+        let direction = |v| self.direction_unit.to_radians(v);
+        self.quantity_map(&length, &angle, &direction).apply(doc);
+    }
+
+    /// 文書の値をモデルの基準単位からこの単位系に変換
+    pub fn denormalize(&self, doc: &mut LandXML) {
+        let length = |v| self.linear_unit.from_meters(v);
+        let angle = |v| self.angular_unit.from_radians(v);
+        let direction = |v| self.direction_unit.from_radians(v);
+        self.quantity_map(&length, &angle, &direction).apply(doc);
+    }
+
+    /// 文書の単位系をこの単位系に変換
+    ///
+    /// モデルの値と未知要素の測点・標高・方向角は基準単位（メートル・ラジアン）で
+    /// 保持しているため、Units要素を置き換えると出力時にはこの単位系で書き出されます。
+    /// 未知要素として保持している面積・体積（Definitionのarea、CrossSectのarea・volume、
+    /// SurfVolumeのvol、Parcelのarea）は元の単位系の値から換算します。
+    pub fn convert_document(&self, doc: &mut LandXML) {
+        let source = doc.units.clone().unwrap_or_default();
+        let area = |v| source.area_unit.convert(v, self.area_unit);
+        let volume = |v| source.volume_unit.convert(v, self.volume_unit);
+        QuantityMap {
+            area: (source.area_unit != self.area_unit).then_some(&area),
+            volume: (source.volume_unit != self.volume_unit).then_some(&volume),
+            ..Default::default()
+        }
+        .apply_extensions(doc);
+        doc.units = Some(self.clone());
+    }
+
+    /// 基準単位と異なる値のみを変換する対応表
+    fn quantity_map<'a>(
+        &self,
+        length: &'a dyn Fn(f64) -> f64,
+        angle: &'a dyn Fn(f64) -> f64,
+        direction: &'a dyn Fn(f64) -> f64,
+    ) -> QuantityMap<'a> {
+        QuantityMap {
+            length: (self.linear_unit != LinearUnit::Meter).then_some(length),
+            angle: (self.angular_unit != AngularUnit::Radian).then_some(angle),
+            direction: (self.direction_unit != AngularUnit::Radian).then_some(direction),
+            ..Default::default()
+        }
+    }
+
+    /// Units要素を生成
+    pub(crate) fn to_xml(&self) -> XmlElement {
+        let system = if self.is_metric() {
+            "Metric"
+        } else {
+            "Imperial"
+        };
+        XmlElement::new("Units")
+            .with_child(
+                XmlElement::new(system)
+                    .with_attr("areaUnit", self.area_unit.as_landxml_str())
+                    .with_attr("linearUnit", self.linear_unit.as_landxml_str())
+                    .with_attr("volumeUnit", self.volume_unit.as_landxml_str())
+                    .with_attr("temperatureUnit", self.temperature_unit.as_landxml_str())
+                    .with_attr("pressureUnit", self.pressure_unit.as_landxml_str())
+                    .with_attr("angularUnit", self.angular_unit.as_landxml_str())
+                    .with_attr("directionUnit", self.direction_unit.as_landxml_str()),
+            )
+            .with_extensions(&self.extensions)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::RawElement;
 
     #[test]
     fn test_default_units() {
@@ -67,4 +607,209 @@ mod tests {
         assert_eq!(units.area_unit, AreaUnit::SquareMeter);
         assert_eq!(units.volume_unit, VolumeUnit::CubicMeter);
     }
+
+    #[test]
+    fn test_units_from_xml() {
+        let xml = r#"<Units>
+            <Metric areaUnit="squareMeter" linearUnit="meter" volumeUnit="cubicMeter" temperatureUnit="celsius" pressureUnit="HPA" angularUnit="decimal dd.mm.ss" directionUnit="decimal  dd.mm.ss"/>
+        </Units>"#;
+        let units = Units::from_xml(&XmlElement::parse_str(xml).unwrap()).unwrap();
+        assert!(units.is_metric());
+        assert_eq!(units.angular_unit, AngularUnit::DecimalDms);
+        assert_eq!(units.direction_unit, AngularUnit::DecimalDms);
+        assert_eq!(units.angular_unit.as_landxml_str(), "decimal dd.mm.ss");
+        assert_eq!(units.temperature_unit, TemperatureUnit::Celsius);
+        assert_eq!(units.pressure_unit, PressureUnit::Hectopascal);
+
+        let xml = r#"<Units><Imperial linearUnit="USSurveyFoot" pressureUnit="inchHG" angularUnit="decimal degrees"/></Units>"#;
+        let units = Units::from_xml(&XmlElement::parse_str(xml).unwrap()).unwrap();
+        assert!(!units.is_metric());
+        assert_eq!(units.direction_unit, AngularUnit::Radian);
+        assert_eq!(units.temperature_unit, TemperatureUnit::Fahrenheit);
+        assert_eq!(units.pressure_unit, PressureUnit::InchHg);
+        let imperial = units.to_xml();
+        let system = imperial.child("Imperial").unwrap();
+        assert_eq!(system.attr("pressureUnit"), Some("inHG"));
+        assert_eq!(system.attr("directionUnit"), Some("radians"));
+        // 省略された面積・体積は単位系の標準値
+        assert_eq!(system.attr("areaUnit"), Some("squareFoot"));
+        assert_eq!(system.attr("volumeUnit"), Some("cubicFeet"));
+
+        let xml = r#"<Units><Imperial areaUnit="squareMiles" volumeUnit="acreFeet"/></Units>"#;
+        let units = Units::from_xml(&XmlElement::parse_str(xml).unwrap()).unwrap();
+        assert_eq!(units.area_unit, AreaUnit::SquareMile);
+        assert_eq!(units.volume_unit, VolumeUnit::AcreFoot);
+        let xml = r#"<Units><Metric areaUnit="squareCentimeter" volumeUnit="liter"/></Units>"#;
+        let units = Units::from_xml(&XmlElement::parse_str(xml).unwrap()).unwrap();
+        assert_eq!(units.area_unit.as_landxml_str(), "squareCentimeter");
+        assert_eq!(units.volume_unit.as_landxml_str(), "liter");
+
+        // 未知の単位名はエラー
+        let xml = r#"<Units><Imperial volumeUnit="barrel"/></Units>"#;
+        assert!(matches!(
+            Units::from_xml(&XmlElement::parse_str(xml).unwrap()),
+            Err(LandXMLError::InvalidFormat { .. })
+        ));
+    }
+
+    #[test]
+    fn test_decimal_dms() {
+        // 30.2045 = 30°20'45"
+        let degrees = dms_to_degrees(30.2045);
+        assert!((degrees - (30.0 + 20.0 / 60.0 + 45.0 / 3600.0)).abs() < 1e-12);
+        assert!((dms_to_degrees(-0.3) + 0.5).abs() < 1e-12);
+        assert!((degrees_to_dms(degrees) - 30.2045).abs() < 1e-12);
+        // 59.99999999秒は繰り上げる
+        assert!((degrees_to_dms(10.0 - 1e-12) - 10.0).abs() < 1e-12);
+
+        let units = Units {
+            direction_unit: AngularUnit::DecimalDms,
+            ..Default::default()
+        };
+        let radians = units.direction_to_radians(30.2045);
+        assert!((radians - degrees.to_radians()).abs() < 1e-12);
+        assert!(
+            (AngularUnit::Gradian.to_radians(100.0) - std::f64::consts::FRAC_PI_2).abs() < 1e-12
+        );
+        assert!((AngularUnit::DecimalDms.from_radians(radians) - 30.2045).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_conversion_factors() {
+        assert!((LinearUnit::UsSurveyFoot.to_meters(3937.0) - 1200.0).abs() < 1e-9);
+        assert!((LinearUnit::Foot.convert(3.0, LinearUnit::Inch) - 36.0).abs() < 1e-9);
+        assert!((AreaUnit::Acre.convert(1.0, AreaUnit::SquareFoot) - 43_560.0).abs() < 1e-6);
+        assert!((AreaUnit::Hectare.to_square_meters(1.5) - 15_000.0).abs() < 1e-9);
+        assert!((VolumeUnit::CubicYard.convert(1.0, VolumeUnit::CubicFoot) - 27.0).abs() < 1e-9);
+        assert!((VolumeUnit::CubicFoot.from_cubic_meters(1.0) - 35.314_666_7).abs() < 1e-6);
+        assert!((AreaUnit::SquareMile.convert(1.0, AreaUnit::Acre) - 640.0).abs() < 1e-9);
+        assert!((AreaUnit::SquareInch.convert(144.0, AreaUnit::SquareFoot) - 1.0).abs() < 1e-12);
+        assert!((VolumeUnit::AcreFoot.convert(1.0, VolumeUnit::CubicFoot) - 43_560.0).abs() < 1e-6);
+        assert!((VolumeUnit::CubicInch.convert(1728.0, VolumeUnit::CubicFoot) - 1.0).abs() < 1e-12);
+        assert!((VolumeUnit::HectareMeter.convert(1.0, VolumeUnit::Liter) - 1e7).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_normalize_round_trip() {
+        let xml = r#"<Alignment name="A" staStart="100.0">
+            <CoordGeom>
+              <Curve rot="cw" radius="1000.0" delta="10.3000" dirStart="90.0" length="184.0">
+                <Start>0.0 0.0 10.0</Start><End>100.0 100.0</End>
+              </Curve>
+            </CoordGeom>
+        </Alignment>"#;
+        let alignment =
+            crate::models::Alignment::from_xml(&XmlElement::parse_str(xml).unwrap()).unwrap();
+        let mut doc = LandXML {
+            alignments: vec![alignment],
+            ..Default::default()
+        };
+        let units = Units {
+            linear_unit: LinearUnit::Foot,
+            angular_unit: AngularUnit::DecimalDms,
+            direction_unit: AngularUnit::DecimalDegrees,
+            ..Default::default()
+        };
+        let original = doc.clone();
+        units.normalize(&mut doc);
+
+        let alignment = &doc.alignments[0];
+        assert!((alignment.sta_start.unwrap() - 30.48).abs() < 1e-9);
+        let crate::alignments::GeomElement::Curve(curve) =
+            &alignment.coord_geom.as_ref().unwrap().elements[0]
+        else {
+            panic!("expected curve");
+        };
+        assert!((curve.radius.unwrap() - 304.8).abs() < 1e-9);
+        assert!((curve.start_elevation.unwrap() - 3.048).abs() < 1e-9);
+        assert!((curve.delta.unwrap() - 10.5f64.to_radians()).abs() < 1e-12);
+        assert!((curve.dir_start.unwrap() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+
+        units.denormalize(&mut doc);
+        let json = |doc: &LandXML| serde_json::to_value(&doc.alignments).unwrap();
+        let (restored, original) = (json(&doc), json(&original));
+        let restored = restored.pointer("/0/coord_geom/elements/0/Curve").unwrap();
+        let original = original.pointer("/0/coord_geom/elements/0/Curve").unwrap();
+        for key in ["radius", "delta", "dir_start", "length"] {
+            let (a, b) = (
+                restored[key].as_f64().unwrap(),
+                original[key].as_f64().unwrap(),
+            );
+            assert!((a - b).abs() < 1e-9, "{}: {} != {}", key, a, b);
+        }
+    }
+
+    #[test]
+    fn test_normalize_extensions() {
+        let alignment = r#"<Alignment name="A">
+            <StaEquation staBack="100" staAhead="110.5"/>
+            <Feature name="Interval"><Property label="main" value="20"/></Feature>
+        </Alignment>"#;
+        let cg_points = r#"<CgPoints>
+            <CgPoint name="P1" featureRef="0">10.0 20.0</CgPoint>
+            <Feature name="0">
+              <Property label="sta" value="-90"/>
+              <Property label="tangentDirectionAngle" value="30.2045"/>
+            </Feature>
+        </CgPoints>"#;
+        let parse = |xml| XmlElement::parse_str(xml).unwrap();
+        let mut doc = LandXML {
+            alignments: vec![crate::models::Alignment::from_xml(&parse(alignment)).unwrap()],
+            cg_points: vec![crate::cg_points::CgPoints::from_xml(&parse(cg_points)).unwrap()],
+            ..Default::default()
+        };
+        let units = Units {
+            linear_unit: LinearUnit::Foot,
+            direction_unit: AngularUnit::DecimalDms,
+            ..Default::default()
+        };
+        let original = doc.clone();
+        units.normalize(&mut doc);
+
+        let value = |element: &RawElement, key: &str| -> f64 {
+            let attribute = |e: &RawElement, k: &str| {
+                e.attributes
+                    .iter()
+                    .find(|(a, _)| a == k)
+                    .map(|(_, v)| v.clone())
+            };
+            attribute(element, key)
+                .or_else(|| {
+                    element
+                        .children
+                        .iter()
+                        .find(|p| attribute(p, "label").as_deref() == Some(key))
+                        .and_then(|p| attribute(p, "value"))
+                })
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let extensions = &doc.alignments[0].extensions;
+        let equation = extensions.elements_named("StaEquation").next().unwrap();
+        assert!((value(equation, "staBack") - 30.48).abs() < 1e-9);
+        assert!((value(equation, "staAhead") - 110.5 * 0.3048).abs() < 1e-9);
+        let interval = extensions.elements_named("Feature").next().unwrap();
+        assert!((value(interval, "main") - 6.096).abs() < 1e-9);
+
+        let feature = doc.cg_points[0]
+            .extensions
+            .elements_named("Feature")
+            .next()
+            .unwrap();
+        assert!((value(feature, "sta") + 27.432).abs() < 1e-9);
+        let expected = (30.0 + 20.0 / 60.0 + 45.0 / 3600.0f64).to_radians();
+        assert!((value(feature, "tangentDirectionAngle") - expected).abs() < 1e-12);
+
+        // 出力時の逆変換で元の表記に戻る
+        units.denormalize(&mut doc);
+        assert_eq!(
+            doc.alignments[0].extensions,
+            original.alignments[0].extensions
+        );
+        assert_eq!(
+            doc.cg_points[0].extensions,
+            original.cg_points[0].extensions
+        );
+    }
 }
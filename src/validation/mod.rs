@@ -0,0 +1,11 @@
+//! Validation module for LandXML/J-LandXML deliverables
+//!
+//! This module provides document-level checks:
+//! - XSD schema conformance (J-LandXML Ver.1.7 schema bundled)
+//! - MLIT delivery rules (電子納品チェック) with a configurable rule catalogue
+
+pub mod rules;
+pub mod xsd;
+
+pub use rules::{DeliveryReport, DeliveryRule, DeliveryRuleChecker, RuleSeverity, RuleViolation};
+pub use xsd::{SchemaViolation, SchemaViolationKind, XsdValidator};
@@ -0,0 +1,620 @@
+//! MLIT J-LandXML delivery rule checker (電子納品チェック)
+//!
+//! XSD適合性とは別に、国土交通省 J-LandXML 交換標準の業務ルールを検査します：
+//! - 規則カタログ（ID・説明・重大度）
+//! - 規則ごとの有効/無効、重大度の変更
+//! - 座標系の検査結果（ValidationWarning）の取り込み
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::coordinate_system::{
+    CoordinateSystemValidator, JLandXmlDocument, JLandXmlVersion, ValidationWarning,
+};
+use crate::project::{Project, ProjectPhase};
+
+/// 規則違反の重大度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum RuleSeverity {
+    /// 参考情報
+    Info,
+    /// 警告（納品可能だが確認が必要）
+    Warning,
+    /// エラー（納品不可）
+    Error,
+}
+
+impl RuleSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleSeverity::Info => "info",
+            RuleSeverity::Warning => "warning",
+            RuleSeverity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for RuleSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 規則の検出結果（違反1件分）
+struct Finding {
+    path: String,
+    message: String,
+    warning: Option<ValidationWarning>,
+}
+
+impl Finding {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            warning: None,
+        }
+    }
+}
+
+/// 納品規則
+#[derive(Clone)]
+pub struct DeliveryRule {
+    /// 規則ID（例: "JLX-001"）
+    pub id: &'static str,
+    /// 規則の説明
+    pub description: &'static str,
+    /// 違反時の重大度
+    pub severity: RuleSeverity,
+    /// 有効かどうか
+    pub enabled: bool,
+    check: fn(&JLandXmlDocument) -> Vec<Finding>,
+}
+
+impl fmt::Debug for DeliveryRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeliveryRule")
+            .field("id", &self.id)
+            .field("description", &self.description)
+            .field("severity", &self.severity)
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
+
+/// 規則違反
+#[derive(Debug, Clone)]
+pub struct RuleViolation {
+    pub rule_id: &'static str,
+    pub severity: RuleSeverity,
+    /// 対象要素のパス（例: "Alignments/Alignment[○○路線]"）
+    pub path: String,
+    pub message: String,
+    /// 座標系検査に由来する場合の元の警告
+    pub warning: Option<ValidationWarning>,
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} {}: {}",
+            self.severity, self.rule_id, self.path, self.message
+        )
+    }
+}
+
+/// 検査結果
+#[derive(Debug, Clone, Default)]
+pub struct DeliveryReport {
+    pub violations: Vec<RuleViolation>,
+}
+
+impl DeliveryReport {
+    /// エラーの違反を列挙
+    pub fn errors(&self) -> impl Iterator<Item = &RuleViolation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == RuleSeverity::Error)
+    }
+
+    /// 警告の違反を列挙
+    pub fn warnings(&self) -> impl Iterator<Item = &RuleViolation> {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == RuleSeverity::Warning)
+    }
+
+    /// エラーがなく納品可能かどうか
+    pub fn is_deliverable(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// 指定規則の違反を列挙
+    pub fn by_rule<'a>(&'a self, rule_id: &'a str) -> impl Iterator<Item = &'a RuleViolation> {
+        self.violations.iter().filter(move |v| v.rule_id == rule_id)
+    }
+}
+
+/// 電子納品規則チェッカー
+#[derive(Debug, Clone)]
+pub struct DeliveryRuleChecker {
+    rules: Vec<DeliveryRule>,
+}
+
+impl Default for DeliveryRuleChecker {
+    fn default() -> Self {
+        Self::mlit()
+    }
+}
+
+impl DeliveryRuleChecker {
+    /// 国土交通省 J-LandXML 交換標準の規則カタログで作成
+    pub fn mlit() -> Self {
+        Self {
+            rules: mlit_catalogue(),
+        }
+    }
+
+    /// 規則カタログを取得
+    pub fn rules(&self) -> &[DeliveryRule] {
+        &self.rules
+    }
+
+    /// 指定規則の有効/無効を設定（存在しないIDは無視）
+    pub fn with_rule_enabled(mut self, rule_id: &str, enabled: bool) -> Self {
+        if let Some(rule) = self.rules.iter_mut().find(|r| r.id == rule_id) {
+            rule.enabled = enabled;
+        }
+        self
+    }
+
+    /// 指定規則の重大度を変更（存在しないIDは無視）
+    pub fn with_severity(mut self, rule_id: &str, severity: RuleSeverity) -> Self {
+        if let Some(rule) = self.rules.iter_mut().find(|r| r.id == rule_id) {
+            rule.severity = severity;
+        }
+        self
+    }
+
+    /// 指定規則が有効かどうか
+    pub fn is_enabled(&self, rule_id: &str) -> bool {
+        self.rules.iter().any(|r| r.id == rule_id && r.enabled)
+    }
+
+    /// 文書を検査
+    pub fn check(&self, doc: &JLandXmlDocument) -> DeliveryReport {
+        let violations = self
+            .rules
+            .iter()
+            .filter(|rule| rule.enabled)
+            .flat_map(|rule| {
+                (rule.check)(doc)
+                    .into_iter()
+                    .map(move |finding| RuleViolation {
+                        rule_id: rule.id,
+                        severity: rule.severity,
+                        path: finding.path,
+                        message: finding.message,
+                        warning: finding.warning,
+                    })
+            })
+            .collect();
+        DeliveryReport { violations }
+    }
+}
+
+// ============================================================================
+// 規則カタログ
+// ============================================================================
+
+fn mlit_catalogue() -> Vec<DeliveryRule> {
+    let rule = |id, description, severity, check| DeliveryRule {
+        id,
+        description,
+        severity,
+        enabled: true,
+        check,
+    };
+    vec![
+        rule(
+            "JLX-001",
+            "適用基準（applicationCriterion）が記載されていること",
+            RuleSeverity::Error,
+            check_criterion_present,
+        ),
+        rule(
+            "JLX-002",
+            "applicationCriterionが対応するJ-LandXMLのバージョンであること",
+            RuleSeverity::Error,
+            check_criterion_version,
+        ),
+        rule(
+            "JLX-003",
+            "projectPhaseが定義された設計段階であること",
+            RuleSeverity::Error,
+            check_project_phase,
+        ),
+        rule(
+            "JLX-004",
+            "座標系が定義され、平面直角座標系の系番号が特定できること",
+            RuleSeverity::Error,
+            check_coordinate_system_defined,
+        ),
+        rule(
+            "JLX-005",
+            "座標系の設定（differTP、測地系、系番号表記）が整合していること",
+            RuleSeverity::Warning,
+            check_coordinate_system_consistency,
+        ),
+        rule(
+            "JLX-006",
+            "詳細設計ではすべての線形に平面・縦断・横断（CoordGeom/Profile/CrossSects）があること",
+            RuleSeverity::Error,
+            check_detailed_design_alignments,
+        ),
+        rule(
+            "JLX-007",
+            "stratumMainDataが文書内の既存データを参照していること",
+            RuleSeverity::Error,
+            check_stratum_main_data,
+        ),
+        rule(
+            "JLX-008",
+            "線形名が空でなく一意であること",
+            RuleSeverity::Error,
+            check_alignment_names,
+        ),
+        rule(
+            "JLX-009",
+            "TIN地表面が3点以上と1つ以上の三角形を持ち、面が定義済みの点のみを参照すること",
+            RuleSeverity::Warning,
+            check_surfaces,
+        ),
+        rule(
+            "JLX-010",
+            "単位系がメートル法（Metric）であること",
+            RuleSeverity::Warning,
+            check_metric_units,
+        ),
+        rule(
+            "JLX-011",
+            "宣言バージョンで定義された要素・プロパティのみを使用していること",
+            // バージョン間の差異は出典未確認のため警告とする
+            RuleSeverity::Warning,
+            check_version_profile,
+        ),
+    ]
+}
+
+fn project(doc: &JLandXmlDocument) -> Option<&Project> {
+    doc.base.project.as_ref()
+}
+
+fn application_criterion(doc: &JLandXmlDocument) -> Option<&str> {
+    doc.application_criterion
+        .as_deref()
+        .or_else(|| project(doc).and_then(|p| p.application_criterion.as_deref()))
+}
+
+fn check_criterion_present(doc: &JLandXmlDocument) -> Vec<Finding> {
+    match application_criterion(doc) {
+        Some(value) if !value.trim().is_empty() => Vec::new(),
+        _ => vec![Finding::new(
+            "Project",
+            "applicationCriterion is not specified",
+        )],
+    }
+}
+
+fn check_criterion_version(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let Some(criterion) = application_criterion(doc) else {
+        return Vec::new();
+    };
+    match JLandXmlVersion::from_criterion(criterion) {
+        Ok(_) => Vec::new(),
+        Err(_) => {
+            let supported: Vec<&str> = JLandXmlVersion::ALL.iter().map(|v| v.as_str()).collect();
+            vec![Finding::new(
+                "Project",
+                format!(
+                    "applicationCriterion '{}' is not a supported version ({})",
+                    criterion,
+                    supported.join(", ")
+                ),
+            )]
+        }
+    }
+}
+
+fn check_project_phase(doc: &JLandXmlDocument) -> Vec<Finding> {
+    match project(doc).and_then(|p| p.project_phase.as_deref()) {
+        None => vec![Finding::new("Project", "projectPhase is not specified")],
+        Some(value) if ProjectPhase::from_label(value).is_none() => vec![Finding::new(
+            "Project",
+            format!("projectPhase '{}' is not a defined phase", value),
+        )],
+        Some(_) => Vec::new(),
+    }
+}
+
+fn check_coordinate_system_defined(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let Some(cs) = &doc.coordinate_system else {
+        return vec![Finding::new(
+            "CoordinateSystem",
+            "CoordinateSystem is not defined",
+        )];
+    };
+    let mut findings = Vec::new();
+    if cs.horizontal_coordinate_system_name.trim().is_empty() {
+        findings.push(Finding::new(
+            "CoordinateSystem",
+            "horizontalCoordinateSystemName is not specified",
+        ));
+    } else if cs.plane_coordinate_zone.is_none() {
+        findings.push(Finding::new(
+            "CoordinateSystem",
+            format!(
+                "horizontalCoordinateSystemName '{}' does not identify a plane rectangular zone",
+                cs.horizontal_coordinate_system_name
+            ),
+        ));
+    }
+    findings
+}
+
+fn check_coordinate_system_consistency(doc: &JLandXmlDocument) -> Vec<Finding> {
+    // バージョン関連の警告はJLX-011、地表面の警告はJLX-009で扱う
+    let mut warnings: Vec<ValidationWarning> = doc
+        .warnings
+        .iter()
+        .filter(|w| {
+            !matches!(
+                w,
+                ValidationWarning::UnsupportedVersion { .. }
+                    | ValidationWarning::VersionIncompatibleConstruct { .. }
+                    | ValidationWarning::UnresolvedSurfaceFaces { .. }
+            )
+        })
+        .cloned()
+        .collect();
+    let mut findings = Vec::new();
+
+    if let Some(cs) = &doc.coordinate_system {
+        if let Some(zone) = cs.plane_coordinate_zone {
+            match CoordinateSystemValidator::validate_complete_system(
+                cs.horizontal_datum,
+                cs.vertical_datum,
+                zone,
+                cs.differ_tp,
+            ) {
+                Ok(found) => warnings.extend(found),
+                Err(e) => findings.push(Finding::new("CoordinateSystem", e.to_string())),
+            }
+        }
+    }
+
+    findings.extend(warnings.into_iter().map(|warning| Finding {
+        path: "CoordinateSystem".to_string(),
+        message: warning.to_string(),
+        warning: Some(warning),
+    }));
+    findings
+}
+
+fn check_detailed_design_alignments(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let phase = project(doc).and_then(Project::phase);
+    if phase != Some(ProjectPhase::Detailed) {
+        return Vec::new();
+    }
+    let mut findings = Vec::new();
+    for alignment in &doc.base.alignments {
+        let missing: Vec<&str> = [
+            ("CoordGeom", alignment.coord_geom.is_some()),
+            ("Profile", alignment.profile.is_some()),
+            ("CrossSects", alignment.cross_sects.is_some()),
+        ]
+        .into_iter()
+        .filter(|(_, present)| !present)
+        .map(|(name, _)| name)
+        .collect();
+        if !missing.is_empty() {
+            findings.push(Finding::new(
+                format!("Alignments/Alignment[{}]", alignment.name),
+                format!("detailed design alignment lacks {}", missing.join(", ")),
+            ));
+        }
+    }
+    findings
+}
+
+fn check_stratum_main_data(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let Some(value) = project(doc).and_then(|p| p.stratum_main_data.as_deref()) else {
+        return Vec::new();
+    };
+    let value = value.trim();
+    let surfaces: Vec<&str> = doc
+        .base
+        .surfaces
+        .iter()
+        .flat_map(|s| s.surfaces.iter())
+        .filter_map(|s| s.name.as_deref())
+        .collect();
+    let has_surfaces = doc.base.surfaces.iter().any(|s| !s.surfaces.is_empty());
+    let has_cross_sects = doc.base.alignments.iter().any(|a| a.cross_sects.is_some());
+
+    // 種別（Surface/CrossSect）の指定、またはSurface名による参照
+    let resolved = match value {
+        "Surface" | "Surfaces" => has_surfaces,
+        "CrossSect" | "CrossSects" | "CrossSectSurf" => has_cross_sects,
+        name => surfaces.contains(&name),
+    };
+    if resolved {
+        Vec::new()
+    } else {
+        vec![Finding::new(
+            "Project",
+            format!(
+                "stratumMainData '{}' does not reference existing data",
+                value
+            ),
+        )]
+    }
+}
+
+fn check_alignment_names(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let mut seen = HashSet::new();
+    let mut findings = Vec::new();
+    for alignment in &doc.base.alignments {
+        if alignment.name.trim().is_empty() {
+            findings.push(Finding::new(
+                "Alignments/Alignment",
+                "alignment name is empty",
+            ));
+        } else if !seen.insert(alignment.name.as_str()) {
+            findings.push(Finding::new(
+                format!("Alignments/Alignment[{}]", alignment.name),
+                format!("alignment name '{}' is duplicated", alignment.name),
+            ));
+        }
+    }
+    findings
+}
+
+fn check_surfaces(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for surface in doc.base.surfaces.iter().flat_map(|s| s.surfaces.iter()) {
+        let path = format!(
+            "Surfaces/Surface[{}]",
+            surface.name.as_deref().unwrap_or_default()
+        );
+        if surface.points.len() < 3 {
+            findings.push(Finding::new(
+                path.clone(),
+                format!(
+                    "surface has {} points (at least 3 required)",
+                    surface.points.len()
+                ),
+            ));
+        }
+        if surface.triangles.is_empty() {
+            findings.push(Finding::new(path.clone(), "surface has no triangles"));
+        }
+        if !surface.unresolved_faces.is_empty() {
+            findings.push(Finding::new(
+                path,
+                format!(
+                    "{} faces do not reference three defined points",
+                    surface.unresolved_faces.len()
+                ),
+            ));
+        }
+    }
+    findings
+}
+
+fn check_version_profile(doc: &JLandXmlDocument) -> Vec<Finding> {
+    let Some(version) = doc.version() else {
+        return Vec::new();
+    };
+    version
+        .profile()
+        .unsupported_constructs(doc)
+        .into_iter()
+        .map(|construct| {
+            Finding::new(
+                "LandXML",
+                format!(
+                    "{} is not defined in J-LandXML Ver.{}",
+                    construct,
+                    version.as_str()
+                ),
+            )
+        })
+        .collect()
+}
+
+fn check_metric_units(doc: &JLandXmlDocument) -> Vec<Finding> {
+    match &doc.base.units {
+        Some(units) if !units.is_metric() => {
+            vec![Finding::new("Units", "units are not Metric")]
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JLandXmlParser;
+
+    fn sample() -> JLandXmlDocument {
+        JLandXmlParser::from_file("tests/data/j_landxml_sample.xml")
+            .unwrap()
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sample_report() {
+        let report = DeliveryRuleChecker::default().check(&sample());
+        let ids: Vec<&str> = report.violations.iter().map(|v| v.rule_id).collect();
+        // 見本データのSurfaceは2点のみで、面は未定義の点を参照している
+        assert_eq!(
+            ids,
+            vec!["JLX-009", "JLX-009", "JLX-009"],
+            "{:#?}",
+            report.violations
+        );
+        assert!(report.is_deliverable());
+    }
+
+    #[test]
+    fn test_rule_violations_and_toggles() {
+        let mut doc = sample();
+        doc.application_criterion = Some("MlitLandXmlVer.1.4".to_string());
+        doc.base.alignments[0].profile = None;
+        let duplicate = doc.base.alignments[0].clone();
+        doc.base.alignments.push(duplicate);
+
+        let checker = DeliveryRuleChecker::mlit().with_rule_enabled("JLX-009", false);
+        let report = checker.check(&doc);
+        assert_eq!(report.by_rule("JLX-002").count(), 1);
+        assert_eq!(report.by_rule("JLX-006").count(), 2);
+        assert_eq!(report.by_rule("JLX-008").count(), 1);
+        assert_eq!(report.by_rule("JLX-009").count(), 0);
+        assert!(!report.is_deliverable());
+
+        let relaxed = checker
+            .with_severity("JLX-002", RuleSeverity::Warning)
+            .with_rule_enabled("JLX-006", false)
+            .with_rule_enabled("JLX-008", false);
+        let report = relaxed.check(&doc);
+        assert!(report.is_deliverable());
+        assert_eq!(report.warnings().count(), 1);
+    }
+
+    #[test]
+    fn test_version_profile_rule() {
+        let mut doc = sample();
+        doc.j_landxml_version = Some("1.6".to_string());
+        let report = DeliveryRuleChecker::default().check(&doc);
+        assert_eq!(report.by_rule("JLX-011").count(), 2);
+    }
+
+    #[test]
+    fn test_coordinate_system_warnings() {
+        let mut doc = sample();
+        if let Some(cs) = doc.coordinate_system.as_mut() {
+            cs.differ_tp = None;
+        }
+        let report = DeliveryRuleChecker::default().check(&doc);
+        let violation = report.by_rule("JLX-005").next().unwrap();
+        assert!(matches!(
+            violation.warning,
+            Some(ValidationWarning::MissingDifferTp { .. })
+        ));
+    }
+}
@@ -0,0 +1,1244 @@
+//! XSD schema conformance validation
+//!
+//! J-LandXMLのXSDを読み込み、外部ツールを使わずに文書の適合性を検証します：
+//! - 必須属性の有無と未定義属性
+//! - 列挙型（stateType, spiralType, sideofRoadType, surfTypeEnum等）
+//! - 子要素の出現順序と出現回数（xs:sequence / xs:choice）
+//! - 値の型（xs:double, xs:date, xs:positiveInteger, リスト型の要素数等）
+//! - xs:uniqueによる一意性制約
+//!
+//! 検出した違反は行・列番号付きで報告します。
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::LandXMLError;
+use crate::xml::XmlElement;
+
+/// 同梱のJ-LandXML Ver.1.7スキーマ
+const J_LANDXML_V17_XSD: &str = include_str!("../../tests/data/J-LandXML_schema_v17.xsd");
+
+/// スキーマ違反の種類
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SchemaViolationKind {
+    /// スキーマに定義されていない要素
+    UnknownElement { element: String },
+    /// 内容モデル上、この位置に出現できない要素
+    UnexpectedElement { element: String, expected: String },
+    /// 必須の子要素が不足している
+    IncompleteContent { expected: String },
+    /// 必須属性がない
+    MissingAttribute { attribute: String },
+    /// 定義されていない属性
+    UnknownAttribute { attribute: String },
+    /// 属性値またはテキストが型に適合しない（targetは"@属性名"または"text()"）
+    InvalidValue {
+        target: String,
+        value: String,
+        reason: String,
+    },
+    /// テキストを持てない要素にテキストがある
+    UnexpectedText,
+    /// xs:uniqueの一意性制約違反
+    DuplicateValue { constraint: String, value: String },
+}
+
+impl fmt::Display for SchemaViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaViolationKind::UnknownElement { element } => {
+                write!(f, "element '{}' is not declared in the schema", element)
+            }
+            SchemaViolationKind::UnexpectedElement { element, expected } => {
+                write!(
+                    f,
+                    "element '{}' is not allowed here (content model: {})",
+                    element, expected
+                )
+            }
+            SchemaViolationKind::IncompleteContent { expected } => {
+                write!(f, "content is incomplete (content model: {})", expected)
+            }
+            SchemaViolationKind::MissingAttribute { attribute } => {
+                write!(f, "required attribute '{}' is missing", attribute)
+            }
+            SchemaViolationKind::UnknownAttribute { attribute } => {
+                write!(f, "attribute '{}' is not declared", attribute)
+            }
+            SchemaViolationKind::InvalidValue {
+                target,
+                value,
+                reason,
+            } => {
+                // 長い座標リストは先頭のみ表示
+                let shown: String = value.chars().take(40).collect();
+                let ellipsis = if value.chars().count() > 40 {
+                    "..."
+                } else {
+                    ""
+                };
+                write!(
+                    f,
+                    "invalid value '{}{}' for {}: {}",
+                    shown, ellipsis, target, reason
+                )
+            }
+            SchemaViolationKind::UnexpectedText => {
+                write!(f, "text content is not allowed")
+            }
+            SchemaViolationKind::DuplicateValue { constraint, value } => {
+                write!(f, "duplicate value '{}' violates '{}'", value, constraint)
+            }
+        }
+    }
+}
+
+/// スキーマ違反（行・列番号は1始まり）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub line: usize,
+    pub column: usize,
+    /// 要素パス（例: "/LandXML/Alignments/Alignment"）
+    pub path: String,
+    pub kind: SchemaViolationKind,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, self.path, self.kind
+        )
+    }
+}
+
+// ============================================================================
+// スキーマモデル
+// ============================================================================
+
+/// 組み込みデータ型
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Builtin {
+    String,
+    Double,
+    Integer,
+    PositiveInteger,
+    NonNegativeInteger,
+    Boolean,
+    Date,
+    Time,
+    DateTime,
+}
+
+impl Builtin {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "double" | "float" | "decimal" => Builtin::Double,
+            "integer" | "int" | "long" | "short" => Builtin::Integer,
+            "positiveInteger" => Builtin::PositiveInteger,
+            "nonNegativeInteger" => Builtin::NonNegativeInteger,
+            "boolean" => Builtin::Boolean,
+            "date" => Builtin::Date,
+            "time" => Builtin::Time,
+            "dateTime" => Builtin::DateTime,
+            _ => Builtin::String,
+        }
+    }
+
+    fn check(self, value: &str) -> Result<(), String> {
+        let v = value.trim();
+        let ok = match self {
+            Builtin::String => return Ok(()),
+            Builtin::Double => is_double(v),
+            Builtin::Integer => is_integer(v),
+            Builtin::PositiveInteger => {
+                is_integer(v)
+                    && !v.trim_start_matches('+').trim_start_matches('0').is_empty()
+                    && !v.starts_with('-')
+            }
+            Builtin::NonNegativeInteger => {
+                is_integer(v) && (!v.starts_with('-') || v[1..].bytes().all(|b| b == b'0'))
+            }
+            Builtin::Boolean => matches!(v, "true" | "false" | "1" | "0"),
+            Builtin::Date => is_date(v),
+            Builtin::Time => is_time(v),
+            Builtin::DateTime => v
+                .split_once('T')
+                .map(|(d, t)| is_date_body(d) && is_time(t))
+                .unwrap_or(false),
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("expected xs:{}", self.xs_name()))
+        }
+    }
+
+    fn xs_name(self) -> &'static str {
+        match self {
+            Builtin::String => "string",
+            Builtin::Double => "double",
+            Builtin::Integer => "integer",
+            Builtin::PositiveInteger => "positiveInteger",
+            Builtin::NonNegativeInteger => "nonNegativeInteger",
+            Builtin::Boolean => "boolean",
+            Builtin::Date => "date",
+            Builtin::Time => "time",
+            Builtin::DateTime => "dateTime",
+        }
+    }
+}
+
+/// 制約ファセット
+#[derive(Debug, Clone, Default)]
+struct Facets {
+    enumeration: Vec<String>,
+    length: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+/// 単純型
+#[derive(Debug, Clone)]
+enum SimpleType {
+    Builtin(Builtin),
+    Named(String),
+    Restriction {
+        base: Box<SimpleType>,
+        facets: Facets,
+    },
+    List(Box<SimpleType>),
+}
+
+/// 属性宣言
+#[derive(Debug, Clone)]
+struct AttributeDecl {
+    name: String,
+    simple_type: SimpleType,
+    required: bool,
+}
+
+/// 出現回数付きの内容モデル
+#[derive(Debug, Clone)]
+struct Particle {
+    term: Term,
+    min: usize,
+    max: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Term {
+    Element(String),
+    Sequence(Vec<Particle>),
+    Choice(Vec<Particle>),
+}
+
+/// 複合型
+#[derive(Debug, Clone, Default)]
+struct ComplexType {
+    attributes: Vec<AttributeDecl>,
+    particle: Option<Particle>,
+    /// simpleContentの型
+    simple_content: Option<SimpleType>,
+    /// simpleContentの拡張元（名前付き複合型の場合に属性を継承）
+    base: Option<String>,
+    mixed: bool,
+}
+
+/// 要素の型
+#[derive(Debug, Clone)]
+enum ElementType {
+    Named(String),
+    Complex(ComplexType),
+    Simple(SimpleType),
+    Any,
+}
+
+/// 一意性制約
+#[derive(Debug, Clone)]
+struct UniqueConstraint {
+    name: String,
+    selector: Vec<String>,
+    field: String,
+}
+
+/// 要素宣言
+#[derive(Debug, Clone)]
+struct ElementDecl {
+    element_type: ElementType,
+    uniques: Vec<UniqueConstraint>,
+}
+
+/// 解析済みスキーマ
+#[derive(Debug, Clone, Default)]
+struct Schema {
+    elements: HashMap<String, ElementDecl>,
+    complex_types: HashMap<String, ComplexType>,
+    simple_types: HashMap<String, SimpleType>,
+}
+
+// ============================================================================
+// XSD解析
+// ============================================================================
+
+/// "xs:double" 等の型参照を解決
+fn type_reference(name: &str) -> SimpleType {
+    match name.split_once(':') {
+        Some((prefix, local)) if prefix == "xs" || prefix == "xsd" => {
+            SimpleType::Builtin(Builtin::from_name(local))
+        }
+        Some((_, local)) => SimpleType::Named(local.to_string()),
+        None => SimpleType::Named(name.to_string()),
+    }
+}
+
+fn local(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn parse_occurs(element: &XmlElement) -> (usize, Option<usize>) {
+    let min = element
+        .attr("minOccurs")
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(1);
+    let max = match element.attr("maxOccurs").map(str::trim) {
+        Some("unbounded") => None,
+        Some(v) => Some(v.parse().unwrap_or(1)),
+        None => Some(1),
+    };
+    (min, max)
+}
+
+impl Schema {
+    fn from_xml(root: &XmlElement) -> Result<Self, LandXMLError> {
+        if root.name != "schema" {
+            return Err(LandXMLError::InvalidFormat {
+                message: format!("Expected xs:schema root element, found '{}'", root.name),
+            });
+        }
+        let mut schema = Schema::default();
+        for child in &root.children {
+            let Some(name) = child.attr_string("name") else {
+                continue;
+            };
+            match child.name.as_str() {
+                "element" => {
+                    schema.elements.insert(name, Self::element_decl(child)?);
+                }
+                "complexType" => {
+                    schema
+                        .complex_types
+                        .insert(name, Self::complex_type(child)?);
+                }
+                "simpleType" => {
+                    schema.simple_types.insert(name, Self::simple_type(child)?);
+                }
+                _ => {}
+            }
+        }
+        Ok(schema)
+    }
+
+    fn element_decl(element: &XmlElement) -> Result<ElementDecl, LandXMLError> {
+        let element_type = if let Some(type_name) = element.attr("type") {
+            match type_reference(type_name) {
+                SimpleType::Named(name) => ElementType::Named(name),
+                builtin => ElementType::Simple(builtin),
+            }
+        } else if let Some(complex) = element.child("complexType") {
+            ElementType::Complex(Self::complex_type(complex)?)
+        } else if let Some(simple) = element.child("simpleType") {
+            ElementType::Simple(Self::simple_type(simple)?)
+        } else {
+            ElementType::Any
+        };
+
+        let uniques = element
+            .children_named("unique")
+            .filter_map(|unique| {
+                let selector = unique.child("selector")?.attr("xpath")?;
+                let field = unique.child("field")?.attr("xpath")?;
+                Some(UniqueConstraint {
+                    name: unique.attr_string("name").unwrap_or_default(),
+                    selector: selector
+                        .trim_start_matches("./")
+                        .split('/')
+                        .map(|s| local(s).to_string())
+                        .collect(),
+                    field: field.trim_start_matches('@').to_string(),
+                })
+            })
+            .collect();
+
+        Ok(ElementDecl {
+            element_type,
+            uniques,
+        })
+    }
+
+    fn complex_type(element: &XmlElement) -> Result<ComplexType, LandXMLError> {
+        let mut complex = ComplexType {
+            mixed: element.attr("mixed") == Some("true"),
+            ..Default::default()
+        };
+        for child in &element.children {
+            match child.name.as_str() {
+                "sequence" | "choice" => complex.particle = Some(Self::particle(child)?),
+                "attribute" => complex.attributes.extend(Self::attribute_decl(child)?),
+                "simpleContent" => {
+                    let extension = child
+                        .child("extension")
+                        .or_else(|| child.child("restriction"))
+                        .ok_or_else(|| LandXMLError::InvalidFormat {
+                            message: "xs:simpleContent without extension".to_string(),
+                        })?;
+                    let base = extension.attr("base").unwrap_or("xs:string");
+                    match type_reference(base) {
+                        SimpleType::Named(name) => complex.base = Some(name),
+                        builtin => complex.simple_content = Some(builtin),
+                    }
+                    for attribute in extension.children_named("attribute") {
+                        complex.attributes.extend(Self::attribute_decl(attribute)?);
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(complex)
+    }
+
+    fn particle(element: &XmlElement) -> Result<Particle, LandXMLError> {
+        let (min, max) = parse_occurs(element);
+        let term = match element.name.as_str() {
+            "element" => {
+                let name = element
+                    .attr("ref")
+                    .or_else(|| element.attr("name"))
+                    .ok_or_else(|| LandXMLError::InvalidFormat {
+                        message: "xs:element without name or ref".to_string(),
+                    })?;
+                Term::Element(local(name).to_string())
+            }
+            "sequence" | "choice" => {
+                let items = element
+                    .children
+                    .iter()
+                    .filter(|c| matches!(c.name.as_str(), "element" | "sequence" | "choice"))
+                    .map(Self::particle)
+                    .collect::<Result<Vec<_>, _>>()?;
+                if element.name == "sequence" {
+                    Term::Sequence(items)
+                } else {
+                    Term::Choice(items)
+                }
+            }
+            other => {
+                return Err(LandXMLError::InvalidFormat {
+                    message: format!("Unsupported particle xs:{}", other),
+                })
+            }
+        };
+        Ok(Particle { term, min, max })
+    }
+
+    fn attribute_decl(element: &XmlElement) -> Result<Option<AttributeDecl>, LandXMLError> {
+        let Some(name) = element.attr_string("name") else {
+            return Ok(None);
+        };
+        if element.attr("use") == Some("prohibited") {
+            return Ok(None);
+        }
+        let simple_type = match element.attr("type") {
+            Some(type_name) => type_reference(type_name),
+            None => match element.child("simpleType") {
+                Some(simple) => Self::simple_type(simple)?,
+                None => SimpleType::Builtin(Builtin::String),
+            },
+        };
+        Ok(Some(AttributeDecl {
+            name,
+            simple_type,
+            required: element.attr("use") == Some("required"),
+        }))
+    }
+
+    fn simple_type(element: &XmlElement) -> Result<SimpleType, LandXMLError> {
+        if let Some(restriction) = element.child("restriction") {
+            let base = match restriction.attr("base") {
+                Some(base) => type_reference(base),
+                None => match restriction.child("simpleType") {
+                    Some(simple) => Self::simple_type(simple)?,
+                    None => SimpleType::Builtin(Builtin::String),
+                },
+            };
+            let mut facets = Facets::default();
+            for facet in &restriction.children {
+                let value = facet.attr("value").unwrap_or_default();
+                let count = value.trim().parse().ok();
+                match facet.name.as_str() {
+                    "enumeration" => facets.enumeration.push(value.to_string()),
+                    "length" => facets.length = count,
+                    "minLength" => facets.min_length = count,
+                    "maxLength" => facets.max_length = count,
+                    _ => {}
+                }
+            }
+            return Ok(SimpleType::Restriction {
+                base: Box::new(base),
+                facets,
+            });
+        }
+        if let Some(list) = element.child("list") {
+            let item = match list.attr("itemType") {
+                Some(item) => type_reference(item),
+                None => match list.child("simpleType") {
+                    Some(simple) => Self::simple_type(simple)?,
+                    None => SimpleType::Builtin(Builtin::String),
+                },
+            };
+            return Ok(SimpleType::List(Box::new(item)));
+        }
+        Ok(SimpleType::Builtin(Builtin::String))
+    }
+
+    // ========================================================================
+    // 型の解決
+    // ========================================================================
+
+    /// 単純型がリスト型か（制約の長さを要素数として扱うかの判定）
+    fn is_list(&self, simple: &SimpleType) -> bool {
+        self.is_list_depth(simple, 0)
+    }
+
+    fn is_list_depth(&self, simple: &SimpleType, depth: usize) -> bool {
+        if depth > 32 {
+            return false;
+        }
+        match simple {
+            SimpleType::Builtin(_) => false,
+            SimpleType::List(_) => true,
+            SimpleType::Restriction { base, .. } => self.is_list_depth(base, depth + 1),
+            SimpleType::Named(name) => self
+                .simple_types
+                .get(name)
+                .map(|t| self.is_list_depth(t, depth + 1))
+                .unwrap_or(false),
+        }
+    }
+
+    /// 値を単純型で検証
+    fn check_value(&self, simple: &SimpleType, value: &str) -> Result<(), String> {
+        self.check_value_depth(simple, value, 0)
+    }
+
+    fn check_value_depth(
+        &self,
+        simple: &SimpleType,
+        value: &str,
+        depth: usize,
+    ) -> Result<(), String> {
+        if depth > 32 {
+            return Ok(());
+        }
+        match simple {
+            SimpleType::Builtin(builtin) => builtin.check(value),
+            SimpleType::Named(name) => match self.simple_types.get(name) {
+                Some(t) => self.check_value_depth(t, value, depth + 1),
+                None => Ok(()),
+            },
+            SimpleType::List(item) => value.split_whitespace().try_for_each(|v| {
+                self.check_value_depth(item, v, depth + 1)
+                    .map_err(|reason| format!("item '{}': {}", v, reason))
+            }),
+            SimpleType::Restriction { base, facets } => {
+                self.check_value_depth(base, value, depth + 1)?;
+                if !facets.enumeration.is_empty() && !facets.enumeration.iter().any(|e| e == value)
+                {
+                    return Err(format!("expected one of {}", facets.enumeration.join(", ")));
+                }
+                let length = if self.is_list(base) {
+                    value.split_whitespace().count()
+                } else {
+                    value.chars().count()
+                };
+                let unit = if self.is_list(base) {
+                    "items"
+                } else {
+                    "characters"
+                };
+                if let Some(expected) = facets.length.filter(|&n| n != length) {
+                    return Err(format!("expected {} {}, found {}", expected, unit, length));
+                }
+                if let Some(min) = facets.min_length.filter(|&n| length < n) {
+                    return Err(format!(
+                        "expected at least {} {}, found {}",
+                        min, unit, length
+                    ));
+                }
+                if let Some(max) = facets.max_length.filter(|&n| length > n) {
+                    return Err(format!(
+                        "expected at most {} {}, found {}",
+                        max, unit, length
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 要素型を解決（名前付き型は複合型・単純型の順に探索）
+    fn resolve<'a>(&'a self, element_type: &'a ElementType) -> ResolvedType<'a> {
+        match element_type {
+            ElementType::Complex(complex) => ResolvedType::Complex(complex),
+            ElementType::Simple(simple) => ResolvedType::Simple(simple.clone()),
+            ElementType::Named(name) => {
+                if let Some(complex) = self.complex_types.get(name) {
+                    ResolvedType::Complex(complex)
+                } else if self.simple_types.contains_key(name) {
+                    ResolvedType::Simple(SimpleType::Named(name.clone()))
+                } else {
+                    ResolvedType::Any
+                }
+            }
+            ElementType::Any => ResolvedType::Any,
+        }
+    }
+
+    /// 複合型の属性宣言とsimpleContentを拡張元まで辿って取得
+    fn flatten<'a>(
+        &'a self,
+        complex: &'a ComplexType,
+    ) -> (Vec<&'a AttributeDecl>, Option<SimpleType>) {
+        let mut attributes: Vec<&AttributeDecl> = complex.attributes.iter().collect();
+        let mut simple = complex.simple_content.clone();
+        let mut base = complex.base.as_deref();
+        let mut depth = 0;
+        while let Some(name) = base {
+            depth += 1;
+            if depth > 32 {
+                break;
+            }
+            if let Some(parent) = self.complex_types.get(name) {
+                attributes.extend(parent.attributes.iter());
+                if simple.is_none() {
+                    simple = parent.simple_content.clone();
+                }
+                base = parent.base.as_deref();
+            } else {
+                if simple.is_none() {
+                    simple = Some(SimpleType::Named(name.to_string()));
+                }
+                break;
+            }
+        }
+        (attributes, simple)
+    }
+}
+
+enum ResolvedType<'a> {
+    Complex(&'a ComplexType),
+    Simple(SimpleType),
+    Any,
+}
+
+// ============================================================================
+// 内容モデルの照合
+// ============================================================================
+
+/// 子要素名の列を内容モデルと照合する
+struct ContentMatcher<'a> {
+    names: &'a [&'a str],
+    /// 照合できた最も先の位置（エラー位置の特定に使用）
+    furthest: usize,
+}
+
+impl<'a> ContentMatcher<'a> {
+    fn new(names: &'a [&'a str]) -> Self {
+        Self { names, furthest: 0 }
+    }
+
+    /// 子要素列全体が内容モデルに一致するか
+    fn matches(&mut self, particle: &Particle) -> bool {
+        let start = BTreeSet::from([0]);
+        self.particle(particle, &start).contains(&self.names.len())
+    }
+
+    /// 出現回数を考慮して、開始位置の集合から到達可能な終了位置の集合を求める
+    fn particle(&mut self, particle: &Particle, starts: &BTreeSet<usize>) -> BTreeSet<usize> {
+        let mut result = BTreeSet::new();
+        if particle.min == 0 {
+            result.extend(starts.iter().copied());
+        }
+        let mut current = starts.clone();
+        let mut count = 0;
+        while particle.max.is_none_or(|max| count < max) {
+            let next = self.term(&particle.term, &current);
+            count += 1;
+            if next.is_empty() {
+                break;
+            }
+            if count >= particle.min {
+                // 新たに到達した位置がなければ以降も変化しない
+                if next.is_subset(&result) {
+                    break;
+                }
+                result.extend(next.iter().copied());
+            }
+            current = next;
+        }
+        result
+    }
+
+    fn term(&mut self, term: &Term, starts: &BTreeSet<usize>) -> BTreeSet<usize> {
+        match term {
+            Term::Element(name) => {
+                let ends: BTreeSet<usize> = starts
+                    .iter()
+                    .filter(|&&s| self.names.get(s) == Some(&name.as_str()))
+                    .map(|&s| s + 1)
+                    .collect();
+                if let Some(&last) = ends.iter().next_back() {
+                    self.furthest = self.furthest.max(last);
+                }
+                ends
+            }
+            Term::Sequence(items) => {
+                let mut current = starts.clone();
+                for item in items {
+                    current = self.particle(item, &current);
+                    if current.is_empty() {
+                        break;
+                    }
+                }
+                current
+            }
+            Term::Choice(items) => {
+                let mut result = BTreeSet::new();
+                for item in items {
+                    result.extend(self.particle(item, starts));
+                }
+                result
+            }
+        }
+    }
+}
+
+impl Particle {
+    /// 内容モデルの表記（例: "(Start, End, Feature*)"）
+    fn describe(&self) -> String {
+        let body = match &self.term {
+            Term::Element(name) => name.clone(),
+            Term::Sequence(items) | Term::Choice(items) => {
+                let separator = if matches!(self.term, Term::Sequence(_)) {
+                    ", "
+                } else {
+                    " | "
+                };
+                let inner: Vec<String> = items.iter().map(Particle::describe).collect();
+                format!("({})", inner.join(separator))
+            }
+        };
+        let suffix = match (self.min, self.max) {
+            (1, Some(1)) => String::new(),
+            (0, Some(1)) => "?".to_string(),
+            (0, None) => "*".to_string(),
+            (1, None) => "+".to_string(),
+            (min, None) => format!("{{{},}}", min),
+            (min, Some(max)) => format!("{{{},{}}}", min, max),
+        };
+        format!("{}{}", body, suffix)
+    }
+}
+
+// ============================================================================
+// 検証
+// ============================================================================
+
+/// XSDスキーマによる適合性検証
+#[derive(Debug, Clone)]
+pub struct XsdValidator {
+    schema: Schema,
+}
+
+impl XsdValidator {
+    /// XSD文字列からバリデーターを作成
+    pub fn from_xsd_str(xsd: &str) -> Result<Self, LandXMLError> {
+        let root = XmlElement::parse_str(xsd)?;
+        Ok(Self {
+            schema: Schema::from_xml(&root)?,
+        })
+    }
+
+    /// XSDファイルからバリデーターを作成
+    pub fn from_xsd_file<P: AsRef<Path>>(path: P) -> Result<Self, LandXMLError> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_xsd_str(&content)
+    }
+
+    /// 同梱のJ-LandXML Ver.1.7スキーマでバリデーターを作成
+    pub fn j_landxml_v17() -> Self {
+        Self::from_xsd_str(J_LANDXML_V17_XSD).expect("bundled J-LandXML schema must be valid")
+    }
+
+    /// XML文字列を検証して違反を列挙
+    pub fn validate_str(&self, content: &str) -> Result<Vec<SchemaViolation>, LandXMLError> {
+        let root = XmlElement::parse_str(content)?;
+        let mut context = ValidationContext {
+            schema: &self.schema,
+            content,
+            violations: Vec::new(),
+        };
+        match self.schema.elements.get(&root.name) {
+            Some(decl) => context.element(&root, decl, ""),
+            None => {
+                let kind = SchemaViolationKind::UnknownElement {
+                    element: root.name.clone(),
+                };
+                context.report(&root, &format!("/{}", root.name), kind);
+            }
+        }
+        Ok(context.violations)
+    }
+
+    /// XMLファイルを検証して違反を列挙
+    pub fn validate_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<SchemaViolation>, LandXMLError> {
+        let (content, _) = crate::xml::read_document(path)?;
+        self.validate_str(&content)
+    }
+}
+
+impl Default for XsdValidator {
+    fn default() -> Self {
+        Self::j_landxml_v17()
+    }
+}
+
+struct ValidationContext<'a> {
+    schema: &'a Schema,
+    content: &'a str,
+    violations: Vec<SchemaViolation>,
+}
+
+/// 検証対象外の属性（名前空間宣言・xsi属性）
+fn is_reserved_attribute(key: &str) -> bool {
+    key == "xmlns"
+        || key.starts_with("xmlns:")
+        || key.starts_with("xsi:")
+        || key.starts_with("xml:")
+}
+
+impl<'a> ValidationContext<'a> {
+    fn report(&mut self, element: &XmlElement, path: &str, kind: SchemaViolationKind) {
+        let (line, column) = XmlElement::line_column(self.content, element.offset);
+        self.violations.push(SchemaViolation {
+            line,
+            column,
+            path: path.to_string(),
+            kind,
+        });
+    }
+
+    fn element(&mut self, element: &XmlElement, decl: &'a ElementDecl, parent_path: &str) {
+        let path = format!("{}/{}", parent_path, element.name);
+        let nil = element.attr("xsi:nil") == Some("true");
+
+        match self.schema.resolve(&decl.element_type) {
+            ResolvedType::Any => {}
+            ResolvedType::Simple(simple) => {
+                for attribute in &element.attributes {
+                    if !is_reserved_attribute(&attribute.0) {
+                        let kind = SchemaViolationKind::UnknownAttribute {
+                            attribute: attribute.0.clone(),
+                        };
+                        self.report(element, &path, kind);
+                    }
+                }
+                self.reject_children(element, &path, "(text only)");
+                if !nil {
+                    self.text(element, &path, &simple);
+                }
+            }
+            ResolvedType::Complex(complex) => {
+                self.complex(element, complex, &path, nil);
+            }
+        }
+
+        for unique in &decl.uniques {
+            self.unique(element, unique, &path);
+        }
+    }
+
+    fn complex(&mut self, element: &XmlElement, complex: &'a ComplexType, path: &str, nil: bool) {
+        let (attributes, simple) = self.schema.flatten(complex);
+
+        for (key, value) in &element.attributes {
+            if is_reserved_attribute(key) {
+                continue;
+            }
+            match attributes.iter().find(|a| &a.name == key) {
+                Some(decl) => {
+                    if let Err(reason) = self.schema.check_value(&decl.simple_type, value) {
+                        let kind = SchemaViolationKind::InvalidValue {
+                            target: format!("@{}", key),
+                            value: value.clone(),
+                            reason,
+                        };
+                        self.report(element, path, kind);
+                    }
+                }
+                None => {
+                    let kind = SchemaViolationKind::UnknownAttribute {
+                        attribute: key.clone(),
+                    };
+                    self.report(element, path, kind);
+                }
+            }
+        }
+        for decl in attributes.iter().filter(|a| a.required) {
+            if element.attr(&decl.name).is_none() {
+                let kind = SchemaViolationKind::MissingAttribute {
+                    attribute: decl.name.clone(),
+                };
+                self.report(element, path, kind);
+            }
+        }
+        if nil {
+            return;
+        }
+
+        if let Some(simple) = simple {
+            self.reject_children(element, path, "(text only)");
+            self.text(element, path, &simple);
+            return;
+        }
+
+        if !complex.mixed && !element.text.is_empty() {
+            self.report(element, path, SchemaViolationKind::UnexpectedText);
+        }
+
+        match &complex.particle {
+            Some(particle) => self.content(element, particle, path),
+            None => self.reject_children(element, path, "(empty)"),
+        }
+    }
+
+    fn content(&mut self, element: &XmlElement, particle: &Particle, path: &str) {
+        let names: Vec<&str> = element.children.iter().map(|c| c.name.as_str()).collect();
+        let mut matcher = ContentMatcher::new(&names);
+        if !matcher.matches(particle) {
+            match element.children.get(matcher.furthest) {
+                // 未定義要素は下でUnknownElementとして報告する
+                Some(child) if !self.schema.elements.contains_key(&child.name) => {}
+                Some(child) => {
+                    let kind = SchemaViolationKind::UnexpectedElement {
+                        element: child.name.clone(),
+                        expected: particle.describe(),
+                    };
+                    self.report(child, &format!("{}/{}", path, child.name), kind);
+                }
+                None => {
+                    let kind = SchemaViolationKind::IncompleteContent {
+                        expected: particle.describe(),
+                    };
+                    self.report(element, path, kind);
+                }
+            }
+        }
+
+        for child in &element.children {
+            match self.schema.elements.get(&child.name) {
+                Some(decl) => self.element(child, decl, path),
+                None => {
+                    let kind = SchemaViolationKind::UnknownElement {
+                        element: child.name.clone(),
+                    };
+                    self.report(child, &format!("{}/{}", path, child.name), kind);
+                }
+            }
+        }
+    }
+
+    fn reject_children(&mut self, element: &XmlElement, path: &str, expected: &str) {
+        for child in &element.children {
+            let kind = SchemaViolationKind::UnexpectedElement {
+                element: child.name.clone(),
+                expected: expected.to_string(),
+            };
+            self.report(child, &format!("{}/{}", path, child.name), kind);
+        }
+    }
+
+    fn text(&mut self, element: &XmlElement, path: &str, simple: &SimpleType) {
+        if let Err(reason) = self.schema.check_value(simple, &element.text) {
+            let kind = SchemaViolationKind::InvalidValue {
+                target: "text()".to_string(),
+                value: element.text.clone(),
+                reason,
+            };
+            self.report(element, path, kind);
+        }
+    }
+
+    fn unique(&mut self, element: &XmlElement, unique: &UniqueConstraint, path: &str) {
+        let mut selected: Vec<&XmlElement> = vec![element];
+        for step in &unique.selector {
+            selected = selected
+                .iter()
+                .flat_map(|e| e.children_named(step))
+                .collect();
+        }
+        let mut seen = HashSet::new();
+        for target in selected {
+            if let Some(value) = target.attr(&unique.field) {
+                if !seen.insert(value.trim().to_string()) {
+                    let kind = SchemaViolationKind::DuplicateValue {
+                        constraint: unique.name.clone(),
+                        value: value.to_string(),
+                    };
+                    self.report(target, &format!("{}/{}", path, target.name), kind);
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// 値の字句検査
+// ============================================================================
+
+fn is_double(v: &str) -> bool {
+    matches!(v, "INF" | "+INF" | "-INF" | "NaN")
+        || (!v.is_empty()
+            && v.bytes()
+                .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'))
+            && v.parse::<f64>().is_ok())
+}
+
+fn is_integer(v: &str) -> bool {
+    let digits = v.strip_prefix(['+', '-']).unwrap_or(v);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_digits(v: &str, len: usize) -> bool {
+    v.len() == len && v.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// タイムゾーン指定（Z, +09:00等）を除去
+fn strip_timezone(v: &str) -> Option<&str> {
+    if let Some(body) = v.strip_suffix('Z') {
+        return Some(body);
+    }
+    if v.len() > 6 {
+        let (body, tz) = v.split_at(v.len() - 6);
+        let tz = tz.as_bytes();
+        if (tz[0] == b'+' || tz[0] == b'-')
+            && tz[3] == b':'
+            && tz[1..3].iter().chain(&tz[4..]).all(u8::is_ascii_digit)
+        {
+            return Some(body);
+        }
+    }
+    Some(v)
+}
+
+fn is_date_body(v: &str) -> bool {
+    let parts: Vec<&str> = v.split('-').collect();
+    if parts.len() != 3 || !is_digits(parts[0], 4) || !is_digits(parts[1], 2) {
+        return false;
+    }
+    if !is_digits(parts[2], 2) {
+        return false;
+    }
+    let month: u32 = parts[1].parse().unwrap_or(0);
+    let day: u32 = parts[2].parse().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+fn is_date(v: &str) -> bool {
+    strip_timezone(v).map(is_date_body).unwrap_or(false)
+}
+
+fn is_time(v: &str) -> bool {
+    let Some(body) = strip_timezone(v) else {
+        return false;
+    };
+    let parts: Vec<&str> = body.split(':').collect();
+    if parts.len() != 3 || !is_digits(parts[0], 2) || !is_digits(parts[1], 2) {
+        return false;
+    }
+    let (seconds, fraction) = match parts[2].split_once('.') {
+        Some((s, f)) => (s, Some(f)),
+        None => (parts[2], None),
+    };
+    if !is_digits(seconds, 2) || fraction.is_some_and(|f| !is_digits(f, f.len()) || f.is_empty()) {
+        return false;
+    }
+    let hour: u32 = parts[0].parse().unwrap_or(99);
+    let minute: u32 = parts[1].parse().unwrap_or(99);
+    let second: u32 = seconds.parse().unwrap_or(99);
+    hour <= 24 && minute < 60 && second < 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" date="2024-01-01" time="12:00:00" version="1.2">
+  <Alignments>
+    <Alignment name="A1" length="100.0" staStart="0.0">
+      <CoordGeom>
+        <Line>
+          <Start>0.0 0.0</Start>
+          <End>100.0 0.0</End>
+        </Line>
+      </CoordGeom>
+    </Alignment>
+  </Alignments>
+</LandXML>
+"#;
+
+    fn validate(xml: &str) -> Vec<SchemaViolation> {
+        XsdValidator::j_landxml_v17().validate_str(xml).unwrap()
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let violations = validate(MINIMAL);
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn test_sample_violations() {
+        let violations = XsdValidator::default()
+            .validate_file("tests/data/j_landxml_sample.xml")
+            .unwrap();
+        // directionUnitの空白2つ、改行で分断された数値、Featureの位置、P要素の不足
+        assert_eq!((violations[0].line, violations[0].column), (19, 5));
+        assert!(matches!(
+            &violations[0].kind,
+            SchemaViolationKind::InvalidValue { target, .. } if target == "@directionUnit"
+        ));
+        assert!(violations.iter().any(|v| matches!(
+            &v.kind,
+            SchemaViolationKind::InvalidValue { reason, .. } if reason.starts_with("item '-'")
+        )));
+        assert_eq!(
+            violations
+                .iter()
+                .filter(|v| v.path.ends_with("CrossSectSurf/Feature"))
+                .count(),
+            2
+        );
+        let last = violations.last().unwrap();
+        assert_eq!((last.line, last.column), (271, 9));
+        assert!(matches!(
+            last.kind,
+            SchemaViolationKind::IncompleteContent { .. }
+        ));
+    }
+
+    #[test]
+    fn test_missing_required_attribute() {
+        let xml = MINIMAL.replace(r#" name="A1""#, "");
+        let violations = validate(&xml);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].kind,
+            SchemaViolationKind::MissingAttribute {
+                attribute: "name".to_string()
+            }
+        );
+        assert_eq!((violations[0].line, violations[0].column), (4, 5));
+        assert_eq!(violations[0].path, "/LandXML/Alignments/Alignment");
+    }
+
+    #[test]
+    fn test_enumeration_and_value_types() {
+        let xml = MINIMAL
+            .replace(
+                "<Line>",
+                r#"<Spiral length="10" radiusStart="INF" radiusEnd="200" rot="cw" spiType="euler">"#,
+            )
+            .replace("</Line>", "<PI>50.0 0.0</PI></Spiral>")
+            .replace(r#"length="100.0""#, r#"length="abc""#);
+        let violations = validate(&xml);
+        let kinds: Vec<_> = violations.iter().map(|v| &v.kind).collect();
+        assert_eq!(violations.len(), 2, "{:?}", kinds);
+        assert!(matches!(
+            kinds[0],
+            SchemaViolationKind::InvalidValue { target, .. } if target == "@length"
+        ));
+        assert!(matches!(
+            kinds[1],
+            SchemaViolationKind::InvalidValue { target, value, .. }
+                if target == "@spiType" && value == "euler"
+        ));
+        assert_eq!(violations[1].line, 6);
+    }
+
+    #[test]
+    fn test_element_order_and_cardinality() {
+        let swapped = MINIMAL.replace(
+            "<Start>0.0 0.0</Start>\n          <End>100.0 0.0</End>",
+            "<End>100.0 0.0</End>\n          <Start>0.0 0.0</Start>",
+        );
+        let violations = validate(&swapped);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            &violations[0].kind,
+            SchemaViolationKind::UnexpectedElement { element, .. } if element == "End"
+        ));
+        assert_eq!((violations[0].line, violations[0].column), (7, 11));
+
+        let missing = MINIMAL.replace("\n          <End>100.0 0.0</End>", "");
+        let violations = validate(&missing);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            violations[0].kind,
+            SchemaViolationKind::IncompleteContent { .. }
+        ));
+        assert_eq!(violations[0].line, 6);
+
+        let point = MINIMAL.replace("<End>100.0 0.0</End>", "<End>100.0 0.0 1.0 2.0</End>");
+        let violations = validate(&point);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            &violations[0].kind,
+            SchemaViolationKind::InvalidValue { target, .. } if target == "text()"
+        ));
+    }
+
+    #[test]
+    fn test_unique_constraint() {
+        let xml = MINIMAL.replace(
+            "  </Alignments>",
+            r#"    <Alignment name="A1" length="1" staStart="0"><CoordGeom><Line><Start>0 0</Start><End>1 0</End></Line></CoordGeom></Alignment>
+  </Alignments>"#,
+        );
+        let violations = validate(&xml);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            &violations[0].kind,
+            SchemaViolationKind::DuplicateValue { value, .. } if value == "A1"
+        ));
+    }
+
+    #[test]
+    fn test_lexical_checks() {
+        assert!(is_double("-1.5E3"));
+        assert!(is_double("INF"));
+        assert!(!is_double("1,5"));
+        assert!(is_date("2024-02-29+09:00"));
+        assert!(!is_date("2024/02/29"));
+        assert!(is_time("09:30:00.5Z"));
+        assert!(!is_time("9:30"));
+    }
+}
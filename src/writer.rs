@@ -0,0 +1,571 @@
+//! LandXML/J-LandXML出力
+//!
+//! JLandXmlDocumentをLandXML 1.2（J-LandXML）形式のXMLとして出力します：
+//! - 座標系（J-LandXML拡張属性・differTPのFeature）
+//! - Project（applicationCriterion）・Application・Units
+//! - CgPoints・Alignments（CoordGeom/Profile/CrossSects）・Roadways・Surfaces
+//! - 数値の小数点以下桁数の指定
+//! - Unitsで宣言された単位への換算（モデルはメートル・ラジアンで保持）
+
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use quick_xml::events::{BytesDecl, Event};
+
+use crate::coordinate_system::JLandXmlDocument;
+use crate::error::LandXMLError;
+use crate::models::Point2D;
+use crate::xml::XmlElement;
+
+/// LandXML 1.2の名前空間
+pub const LANDXML_NAMESPACE: &str = "http://www.landxml.org/schema/LandXML-1.2";
+
+/// LandXML/J-LandXMLライター
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LandXmlWriter {
+    /// 数値の小数点以下桁数
+    precision: usize,
+    /// インデント幅（0の場合は改行せずに出力）
+    indent: usize,
+}
+
+impl Default for LandXmlWriter {
+    fn default() -> Self {
+        Self {
+            precision: 8,
+            indent: 2,
+        }
+    }
+}
+
+impl LandXmlWriter {
+    /// 小数点以下8桁・インデント2で作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 数値の小数点以下桁数を設定
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// インデント幅を設定
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// 文書をXML文字列として出力
+    pub fn write_to_string(&self, doc: &JLandXmlDocument) -> Result<String, LandXMLError> {
+        let mut buffer = Vec::new();
+        self.write(doc, &mut buffer)?;
+        String::from_utf8(buffer)
+            .map_err(|e| LandXMLError::ParseError(format!("UTF-8 conversion error: {}", e)))
+    }
+
+    /// 文書をファイルに出力
+    pub fn write_to_file<P: AsRef<Path>>(
+        &self,
+        doc: &JLandXmlDocument,
+        path: P,
+    ) -> Result<(), LandXMLError> {
+        let file = std::fs::File::create(path)?;
+        let mut output = std::io::BufWriter::new(file);
+        self.write(doc, &mut output)?;
+        output.flush()?;
+        Ok(())
+    }
+
+    /// 文書を出力先に書き出し
+    pub fn write<W: Write>(&self, doc: &JLandXmlDocument, output: W) -> Result<(), LandXMLError> {
+        let mut writer = if self.indent > 0 {
+            quick_xml::Writer::new_with_indent(output, b' ', self.indent)
+        } else {
+            quick_xml::Writer::new(output)
+        };
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        self.document_element(doc).write_to(&mut writer)?;
+        if self.indent > 0 {
+            writer.get_mut().write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// LandXMLルート要素を生成
+    fn document_element(&self, doc: &JLandXmlDocument) -> XmlElement {
+        // 基準単位（メートル・ラジアン）の値をUnitsで宣言された単位に戻して出力
+        let denormalized;
+        let base = match &doc.base.units {
+            Some(units) if !units.is_canonical() => {
+                let mut base = doc.base.clone();
+                units.denormalize(&mut base);
+                denormalized = base;
+                &denormalized
+            }
+            _ => &doc.base,
+        };
+        let (current_date, current_time) = current_date_time();
+        let version = if base.version.is_empty() {
+            "1.2"
+        } else {
+            base.version.as_str()
+        };
+
+        let mut root = XmlElement::new("LandXML")
+            .with_attr("xmlns", LANDXML_NAMESPACE)
+            .with_attr("date", base.date.clone().unwrap_or(current_date))
+            .with_attr("time", base.time.clone().unwrap_or(current_time))
+            .with_attr("version", version);
+
+        // applicationCriterionはProjectのFeatureとして出力
+        if base.project.is_some() || doc.application_criterion.is_some() {
+            let mut project = base.project.clone().unwrap_or_default();
+            if doc.application_criterion.is_some() {
+                project.application_criterion = doc.application_criterion.clone();
+            }
+            root = root.with_child(project.to_xml());
+        }
+        if let Some(application) = &base.application {
+            root = root.with_child(application.to_xml());
+        }
+
+        // J-LandXMLの場合は拡張属性、それ以外は標準属性で座標系を出力
+        let coordinate_system = match (&doc.coordinate_system, &base.coordinate_system) {
+            (Some(cs), Some(_)) if doc.is_j_landxml() => Some(cs.to_xml()),
+            (_, Some(base_cs)) => Some(base_cs.to_xml()),
+            (Some(cs), None) => Some(cs.to_xml()),
+            (None, None) => None,
+        };
+        root = root.with_children(coordinate_system);
+
+        if let Some(units) = &base.units {
+            root = root.with_child(units.to_xml());
+        }
+
+        root = root.with_children(base.cg_points.iter().map(|c| c.to_xml(self)));
+        if !base.alignments.is_empty() || !base.alignments_extensions.is_empty() {
+            root = root.with_child(
+                XmlElement::new("Alignments")
+                    .with_children(base.alignments.iter().map(|a| a.to_xml(self)))
+                    .with_extensions(&base.alignments_extensions),
+            );
+        }
+        root.with_children(base.roadways.iter().map(|r| r.to_xml(self)))
+            .with_children(base.surfaces.iter().map(|s| s.to_xml(self)))
+            .with_extensions(&base.extensions)
+    }
+
+    // ========================================================================
+    // 数値の書式
+    // ========================================================================
+
+    /// 数値を指定桁数で書式化（負のゼロは"0"として出力）
+    pub(crate) fn number(&self, value: f64) -> String {
+        let formatted = format!("{:.*}", self.precision, value);
+        match formatted.strip_prefix('-') {
+            Some(unsigned) if unsigned.bytes().all(|b| b == b'0' || b == b'.') => {
+                unsigned.to_string()
+            }
+            _ => formatted,
+        }
+    }
+
+    /// 任意の数値を書式化
+    pub(crate) fn opt_number(&self, value: Option<f64>) -> Option<String> {
+        value.map(|v| self.number(v))
+    }
+
+    /// 数値列を空白区切りで書式化
+    pub(crate) fn numbers(&self, values: &[f64]) -> String {
+        values
+            .iter()
+            .map(|v| self.number(*v))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 点列をPntList2D要素として生成
+    pub(crate) fn pnt_lists_2d(&self, point_lists: &[Vec<Point2D>]) -> Vec<XmlElement> {
+        point_lists
+            .iter()
+            .map(|points| {
+                let values: Vec<f64> = points.iter().flat_map(|p| [p.x, p.y]).collect();
+                XmlElement::new("PntList2D").with_text(self.numbers(&values))
+            })
+            .collect()
+    }
+}
+
+impl JLandXmlDocument {
+    /// 既定の設定でXML文字列として出力
+    pub fn to_xml_string(&self) -> Result<String, LandXMLError> {
+        LandXmlWriter::new().write_to_string(self)
+    }
+
+    /// 既定の設定でファイルに出力
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LandXMLError> {
+        LandXmlWriter::new().write_to_file(self, path)
+    }
+}
+
+/// 現在のUTC日付・時刻（"YYYY-MM-DD", "hh:mm:ss"）
+fn current_date_time() -> (String, String) {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let days = (seconds / 86_400) as i64;
+    let secs_of_day = seconds % 86_400;
+
+    // 1970-01-01からの日数をグレゴリオ暦に変換
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (
+        format!("{:04}-{:02}-{:02}", year, month, day),
+        format!(
+            "{:02}:{:02}:{:02}",
+            secs_of_day / 3_600,
+            secs_of_day % 3_600 / 60,
+            secs_of_day % 60
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coordinate_system::{JLandXmlParser, VerticalDatum};
+    use crate::units::{AngularUnit, Units};
+
+    fn sample_path() -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/j_landxml_sample.xml")
+    }
+
+    #[test]
+    fn test_number_format() {
+        let writer = LandXmlWriter::new().with_precision(3);
+        assert_eq!(writer.number(1.23456), "1.235");
+        assert_eq!(writer.number(-0.0001), "0.000");
+        assert_eq!(writer.numbers(&[-5851.2447, 90.9]), "-5851.245 90.900");
+        assert_eq!(LandXmlWriter::new().with_precision(0).number(-0.2), "0");
+    }
+
+    #[test]
+    fn test_write_and_reparse_sample() {
+        let doc = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let xml = LandXmlWriter::new().write_to_string(&doc).unwrap();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains(r#"horizontalCoordinateSystemName="8(X,Y)""#));
+        assert!(xml.contains(r#"<Property label="differTP" value="-0.8402"/>"#));
+        assert!(xml.contains(r#"radiusStart="INF""#));
+        assert!(xml.contains(r#"angularUnit="decimal dd.mm.ss""#));
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        let reparsed = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let cs = reparsed.coordinate_system.as_ref().unwrap();
+        assert_eq!(cs.vertical_datum, VerticalDatum::YP);
+        assert_eq!(cs.differ_tp, Some(-0.8402));
+        assert_eq!(
+            reparsed.get_plane_coordinate_zone(),
+            doc.get_plane_coordinate_zone()
+        );
+        assert_eq!(reparsed.base.date.as_deref(), Some("2023-03-01"));
+        assert_eq!(
+            reparsed.base.project.as_ref().unwrap().name.as_deref(),
+            Some("○○道路設計")
+        );
+        assert_eq!(
+            reparsed.base.roadways[0].roadways[0].alignment_refs,
+            ["○○路線"]
+        );
+
+        let original = &doc.base.alignments[0];
+        let alignment = &reparsed.base.alignments[0];
+        let elements = &alignment.coord_geom.as_ref().unwrap().elements;
+        assert_eq!(elements.len(), 18);
+        for (a, b) in original
+            .coord_geom
+            .as_ref()
+            .unwrap()
+            .elements
+            .iter()
+            .zip(elements)
+        {
+            assert!((a.start().x - b.start().x).abs() < 1e-8);
+            assert_eq!(a.length(), b.length());
+        }
+        assert_eq!(
+            alignment.profile.as_ref().unwrap().prof_aligns[0]
+                .elements
+                .len(),
+            4
+        );
+        assert_eq!(alignment.cross_sects.as_ref().unwrap().sections.len(), 2);
+        assert_eq!(reparsed.base.cg_points[0].points.len(), 3);
+        assert!(!reparsed.base.cg_points[0].points[0].has_elevation);
+        assert_eq!(
+            reparsed.base.surfaces[0].surfaces[0].points.len(),
+            doc.base.surfaces[0].surfaces[0].points.len()
+        );
+    }
+
+    #[test]
+    fn test_round_trip_keeps_unknown_content() {
+        let mut doc = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        doc.base.alignments[0].desc = Some("変更".to_string());
+        let xml = doc.to_xml_string().unwrap();
+
+        // モデル外の要素・属性が元の位置に残る
+        assert!(xml.contains(r#"xsi:schemaLocation="#));
+        assert!(xml.contains(r#"<Author createdBy="山田太郎" company="○○設計株式会社"/>"#));
+        assert!(xml.contains(r#"<CgPoint name="NO.-4" featureRef="1">"#));
+        assert!(xml.contains(r#"<Property label="designGmType" value="道路"/>"#));
+        assert!(xml.contains(r#"<DesignSpeed speed="40"/>"#));
+        assert!(xml.contains(r#"<Property label="A" value="125.00000000"/>"#));
+        let order = ["<StaEquation", "<CoordGeom>", "<AlignPIs>", "<Profile>"];
+        let positions: Vec<usize> = order.iter().map(|tag| xml.find(tag).unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        assert!(
+            xml.find(r#"<Feature name="Horizontal">"#).unwrap()
+                > xml.find("</CrossSects>").unwrap()
+        );
+
+        // 再解析・再出力しても内容が変わらない
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        let reparsed = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(reparsed.base.alignments[0].desc.as_deref(), Some("変更"));
+        assert_eq!(reparsed.to_xml_string().unwrap(), xml);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_repeated_elements() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Alignments name="本線">
+    <Alignment name="A" length="100.0" staStart="0.0">
+      <Profile name="計画">
+        <ProfAlign name="計画縦断"><PVI>0.0 10.0</PVI></ProfAlign>
+      </Profile>
+      <Profile name="現況">
+        <ProfSurf name="現況地盤"><PntList2D>0.0 9.0 100.0 9.5</PntList2D></ProfSurf>
+      </Profile>
+    </Alignment>
+    <Feature name="first"/>
+  </Alignments>
+  <Alignments desc="ランプ">
+    <Feature name="second"/>
+    <Alignment name="B" length="50.0" staStart="0.0"/>
+  </Alignments>
+</LandXML>"#;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        let doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let written = doc.to_xml_string().unwrap();
+
+        // 2つ目のProfileは未知要素として保持
+        assert!(written.contains(r#"<Profile name="計画">"#), "{}", written);
+        assert!(written.contains(r#"<ProfSurf name="現況地盤">"#));
+        // 複数のAlignments要素は属性・未知要素を統合して1つに出力
+        assert!(written.contains(r#"<Alignments name="本線" desc="ランプ">"#));
+        let order = [
+            r#"<Alignment name="A""#,
+            r#"<Feature name="first"/>"#,
+            r#"<Feature name="second"/>"#,
+            r#"<Alignment name="B""#,
+        ];
+        let positions: Vec<usize> = order.iter().map(|tag| written.find(tag).unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", written);
+    }
+
+    #[test]
+    fn test_units_normalized_and_restored() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Units>
+    <Imperial areaUnit="squareFoot" linearUnit="foot" volumeUnit="cubicYard" temperatureUnit="fahrenheit" pressureUnit="inHG" angularUnit="decimal dd.mm.ss" directionUnit="decimal dd.mm.ss"/>
+  </Units>
+  <Alignments>
+    <Alignment name="A" length="100.0" staStart="0.0">
+      <CoordGeom>
+        <Line dir="30.2045" length="100.0">
+          <Start>0.0 0.0</Start>
+          <End>86.3 50.6</End>
+        </Line>
+      </CoordGeom>
+    </Alignment>
+  </Alignments>
+</LandXML>"#;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        let doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        // モデル上はメートル・ラジアン
+        let alignment = &doc.base.alignments[0];
+        assert!((alignment.length.unwrap() - 30.48).abs() < 1e-9);
+        let crate::alignments::GeomElement::Line(line) =
+            &alignment.coord_geom.as_ref().unwrap().elements[0]
+        else {
+            panic!("expected line");
+        };
+        let expected = (30.0 + 20.0 / 60.0 + 45.0 / 3600.0f64).to_radians();
+        assert!((line.dir.unwrap() - expected).abs() < 1e-12);
+        assert!((line.end.x - 86.3 * 0.3048).abs() < 1e-9);
+
+        // 出力時は宣言された単位に戻る
+        let written = doc.to_xml_string().unwrap();
+        assert!(written.contains(r#"dir="30.20450000""#), "{}", written);
+        assert!(written.contains(r#"<End>86.30000000 50.60000000</End>"#));
+        assert!(written.contains(r#"temperatureUnit="fahrenheit""#));
+        assert!(written.contains(r#"volumeUnit="cubicYard""#));
+    }
+
+    #[test]
+    fn test_convert_units_to_metric() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Units>
+    <Imperial areaUnit="acre" linearUnit="USSurveyFoot" volumeUnit="cubicYard" angularUnit="decimal degrees" directionUnit="decimal degrees"/>
+  </Units>
+  <Surfaces>
+    <Surface name="EG">
+      <Definition surfType="TIN" area2DSurf="2.5" elevMax="3937.0">
+        <Pnts>
+          <P id="1">0.0 0.0 3937.0</P>
+          <P id="2">0.0 3937.0 0.0</P>
+          <P id="3">3937.0 0.0 0.0</P>
+        </Pnts>
+        <Faces><F>1 2 3</F></Faces>
+      </Definition>
+    </Surface>
+    <SurfVolumes>
+      <SurfVolume surf1="EG" surf2="FG" volCut="100.0" volFill="27.0"/>
+    </SurfVolumes>
+  </Surfaces>
+</LandXML>"#;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        let mut doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        let metric = Units {
+            angular_unit: AngularUnit::DecimalDegrees,
+            direction_unit: AngularUnit::DecimalDegrees,
+            ..Default::default()
+        };
+        doc.convert_units(&metric);
+        let written = doc.to_xml_string().unwrap();
+        assert!(written.contains("<Metric"), "{}", written);
+        assert!(written.contains(r#"linearUnit="meter""#));
+        assert!(written.contains("<P id=\"1\">0.00000000 0.00000000 1200.00000000</P>"));
+
+        let surface = &doc.base.surfaces[0].surfaces[0];
+        let attr = |key| surface.definition_extensions.attribute(key).unwrap();
+        assert!((attr("area2DSurf").parse::<f64>().unwrap() - 10_117.141_056).abs() < 1e-6);
+        assert!((attr("elevMax").parse::<f64>().unwrap() - 1200.0).abs() < 1e-9);
+        let volume = doc.base.surfaces[0]
+            .extensions
+            .elements_named("SurfVolumes")
+            .flat_map(|v| v.children.iter())
+            .flat_map(|v| v.attributes.iter())
+            .find(|(k, _)| k == "volFill")
+            .map(|(_, v)| v.parse::<f64>().unwrap())
+            .unwrap();
+        assert!((volume - 27.0 * 0.764_554_857_984).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_convert_units_covers_alignment_extensions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+  <Units>
+    <Imperial areaUnit="squareFoot" linearUnit="foot" volumeUnit="cubicYard" angularUnit="decimal degrees" directionUnit="decimal degrees"/>
+  </Units>
+  <CgPoints>
+    <CgPoint name="P1" featureRef="0">100.0 200.0</CgPoint>
+    <Feature name="0">
+      <Property label="sta" value="-90"/>
+      <Property label="tangentDirectionAngle" value="30.5"/>
+    </Feature>
+  </CgPoints>
+  <Alignments>
+    <Alignment name="A" staStart="0.0">
+      <StaEquation staBack="1000" staAhead="1010"/>
+      <CrossSects>
+        <CrossSect name="NO.1" sta="500.0" areaCut="1000" areaFill="250" volumeCut="10"/>
+      </CrossSects>
+    </Alignment>
+  </Alignments>
+</LandXML>"#;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(xml.as_bytes()).unwrap();
+        let mut doc = JLandXmlParser::from_file(file.path())
+            .unwrap()
+            .parse()
+            .unwrap();
+
+        doc.convert_units(&Units {
+            angular_unit: AngularUnit::DecimalDegrees,
+            direction_unit: AngularUnit::DecimalDegrees,
+            ..Default::default()
+        });
+        let written = doc.to_xml_string().unwrap();
+        assert!(
+            written.contains(r#"<StaEquation staBack="304.8" staAhead="307.848"/>"#),
+            "{}",
+            written
+        );
+        assert!(written.contains(r#"sta="152.40000000""#));
+        assert!(written.contains(r#"areaCut="92.90304" areaFill="23.22576""#));
+        assert!(written.contains(r#"volumeCut="7.64554857984""#));
+        assert!(written.contains(r#"<Property label="sta" value="-27.432"/>"#));
+        assert!(written.contains(r#"<Property label="tangentDirectionAngle" value="30.5"/>"#));
+    }
+
+    #[test]
+    fn test_write_precision() {
+        let doc = JLandXmlParser::from_file(sample_path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let xml = LandXmlWriter::new()
+            .with_precision(3)
+            .with_indent(0)
+            .write_to_string(&doc)
+            .unwrap();
+        assert!(xml.contains("<PVI>-90.000 90.906</PVI>"));
+        assert!(!xml.contains('\n'));
+    }
+}
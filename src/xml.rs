@@ -0,0 +1,731 @@
+//! Lightweight XML element tree
+//!
+//! quick-xmlのイベントから要素ツリーを構築します。
+//! LandXMLの各セクション（Alignments, CgPoints, Surfaces等）の解析と出力に使用します。
+
+use encoding_rs::{Encoding, SHIFT_JIS, UTF_8};
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::error::LandXMLError;
+use crate::models::{ExtensionElement, Extensions, Point2D, RawElement};
+
+/// XML要素
+///
+/// 要素名はローカル名で照合し、名前空間接頭辞は未知要素として再出力するために別に保持します。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct XmlElement {
+    /// 要素名（ローカル名）
+    pub name: String,
+    /// 名前空間接頭辞
+    pub prefix: Option<String>,
+    /// 祖先要素で宣言された、要素名・属性の接頭辞の名前空間（接頭辞, URI）
+    pub namespaces: Vec<(String, String)>,
+    /// 属性（出現順）
+    pub attributes: Vec<(String, String)>,
+    /// 子要素
+    pub children: Vec<XmlElement>,
+    /// テキスト内容（前後の空白は除去済み）
+    pub text: String,
+    /// 開始タグのバイト位置（解析時のみ設定、比較対象外）
+    pub offset: usize,
+}
+
+impl PartialEq for XmlElement {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.prefix == other.prefix
+            && self.attributes == other.attributes
+            && self.children == other.children
+            && self.text == other.text
+    }
+}
+
+impl XmlElement {
+    /// XML文字列を解析してルート要素を取得
+    pub fn parse_str(content: &str) -> Result<Self, LandXMLError> {
+        let mut reader = Reader::from_str(content);
+        reader.trim_text(true);
+
+        let mut stack: Vec<XmlElement> = Vec::new();
+        let mut root: Option<XmlElement> = None;
+        // 開いている要素ごとの名前空間宣言（接頭辞, URI）
+        let mut scopes: Vec<Vec<(String, String)>> = Vec::new();
+
+        loop {
+            let before = reader.buffer_position();
+            match reader.read_event() {
+                Ok(Event::Start(ref e)) => {
+                    let mut element = Self::from_start(e)?;
+                    element.offset = tag_offset(content, before);
+                    element.resolve_namespaces(&scopes);
+                    scopes.push(element.declared_namespaces().collect());
+                    stack.push(element);
+                }
+                Ok(Event::Empty(ref e)) => {
+                    let mut element = Self::from_start(e)?;
+                    element.offset = tag_offset(content, before);
+                    element.resolve_namespaces(&scopes);
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => root = Some(element),
+                    }
+                }
+                Ok(Event::Text(ref e)) => {
+                    let text = e.unescape().map_err(|e| {
+                        LandXMLError::ParseError(format!("Text unescape error: {}", e))
+                    })?;
+                    if let Some(current) = stack.last_mut() {
+                        if !current.text.is_empty() {
+                            current.text.push(' ');
+                        }
+                        current.text.push_str(text.trim());
+                    }
+                }
+                Ok(Event::CData(ref e)) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&String::from_utf8_lossy(e));
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    scopes.pop();
+                    let element = stack.pop().ok_or_else(|| {
+                        LandXMLError::ParseError("Unexpected end tag".to_string())
+                    })?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => root = Some(element),
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(LandXMLError::ParseError(format!(
+                        "XML parsing error at position {}: {}",
+                        reader.buffer_position(),
+                        e
+                    )))
+                }
+                _ => {}
+            }
+        }
+
+        root.ok_or_else(|| LandXMLError::InvalidFormat {
+            message: "No root element found".to_string(),
+        })
+    }
+
+    /// バイト位置を1始まりの行・列番号に変換
+    pub fn line_column(content: &str, offset: usize) -> (usize, usize) {
+        let prefix = &content[..offset.min(content.len())];
+        let line = prefix.matches('\n').count() + 1;
+        let line_start = prefix.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        (line, prefix[line_start..].chars().count() + 1)
+    }
+
+    fn from_start(e: &BytesStart<'_>) -> Result<Self, LandXMLError> {
+        let name = String::from_utf8_lossy(e.local_name().as_ref()).to_string();
+        let prefix = e
+            .name()
+            .prefix()
+            .map(|p| String::from_utf8_lossy(p.as_ref()).to_string());
+        let mut attributes = Vec::new();
+        for attr in e.attributes() {
+            let attr = attr
+                .map_err(|e| LandXMLError::ParseError(format!("Attribute parsing error: {}", e)))?;
+            let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+            let value = attr
+                .unescape_value()
+                .map_err(|e| LandXMLError::ParseError(format!("Attribute unescape error: {}", e)))?
+                .to_string();
+            attributes.push((key, value));
+        }
+        Ok(Self {
+            name,
+            prefix,
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    /// 要素自身で宣言した名前空間（接頭辞, URI）
+    fn declared_namespaces(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        self.attributes.iter().filter_map(|(key, value)| {
+            key.strip_prefix("xmlns:")
+                .map(|prefix| (prefix.to_string(), value.clone()))
+        })
+    }
+
+    /// 要素名・属性の接頭辞のうち祖先要素で宣言されたものの名前空間を記録
+    fn resolve_namespaces(&mut self, scopes: &[Vec<(String, String)>]) {
+        let declared: Vec<(String, String)> = self.declared_namespaces().collect();
+        let prefixes = self.prefix.iter().map(String::as_str).chain(
+            self.attributes
+                .iter()
+                .filter_map(|(key, _)| key.split_once(':').map(|(prefix, _)| prefix)),
+        );
+        for prefix in prefixes {
+            if matches!(prefix, "xmlns" | "xml")
+                || declared.iter().any(|(p, _)| p == prefix)
+                || self.namespaces.iter().any(|(p, _)| p == prefix)
+            {
+                continue;
+            }
+            let uri = scopes
+                .iter()
+                .rev()
+                .flat_map(|scope| scope.iter())
+                .find(|(p, _)| p == prefix)
+                .map(|(_, uri)| uri.clone());
+            if let Some(uri) = uri {
+                self.namespaces.push((prefix.to_string(), uri));
+            }
+        }
+    }
+
+    /// 接頭辞付きの要素名
+    pub fn qualified_name(&self) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{}:{}", prefix, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// 属性値を取得
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// 属性値を文字列として取得
+    pub fn attr_string(&self, key: &str) -> Option<String> {
+        self.attr(key).map(|v| v.to_string())
+    }
+
+    /// 属性値を数値として取得（"INF"等の非数値はNone）
+    pub fn attr_f64(&self, key: &str) -> Option<f64> {
+        self.attr(key)
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| v.is_finite())
+    }
+
+    /// 指定名の最初の子要素を取得
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// 指定名の子要素を列挙
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// 子要素のFeature/Propertyから指定ラベルの値を取得
+    pub fn feature_property(&self, label: &str) -> Option<&str> {
+        self.children_named("Feature")
+            .flat_map(|f| f.children_named("Property"))
+            .find(|p| p.attr("label") == Some(label))
+            .and_then(|p| p.attr("value"))
+    }
+
+    /// テキストを空白区切りの数値列として解析
+    ///
+    /// 改行で符号と数字が分断された値（"-\n70.029"）も1つの値として扱います。
+    pub fn text_numbers(&self) -> Result<Vec<f64>, LandXMLError> {
+        parse_number_list(&self.text)
+    }
+
+    /// テキストを2次元点列（2値ずつ）として解析
+    ///
+    /// 末尾の対にならない値は無視します。
+    pub fn text_points_2d(&self) -> Result<Vec<Point2D>, LandXMLError> {
+        Ok(self
+            .text_numbers()?
+            .chunks_exact(2)
+            .map(|pair| Point2D {
+                x: pair[0],
+                y: pair[1],
+            })
+            .collect())
+    }
+
+    /// PointType要素（"north east" / "north east elev"）を解析
+    pub fn point(&self) -> Result<(Point2D, Option<f64>), LandXMLError> {
+        let values = self.text_numbers()?;
+        if values.len() < 2 {
+            return Err(LandXMLError::InvalidFormat {
+                message: format!(
+                    "{} requires at least 2 coordinate values, got '{}'",
+                    self.name, self.text
+                ),
+            });
+        }
+        Ok((
+            Point2D {
+                x: values[0],
+                y: values[1],
+            },
+            values.get(2).copied(),
+        ))
+    }
+
+    /// 子要素のPntList2Dをすべて点列として解析
+    pub fn pnt_lists_2d(&self) -> Result<Vec<Vec<Point2D>>, LandXMLError> {
+        self.children_named("PntList2D")
+            .map(|list| list.text_points_2d())
+            .collect()
+    }
+
+    /// 必須の子要素を取得
+    pub fn required_child(&self, name: &str) -> Result<&XmlElement, LandXMLError> {
+        self.child(name)
+            .ok_or_else(|| LandXMLError::MissingElement {
+                element: format!("{}/{}", self.name, name),
+            })
+    }
+}
+
+/// 読み取り開始位置から次の開始タグ '<' の位置を求める（trim_textで読み飛ばした空白を除く）
+fn tag_offset(content: &str, from: usize) -> usize {
+    content
+        .get(from..)
+        .and_then(|rest| rest.find('<'))
+        .map(|i| from + i)
+        .unwrap_or(from)
+}
+
+// ============================================================================
+// 文字コード判定
+// ============================================================================
+
+/// ファイルを読み込み、文字コードを判定してUTF-8文字列に変換
+pub(crate) fn read_document<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<(String, &'static Encoding), LandXMLError> {
+    let bytes = std::fs::read(path)?;
+    decode_document(&bytes)
+}
+
+/// バイト列の文字コードを判定してUTF-8文字列に変換
+///
+/// BOM（UTF-8 / UTF-16LE / UTF-16BE）を優先し、なければXML宣言のencoding属性、
+/// いずれもなければUTF-8として扱います。CP932系の表記はShift_JIS（Windows-31J）として扱います。
+pub(crate) fn decode_document(bytes: &[u8]) -> Result<(String, &'static Encoding), LandXMLError> {
+    let (encoding, bom_length) = match Encoding::for_bom(bytes) {
+        Some(found) => found,
+        None => (declared_encoding(bytes)?.unwrap_or(UTF_8), 0),
+    };
+    let content = encoding
+        .decode_without_bom_handling_and_without_replacement(&bytes[bom_length..])
+        .ok_or_else(|| LandXMLError::InvalidFormat {
+            message: format!("Invalid {} byte sequence", encoding.name()),
+        })?;
+    Ok((content.into_owned(), encoding))
+}
+
+/// XML宣言のencoding属性から文字コードを取得
+fn declared_encoding(bytes: &[u8]) -> Result<Option<&'static Encoding>, LandXMLError> {
+    if !bytes.starts_with(b"<?xml") {
+        return Ok(None);
+    }
+    // XML宣言はASCII範囲のみで記述される
+    let end = bytes
+        .windows(2)
+        .position(|w| w == b"?>")
+        .unwrap_or(bytes.len());
+    let declaration = String::from_utf8_lossy(&bytes[..end]);
+    let Some(rest) = declaration
+        .find("encoding")
+        .map(|i| declaration[i + "encoding".len()..].trim_start())
+    else {
+        return Ok(None);
+    };
+    let label = rest
+        .strip_prefix('=')
+        .map(str::trim_start)
+        .and_then(|value| {
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            value[1..].split(quote).next()
+        })
+        .ok_or_else(|| LandXMLError::InvalidFormat {
+            message: "Malformed encoding declaration".to_string(),
+        })?;
+
+    let encoding = match label.to_ascii_lowercase().as_str() {
+        "cp932" | "ms932" | "windows-932" => Some(SHIFT_JIS),
+        other => Encoding::for_label(other.as_bytes()),
+    };
+    encoding
+        .map(Some)
+        .ok_or_else(|| LandXMLError::InvalidFormat {
+            message: format!("Unsupported encoding '{}'", label),
+        })
+}
+
+// ============================================================================
+// XML出力
+// ============================================================================
+
+impl XmlElement {
+    /// 要素を作成
+    pub fn new(name: impl Into<String>) -> Self {
+        let name: String = name.into();
+        match name.split_once(':') {
+            Some((prefix, local)) => Self {
+                name: local.to_string(),
+                prefix: Some(prefix.to_string()),
+                ..Default::default()
+            },
+            None => Self {
+                name,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// 属性を追加
+    pub fn with_attr(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.attributes.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// 値がある場合のみ属性を追加
+    pub fn with_opt_attr(self, key: &str, value: Option<impl Into<String>>) -> Self {
+        match value {
+            Some(value) => self.with_attr(key, value),
+            None => self,
+        }
+    }
+
+    /// 子要素を追加
+    pub fn with_child(mut self, child: XmlElement) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// 子要素をまとめて追加
+    pub fn with_children(mut self, children: impl IntoIterator<Item = XmlElement>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// テキスト内容を設定
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// 要素ツリーを書き出し
+    ///
+    /// 子要素もテキストもない要素は空要素タグ（`<X/>`）として出力します。
+    pub fn write_to<W: std::io::Write>(&self, writer: &mut Writer<W>) -> Result<(), LandXMLError> {
+        let name = self.qualified_name();
+        let mut start = BytesStart::new(name.as_str());
+        for (key, value) in &self.attributes {
+            start.push_attribute((key.as_str(), value.as_str()));
+        }
+
+        if self.children.is_empty() && self.text.is_empty() {
+            writer.write_event(Event::Empty(start))?;
+            return Ok(());
+        }
+
+        writer.write_event(Event::Start(start))?;
+        if !self.text.is_empty() {
+            writer.write_event(Event::Text(BytesText::new(&self.text)))?;
+        }
+        for child in &self.children {
+            child.write_to(writer)?;
+        }
+        writer.write_event(Event::End(BytesEnd::new(name.as_str())))?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 未知要素の保持
+// ============================================================================
+
+impl XmlElement {
+    /// 既知の属性・子要素名以外をExtensionsとして取り出し
+    pub fn extensions(&self, known_attributes: &[&str], known_children: &[&str]) -> Extensions {
+        self.extensions_by(known_attributes, |_, child| {
+            known_children.contains(&child.name.as_str())
+        })
+    }
+
+    /// 既知の属性以外と、判定関数が既知としない子要素をExtensionsとして取り出し
+    ///
+    /// 判定関数には子要素の位置と要素が渡されます。
+    pub fn extensions_by(
+        &self,
+        known_attributes: &[&str],
+        is_known_child: impl Fn(usize, &XmlElement) -> bool,
+    ) -> Extensions {
+        Extensions {
+            attributes: self
+                .attributes
+                .iter()
+                .filter(|(key, _)| !known_attributes.contains(&key.as_str()))
+                .cloned()
+                .collect(),
+            elements: self
+                .children
+                .iter()
+                .enumerate()
+                .filter(|(position, child)| !is_known_child(*position, child))
+                .map(|(position, child)| ExtensionElement {
+                    position,
+                    element: child.to_raw(),
+                })
+                .collect(),
+        }
+    }
+
+    /// 指定位置の子要素が同名の子要素のうち最初のものかどうか
+    pub fn is_first_named(&self, position: usize) -> bool {
+        let name = &self.children[position].name;
+        self.children.iter().position(|c| &c.name == name) == Some(position)
+    }
+
+    /// Featureの全Propertyが指定ラベルのいずれかかどうか
+    pub fn is_feature_with_only(&self, labels: &[&str]) -> bool {
+        self.name == "Feature"
+            && !self.children.is_empty()
+            && self.children.iter().all(|p| {
+                p.name == "Property" && p.attr("label").is_some_and(|l| labels.contains(&l))
+            })
+    }
+
+    /// 属性のないFeatureで、子要素がlabel/value属性のみのPropertyであるかどうか
+    pub fn is_property_feature(&self) -> bool {
+        self.name == "Feature"
+            && self.attributes.is_empty()
+            && !self.children.is_empty()
+            && self.children.iter().all(|p| {
+                p.name == "Property"
+                    && p.children.is_empty()
+                    && p.attr("label").is_some()
+                    && p.attributes
+                        .iter()
+                        .all(|(k, _)| k == "label" || k == "value")
+            })
+    }
+
+    /// 公開用の要素表現に変換
+    ///
+    /// 要素名は接頭辞付きとし、祖先要素で宣言された名前空間は要素単独で
+    /// 出力できるようxmlns属性として追加します。
+    pub fn to_raw(&self) -> RawElement {
+        self.to_raw_within(&[])
+    }
+
+    fn to_raw_within(&self, declared: &[&str]) -> RawElement {
+        let mut attributes = self.attributes.clone();
+        let mut declared = declared.to_vec();
+        declared.extend(
+            self.attributes
+                .iter()
+                .filter_map(|(k, _)| k.strip_prefix("xmlns:")),
+        );
+        for (prefix, uri) in &self.namespaces {
+            if !declared.contains(&prefix.as_str()) {
+                attributes.push((format!("xmlns:{}", prefix), uri.clone()));
+                declared.push(prefix);
+            }
+        }
+        RawElement {
+            name: self.qualified_name(),
+            attributes,
+            children: self
+                .children
+                .iter()
+                .map(|child| child.to_raw_within(&declared))
+                .collect(),
+            text: self.text.clone(),
+        }
+    }
+
+    /// 公開用の要素表現から変換
+    pub fn from_raw(raw: &RawElement) -> Self {
+        let mut element = Self::new(raw.name.as_str());
+        element.attributes = raw.attributes.clone();
+        element.children = raw.children.iter().map(XmlElement::from_raw).collect();
+        element.text = raw.text.clone();
+        element
+    }
+
+    /// 保持していた未知の属性・子要素を元の位置へ戻す
+    ///
+    /// 子要素は元の位置（既知要素の数が変わった場合は末尾まで）に挿入します。
+    pub fn with_extensions(mut self, extensions: &Extensions) -> Self {
+        self.attributes
+            .extend(extensions.attributes.iter().cloned());
+        let mut elements: Vec<&ExtensionElement> = extensions.elements.iter().collect();
+        elements.sort_by_key(|e| e.position);
+        for extension in elements {
+            let position = extension.position.min(self.children.len());
+            self.children
+                .insert(position, XmlElement::from_raw(&extension.element));
+        }
+        self
+    }
+}
+
+/// 空白区切りの数値列を解析
+pub(crate) fn parse_number_list(text: &str) -> Result<Vec<f64>, LandXMLError> {
+    let mut values = Vec::new();
+    let mut pending_sign: Option<&str> = None;
+
+    for token in text.split_whitespace() {
+        if token == "-" || token == "+" {
+            pending_sign = Some(token);
+            continue;
+        }
+        let joined;
+        let token = match pending_sign.take() {
+            Some(sign) => {
+                joined = format!("{}{}", sign, token);
+                joined.as_str()
+            }
+            None => token,
+        };
+        let value = token
+            .parse::<f64>()
+            .map_err(|e| LandXMLError::ParseError(format!("Invalid number '{}': {}", token, e)))?;
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tree() {
+        let xml = r#"<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" version="1.2">
+            <CgPoints name="pts"><CgPoint name="P1">1.0 2.0 3.0</CgPoint></CgPoints>
+            <Feature><Property label="differTP" value="-0.8402"/></Feature>
+        </LandXML>"#;
+        let root = XmlElement::parse_str(xml).unwrap();
+        assert_eq!(root.name, "LandXML");
+        assert_eq!(root.attr("version"), Some("1.2"));
+        let point = root.child("CgPoints").unwrap().child("CgPoint").unwrap();
+        assert_eq!(point.text_numbers().unwrap(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(root.feature_property("differTP"), Some("-0.8402"));
+    }
+
+    #[test]
+    fn test_decode_document() {
+        let (text, encoding) = decode_document("<a>詳細</a>".as_bytes()).unwrap();
+        assert_eq!((text.as_str(), encoding), ("<a>詳細</a>", UTF_8));
+
+        let xml = "<?xml version=\"1.0\" encoding=\"CP932\"?><a>①詳細</a>";
+        let (bytes, _, _) = SHIFT_JIS.encode(xml);
+        let (text, encoding) = decode_document(&bytes).unwrap();
+        assert_eq!((text.as_str(), encoding), (xml, SHIFT_JIS));
+
+        let xml = "<?xml version='1.0' encoding='EUC-JP'?><a>測点</a>";
+        let (bytes, _, _) = encoding_rs::EUC_JP.encode(xml);
+        let (text, encoding) = decode_document(&bytes).unwrap();
+        assert_eq!((text.as_str(), encoding), (xml, encoding_rs::EUC_JP));
+
+        // BOMは宣言より優先
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-16\"?><a>測点</a>";
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+        let (text, encoding) = decode_document(&bytes).unwrap();
+        assert_eq!((text.as_str(), encoding), (xml, encoding_rs::UTF_16LE));
+
+        let (bytes, _, _) = SHIFT_JIS.encode("<?xml version=\"1.0\"?><a>詳細</a>");
+        assert!(decode_document(&bytes).is_err());
+        assert!(decode_document(b"<?xml version=\"1.0\" encoding=\"x-unknown\"?><a/>").is_err());
+    }
+
+    #[test]
+    fn test_number_list_with_split_sign() {
+        let values = parse_number_list("-90.0 90.966 -\n70.029 91.193").unwrap();
+        assert_eq!(values, vec![-90.0, 90.966, -70.029, 91.193]);
+        assert!(parse_number_list("1.0 abc").is_err());
+    }
+
+    #[test]
+    fn test_write_tree() {
+        let element = XmlElement::new("CgPoints")
+            .with_attr("name", "A&B")
+            .with_opt_attr("desc", None::<String>)
+            .with_child(XmlElement::new("CgPoint").with_text("1.0 2.0"))
+            .with_child(XmlElement::new("Feature"));
+
+        let mut writer = Writer::new(Vec::new());
+        element.write_to(&mut writer).unwrap();
+        let xml = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(
+            xml,
+            r#"<CgPoints name="A&amp;B"><CgPoint>1.0 2.0</CgPoint><Feature/></CgPoints>"#
+        );
+        assert_eq!(XmlElement::parse_str(&xml).unwrap(), element);
+    }
+
+    #[test]
+    fn test_extensions_round_trip() {
+        let xml = r#"<Alignment name="A" vendor:id="7">
+            <StaEquation staBack="1.0" staAhead="2.0"/>
+            <CoordGeom/>
+            <AlignPIs><AlignPI/></AlignPIs>
+            <Profile/>
+            <Feature><Property label="x" value="1"/></Feature>
+        </Alignment>"#;
+        let element = XmlElement::parse_str(xml).unwrap();
+        let extensions = element.extensions(&["name"], &["CoordGeom", "Profile"]);
+        assert_eq!(extensions.attribute("vendor:id"), Some("7"));
+        assert_eq!(extensions.elements.len(), 3);
+        assert!(extensions.has_feature_property("x"));
+
+        let rebuilt = XmlElement::new("Alignment")
+            .with_attr("name", "A")
+            .with_child(XmlElement::new("CoordGeom"))
+            .with_child(XmlElement::new("Profile"))
+            .with_extensions(&extensions);
+        assert_eq!(rebuilt, element);
+    }
+
+    #[test]
+    fn test_namespaced_extensions() {
+        let xml = r#"<LandXML xmlns="http://www.landxml.org/schema/LandXML-1.2" xmlns:v="urn:vendor">
+            <Alignment name="A" v:id="7">
+                <v:Custom v:kind="x"><v:Item>1</v:Item></v:Custom>
+                <w:Other xmlns:w="urn:other"/>
+            </Alignment>
+        </LandXML>"#;
+        let root = XmlElement::parse_str(xml).unwrap();
+        let alignment = root.child("Alignment").unwrap();
+        let extensions = alignment.extensions(&["name"], &[]);
+        assert_eq!(extensions.attribute("v:id"), Some("7"));
+
+        // 接頭辞付きの要素名と、祖先で宣言された名前空間を保持
+        let custom = &extensions.elements[0].element;
+        assert_eq!(custom.name, "v:Custom");
+        assert_eq!(
+            custom.attributes,
+            vec![
+                ("v:kind".to_string(), "x".to_string()),
+                ("xmlns:v".to_string(), "urn:vendor".to_string()),
+            ]
+        );
+        assert_eq!(custom.children[0].name, "v:Item");
+        assert!(custom.children[0].attributes.is_empty());
+        let other = &extensions.elements[1].element;
+        assert_eq!(other.name, "w:Other");
+        assert_eq!(other.attributes.len(), 1);
+
+        let mut writer = Writer::new(Vec::new());
+        XmlElement::from_raw(custom).write_to(&mut writer).unwrap();
+        assert_eq!(
+            String::from_utf8(writer.into_inner()).unwrap(),
+            r#"<v:Custom v:kind="x" xmlns:v="urn:vendor"><v:Item>1</v:Item></v:Custom>"#
+        );
+    }
+}